@@ -0,0 +1,413 @@
+use crate::coding::linear_code::{
+    format_word, hamming_parity_check, parse_word, reduce_rows, reed_solomon_generator, weight,
+    LinearCode, SyndromeTable,
+};
+use crate::matrix::lattice::{matrix_of, rows_of};
+use crate::matrix::normal_form::format_rows;
+use crate::matrix::{IcedMatrix, IcedMatrixMessage};
+use iced::{
+    button, text_input, Align, Button, Column, Element, Length, Radio, Row, Text, TextInput,
+};
+use num_bigint::BigInt;
+
+pub mod linear_code;
+
+/// longer syndrome tables are cut off in the view
+const SHOWN_LEADERS: usize = 64;
+
+fn to_big(rows: &[Vec<u64>]) -> Vec<Vec<BigInt>> {
+    rows.iter()
+        .map(|row| row.iter().map(|&x| BigInt::from(x)).collect())
+        .collect()
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum MatrixKind {
+    #[default]
+    Generator,
+    ParityCheck,
+}
+
+impl std::fmt::Display for MatrixKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                MatrixKind::Generator => "Generator matrix",
+                MatrixKind::ParityCheck => "Parity-check matrix",
+            }
+        )
+    }
+}
+
+impl From<MatrixKind> for String {
+    fn from(kind: MatrixKind) -> Self {
+        kind.to_string()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum CodingMessage {
+    IcedMatrixMessage(IcedMatrixMessage),
+    KindSelected(MatrixKind),
+    EditPrime(String),
+    EditPreset(String),
+    Hamming,
+    ReedSolomon,
+    Analyse,
+    EditWord(String),
+    Encode,
+    Decode,
+}
+
+/// linear codes over GF(p) from a generator or parity-check matrix
+#[derive(Debug, Clone, Default)]
+pub struct IcedCoding {
+    matrix: IcedMatrix,
+    kind: MatrixKind,
+    prime_text: String,
+    prime_input: text_input::State,
+    preset_text: String,
+    preset_input: text_input::State,
+    hamming_button: button::State,
+    reed_solomon_button: button::State,
+    analyse_button: button::State,
+    word_text: String,
+    word_input: text_input::State,
+    encode_button: button::State,
+    decode_button: button::State,
+    code: Option<LinearCode>,
+    table: Option<SyndromeTable>,
+    /// systematic form, weights and distance of the analysed code
+    notes: Vec<String>,
+    /// the last encoded or decoded word
+    output: Vec<String>,
+    error_message: Option<String>,
+}
+
+impl IcedCoding {
+    pub fn new() -> Self {
+        Self {
+            matrix: IcedMatrix::new(),
+            ..Self::default()
+        }
+    }
+
+    pub fn update(&mut self, message: CodingMessage) {
+        match message {
+            CodingMessage::IcedMatrixMessage(matrix_message) => self.matrix.update(matrix_message),
+            CodingMessage::KindSelected(kind) => self.kind = kind,
+            CodingMessage::EditPrime(content) => self.prime_text = content,
+            CodingMessage::EditPreset(content) => self.preset_text = content,
+            CodingMessage::EditWord(content) => self.word_text = content,
+            CodingMessage::Hamming | CodingMessage::ReedSolomon => {
+                self.error_message = None;
+                match self.preset(matches!(message, CodingMessage::Hamming)) {
+                    Ok(()) => self.analyse(),
+                    Err(message) => self.error_message = Some(message),
+                }
+            }
+            CodingMessage::Analyse => {
+                self.error_message = None;
+                self.analyse();
+            }
+            CodingMessage::Encode | CodingMessage::Decode => {
+                self.error_message = None;
+                self.output.clear();
+                let result = if let CodingMessage::Encode = message {
+                    self.encode()
+                } else {
+                    self.decode()
+                };
+                if let Err(message) = result {
+                    self.error_message = Some(message);
+                }
+            }
+        }
+    }
+
+    /// 2 if left empty
+    fn prime(&self) -> Result<u64, String> {
+        if self.prime_text.trim().is_empty() {
+            return Ok(2);
+        }
+        self.prime_text
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| String::from("p must be a prime"))
+    }
+
+    /// "r" for Hamming codes, "n k" for Reed-Solomon codes, written into the matrix input
+    fn preset(&mut self, hamming: bool) -> Result<(), String> {
+        let prime = self.prime()?;
+        let numbers = self
+            .preset_text
+            .split_whitespace()
+            .map(|x| x.parse::<usize>())
+            .collect::<Result<Vec<usize>, _>>()
+            .map_err(|_| String::from("the preset needs whole numbers"))?;
+        let (rows, kind) = match (hamming, numbers.as_slice()) {
+            (true, [r]) => (hamming_parity_check(*r, prime)?, MatrixKind::ParityCheck),
+            (false, [n, k]) => (
+                reed_solomon_generator(*n, *k, prime)?,
+                MatrixKind::Generator,
+            ),
+            (true, _) => return Err(String::from("Hamming codes take r")),
+            (false, _) => return Err(String::from("Reed-Solomon codes take n k")),
+        };
+        self.matrix = IcedMatrix::from_matrix(matrix_of(&to_big(&rows)));
+        self.kind = kind;
+        Ok(())
+    }
+
+    fn analyse(&mut self) {
+        self.code = None;
+        self.table = None;
+        self.notes.clear();
+        self.output.clear();
+        if let Err(message) = self.build() {
+            self.error_message = Some(message);
+        }
+    }
+
+    fn build(&mut self) -> Result<(), String> {
+        if !self.matrix.is_initialized() {
+            return Err(String::from("Matrix not initialized"));
+        }
+        let prime = self.prime()?;
+        let rows = reduce_rows(&rows_of(&self.matrix.get_matrix_unchecked())?, prime);
+        let code = match self.kind {
+            MatrixKind::Generator => LinearCode::from_generator(&rows, prime)?,
+            MatrixKind::ParityCheck => LinearCode::from_parity_check(&rows, prime)?,
+        };
+        let (systematic, order) = code.systematic();
+        self.notes.push(format!(
+            "{}, {} codewords",
+            code.name(),
+            BigInt::from(prime).pow(code.dimension() as u32)
+        ));
+        self.notes.push(format!(
+            "Systematic generator [I | A] = {}",
+            format_rows(&to_big(&systematic))
+        ));
+        if order.iter().enumerate().any(|(i, &c)| i != c) {
+            let order: Vec<String> = order.iter().map(|c| (c + 1).to_string()).collect();
+            self.notes.push(format!(
+                "    with the columns in the order {}",
+                order.join(" ")
+            ));
+        }
+        self.notes.push(format!(
+            "Parity check H = {}",
+            format_rows(&to_big(&code.parity_check))
+        ));
+
+        match code.weight_distribution() {
+            Ok(distribution) => {
+                let terms: Vec<String> = distribution
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &count)| count > 0)
+                    .map(|(i, count)| format!("A_{} = {}", i, count))
+                    .collect();
+                self.notes
+                    .push(format!("Weight distribution: {}", terms.join(", ")));
+                if let Some(d) = LinearCode::minimum_distance(&distribution) {
+                    let mut line = format!(
+                        "Minimum distance {}, detects {} and corrects {} errors",
+                        d,
+                        d - 1,
+                        (d - 1) / 2
+                    );
+                    if d == code.length() - code.dimension() + 1 {
+                        line.push_str(", MDS");
+                    }
+                    if code.is_perfect(d) {
+                        line.push_str(", perfect");
+                    }
+                    self.notes.push(line);
+                }
+            }
+            Err(message) => self.notes.push(message),
+        }
+
+        match code.syndrome_table() {
+            Ok(table) => {
+                self.notes.push(format!(
+                    "Syndrome table, {} coset leaders{}",
+                    table.leaders.len(),
+                    if table.complete { "" } else { ", incomplete" }
+                ));
+                self.notes
+                    .extend(
+                        table
+                            .leaders
+                            .iter()
+                            .take(SHOWN_LEADERS)
+                            .map(|(syndrome, leader)| {
+                                format!(
+                                    "    {} -> {} (weight {})",
+                                    format_word(syndrome, prime),
+                                    format_word(leader, prime),
+                                    weight(leader)
+                                )
+                            }),
+                    );
+                if table.leaders.len() > SHOWN_LEADERS {
+                    self.notes.push(format!(
+                        "    ... {} more",
+                        table.leaders.len() - SHOWN_LEADERS
+                    ));
+                }
+                self.table = Some(table);
+            }
+            Err(message) => self.notes.push(message),
+        }
+        self.code = Some(code);
+        Ok(())
+    }
+
+    fn encode(&mut self) -> Result<(), String> {
+        let code = self
+            .code
+            .as_ref()
+            .ok_or_else(|| String::from("Please analyse a code first"))?;
+        let message = parse_word(&self.word_text, code.prime)?;
+        let codeword = code.encode(&message)?;
+        self.output.push(format!(
+            "{} encodes to {}",
+            format_word(&message, code.prime),
+            format_word(&codeword, code.prime)
+        ));
+        Ok(())
+    }
+
+    fn decode(&mut self) -> Result<(), String> {
+        let (code, table) = match (&self.code, &self.table) {
+            (Some(code), Some(table)) => (code, table),
+            _ => {
+                return Err(String::from(
+                    "Please analyse a code with a syndrome table first",
+                ))
+            }
+        };
+        let received = parse_word(&self.word_text, code.prime)?;
+        let decoding = code.decode(table, &received)?;
+        let prime = code.prime;
+        self.output.push(format!(
+            "Syndrome {}, error {} ({} symbols corrected)",
+            format_word(&decoding.syndrome, prime),
+            format_word(&decoding.error, prime),
+            weight(&decoding.error)
+        ));
+        self.output.push(format!(
+            "Codeword {}, message {}",
+            format_word(&decoding.codeword, prime),
+            format_word(&decoding.message, prime)
+        ));
+        Ok(())
+    }
+
+    pub fn view(&mut self) -> Element<'_, CodingMessage> {
+        let title = Text::new("Linear codes").size(30).color([0.0, 0.0, 0.0]);
+
+        let selected = self.kind;
+        let kinds = [MatrixKind::Generator, MatrixKind::ParityCheck]
+            .iter()
+            .fold(Column::new().spacing(5), |column, &option| {
+                column.push(Radio::new(
+                    option,
+                    option,
+                    Some(selected),
+                    CodingMessage::KindSelected,
+                ))
+            });
+
+        let settings = Column::new()
+            .spacing(5)
+            .width(Length::Units(350))
+            .push(Text::new("p, 2 if empty"))
+            .push(TextInput::new(
+                &mut self.prime_input,
+                "2",
+                &self.prime_text,
+                CodingMessage::EditPrime,
+            ))
+            .push(Text::new("Hamming: r    Reed-Solomon: n k"))
+            .push(TextInput::new(
+                &mut self.preset_input,
+                "",
+                &self.preset_text,
+                CodingMessage::EditPreset,
+            ))
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .push(
+                        Button::new(&mut self.hamming_button, Text::new("Hamming"))
+                            .on_press(CodingMessage::Hamming),
+                    )
+                    .push(
+                        Button::new(&mut self.reed_solomon_button, Text::new("Reed-Solomon"))
+                            .on_press(CodingMessage::ReedSolomon),
+                    ),
+            );
+
+        let controls = Row::new()
+            .padding(20)
+            .spacing(20)
+            .align_items(Align::Center)
+            .push(kinds)
+            .push(settings)
+            .push(
+                Button::new(&mut self.analyse_button, Text::new("Analyse"))
+                    .on_press(CodingMessage::Analyse),
+            );
+
+        let word = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new("Word: "))
+            .push(
+                TextInput::new(
+                    &mut self.word_input,
+                    "1011",
+                    &self.word_text,
+                    CodingMessage::EditWord,
+                )
+                .width(Length::Units(250)),
+            )
+            .push(
+                Button::new(&mut self.encode_button, Text::new("Encode"))
+                    .on_press(CodingMessage::Encode),
+            )
+            .push(
+                Button::new(&mut self.decode_button, Text::new("Decode"))
+                    .on_press(CodingMessage::Decode),
+            );
+
+        let lines = |lines: &[String]| {
+            lines.iter().fold(Column::new().spacing(5), |col, line| {
+                col.push(Text::new(line))
+            })
+        };
+
+        let maybe_error = match &self.error_message {
+            None => Text::new(""),
+            Some(message) => Text::new(message).color([0.921, 0.039, 0.039]),
+        };
+
+        Column::new()
+            .align_items(Align::Center)
+            .spacing(20)
+            .push(title)
+            .push(controls)
+            .push(self.matrix.view().map(CodingMessage::IcedMatrixMessage))
+            .push(word)
+            .push(lines(&self.output))
+            .push(lines(&self.notes))
+            .push(maybe_error)
+            .into()
+    }
+}
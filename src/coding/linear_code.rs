@@ -0,0 +1,488 @@
+use crate::group_theory::number_theory::{is_prime, pow_mod_u64, primitive_root};
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::ToPrimitive;
+use std::collections::HashMap;
+
+/// entries are u64 residues, products stay far below 2^64
+pub const FIELD_LIMIT: u64 = 251;
+/// p^k codewords are enumerated for the weight distribution
+pub const CODEWORD_LIMIT: u64 = 1 << 16;
+/// p^(n-k) syndromes get a coset leader each
+pub const SYNDROME_LIMIT: u64 = 1 << 12;
+/// error patterns tried before the syndrome table is given up as incomplete
+const PATTERN_LIMIT: usize = 1_000_000;
+
+pub fn check_field(prime: u64) -> Result<(), String> {
+    if prime > FIELD_LIMIT || !is_prime(&BigInt::from(prime)) {
+        Err(format!(
+            "p = {} has to be a prime up to {}",
+            prime, FIELD_LIMIT
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn inverse(a: u64, prime: u64) -> u64 {
+    pow_mod_u64(a, prime - 2, prime)
+}
+
+/// the entries of an integer matrix as residues mod p
+pub fn reduce_rows(rows: &[Vec<BigInt>], prime: u64) -> Vec<Vec<u64>> {
+    let modulo = BigInt::from(prime);
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .map(|x| x.mod_floor(&modulo).to_u64().unwrap_or(0))
+                .collect()
+        })
+        .collect()
+}
+
+/// reduced row echelon form without the zero rows, with the pivot columns
+pub fn row_echelon(rows: &[Vec<u64>], prime: u64) -> (Vec<Vec<u64>>, Vec<usize>) {
+    let mut rows = rows.to_vec();
+    let columns = rows.first().map_or(0, |row| row.len());
+    let mut pivots = Vec::new();
+    for j in 0..columns {
+        let r = pivots.len();
+        let pivot = match (r..rows.len()).find(|&i| rows[i][j] != 0) {
+            Some(i) => i,
+            None => continue,
+        };
+        rows.swap(r, pivot);
+        let scale = inverse(rows[r][j], prime);
+        for x in rows[r].iter_mut() {
+            *x = *x * scale % prime;
+        }
+        let pivot_row = rows[r].clone();
+        for (_, row) in rows.iter_mut().enumerate().filter(|(i, _)| *i != r) {
+            let factor = row[j];
+            if factor != 0 {
+                for (x, y) in row.iter_mut().zip(&pivot_row) {
+                    *x = (*x + (prime - factor) * y) % prime;
+                }
+            }
+        }
+        pivots.push(j);
+    }
+    rows.truncate(pivots.len());
+    (rows, pivots)
+}
+
+/// a basis of {x : M x^T = 0}, one vector per non pivot column of the echelon form
+pub fn kernel(echelon: &[Vec<u64>], pivots: &[usize], columns: usize, prime: u64) -> Vec<Vec<u64>> {
+    (0..columns)
+        .filter(|c| !pivots.contains(c))
+        .map(|c| {
+            let mut vector = vec![0; columns];
+            vector[c] = 1;
+            for (row, &pivot) in echelon.iter().zip(pivots) {
+                vector[pivot] = (prime - row[c]) % prime;
+            }
+            vector
+        })
+        .collect()
+}
+
+pub fn weight(word: &[u64]) -> usize {
+    word.iter().filter(|&&x| x != 0).count()
+}
+
+/// "1011" over fields with single digit elements, "1 0 5 3" otherwise
+pub fn format_word(word: &[u64], prime: u64) -> String {
+    let entries: Vec<String> = word.iter().map(|x| x.to_string()).collect();
+    if prime <= 10 {
+        entries.concat()
+    } else {
+        entries.join(" ")
+    }
+}
+
+/// the inverse of format_word, a lone run of digits is split up below p = 10
+pub fn parse_word(text: &str, prime: u64) -> Result<Vec<u64>, String> {
+    let tokens: Vec<&str> = text
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .collect();
+    let entries: Vec<String> = if tokens.len() == 1 && prime <= 10 {
+        tokens[0].chars().map(String::from).collect()
+    } else {
+        tokens.iter().map(|token| token.to_string()).collect()
+    };
+    entries
+        .iter()
+        .map(|entry| match entry.parse::<u64>() {
+            Ok(x) if x < prime => Ok(x),
+            _ => Err(format!("{} is not an element of GF({})", entry, prime)),
+        })
+        .collect()
+}
+
+fn binomial(n: usize, k: usize) -> u128 {
+    (0..k).fold(1u128, |acc, i| acc * (n - i) as u128 / (i + 1) as u128)
+}
+
+/// the parity checks are all nonzero columns of length r with a leading 1
+pub fn hamming_parity_check(r: usize, prime: u64) -> Result<Vec<Vec<u64>>, String> {
+    check_field(prime)?;
+    let syndromes = (prime as u128).checked_pow(r as u32).unwrap_or(u128::MAX);
+    if r < 2 || syndromes > SYNDROME_LIMIT as u128 {
+        return Err(format!(
+            "Hamming codes need r >= 2 and p^r up to {}",
+            SYNDROME_LIMIT
+        ));
+    }
+    let mut columns: Vec<Vec<u64>> = Vec::new();
+    for index in 1..syndromes as u64 {
+        let column: Vec<u64> = (0..r as u32)
+            .rev()
+            .map(|i| index / prime.pow(i) % prime)
+            .collect();
+        if column.iter().find(|&&x| x != 0) == Some(&1) {
+            columns.push(column);
+        }
+    }
+    Ok((0..r)
+        .map(|i| columns.iter().map(|column| column[i]).collect())
+        .collect())
+}
+
+/// evaluations of 1, x, ..., x^(k-1) at 1, a, a^2, ..., a^(n-1) for a primitive root a
+pub fn reed_solomon_generator(n: usize, k: usize, prime: u64) -> Result<Vec<Vec<u64>>, String> {
+    check_field(prime)?;
+    if k == 0 || k > n || n as u64 > prime - 1 {
+        return Err(format!(
+            "Reed-Solomon codes need 1 <= k <= n <= {}",
+            prime - 1
+        ));
+    }
+    let root = primitive_root(&BigInt::from(prime), 1)
+        .and_then(|root| root.to_u64())
+        .ok_or_else(|| String::from("no primitive root found"))?;
+    Ok((0..k as u64)
+        .map(|i| {
+            (0..n as u64)
+                .map(|j| pow_mod_u64(root, i * j, prime))
+                .collect()
+        })
+        .collect())
+}
+
+/// a linear [n, k] code over GF(p), kept as the row reduced generator and its dual
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinearCode {
+    pub prime: u64,
+    /// reduced row echelon form, the message sits in the pivot columns of a codeword
+    pub generator: Vec<Vec<u64>>,
+    pub pivots: Vec<usize>,
+    pub parity_check: Vec<Vec<u64>>,
+}
+
+impl LinearCode {
+    pub fn from_generator(rows: &[Vec<u64>], prime: u64) -> Result<Self, String> {
+        check_field(prime)?;
+        let columns = rows.first().map_or(0, |row| row.len());
+        let (generator, pivots) = row_echelon(rows, prime);
+        if pivots.is_empty() {
+            return Err(String::from("the rows only generate the zero code"));
+        }
+        let parity_check = kernel(&generator, &pivots, columns, prime);
+        Ok(Self {
+            prime,
+            generator,
+            pivots,
+            parity_check,
+        })
+    }
+
+    pub fn from_parity_check(rows: &[Vec<u64>], prime: u64) -> Result<Self, String> {
+        check_field(prime)?;
+        let columns = rows.first().map_or(0, |row| row.len());
+        let (echelon, pivots) = row_echelon(rows, prime);
+        Self::from_generator(&kernel(&echelon, &pivots, columns, prime), prime)
+    }
+
+    pub fn hamming(r: usize, prime: u64) -> Result<Self, String> {
+        Self::from_parity_check(&hamming_parity_check(r, prime)?, prime)
+    }
+
+    pub fn reed_solomon(n: usize, k: usize, prime: u64) -> Result<Self, String> {
+        Self::from_generator(&reed_solomon_generator(n, k, prime)?, prime)
+    }
+
+    pub fn length(&self) -> usize {
+        self.generator[0].len()
+    }
+
+    pub fn dimension(&self) -> usize {
+        self.generator.len()
+    }
+
+    /// "[7, 4]_2"
+    pub fn name(&self) -> String {
+        format!("[{}, {}]_{}", self.length(), self.dimension(), self.prime)
+    }
+
+    /// the generator as [I_k | A] with the pivot columns moved to the front,
+    /// and the original column of every new one
+    pub fn systematic(&self) -> (Vec<Vec<u64>>, Vec<usize>) {
+        let order: Vec<usize> = self
+            .pivots
+            .iter()
+            .cloned()
+            .chain((0..self.length()).filter(|c| !self.pivots.contains(c)))
+            .collect();
+        let rows = self
+            .generator
+            .iter()
+            .map(|row| order.iter().map(|&c| row[c]).collect())
+            .collect();
+        (rows, order)
+    }
+
+    pub fn encode(&self, message: &[u64]) -> Result<Vec<u64>, String> {
+        if message.len() != self.dimension() {
+            return Err(format!(
+                "messages have {} symbols, not {}",
+                self.dimension(),
+                message.len()
+            ));
+        }
+        Ok((0..self.length())
+            .map(|c| {
+                message
+                    .iter()
+                    .zip(&self.generator)
+                    .fold(0, |sum, (m, row)| (sum + m * row[c]) % self.prime)
+            })
+            .collect())
+    }
+
+    /// H x^T, zero exactly on codewords
+    pub fn syndrome(&self, word: &[u64]) -> Vec<u64> {
+        self.parity_check
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(word)
+                    .fold(0, |sum, (h, x)| (sum + h * x) % self.prime)
+            })
+            .collect()
+    }
+
+    /// A_i, the number of codewords of weight i
+    pub fn weight_distribution(&self) -> Result<Vec<u64>, String> {
+        let count = (self.prime as u128)
+            .checked_pow(self.dimension() as u32)
+            .unwrap_or(u128::MAX);
+        if count > CODEWORD_LIMIT as u128 {
+            return Err(format!(
+                "{} has more than {} codewords to enumerate",
+                self.name(),
+                CODEWORD_LIMIT
+            ));
+        }
+        let mut distribution = vec![0u64; self.length() + 1];
+        let mut message = vec![0u64; self.dimension()];
+        for _ in 0..count {
+            distribution[weight(&self.encode(&message)?)] += 1;
+            // count up in base p
+            for symbol in message.iter_mut() {
+                *symbol = (*symbol + 1) % self.prime;
+                if *symbol != 0 {
+                    break;
+                }
+            }
+        }
+        Ok(distribution)
+    }
+
+    /// the smallest nonzero weight
+    pub fn minimum_distance(distribution: &[u64]) -> Option<usize> {
+        (1..distribution.len()).find(|&i| distribution[i] > 0)
+    }
+
+    /// p^k times the size of a ball of radius t equals p^n
+    pub fn is_perfect(&self, minimum_distance: usize) -> bool {
+        let t = (minimum_distance - 1) / 2;
+        let ball: u128 = (0..=t)
+            .map(|i| binomial(self.length(), i) * ((self.prime - 1) as u128).pow(i as u32))
+            .sum();
+        (self.prime as u128).checked_pow((self.length() - self.dimension()) as u32) == Some(ball)
+    }
+
+    /// a lightest error pattern for every syndrome, found by going through the patterns by weight
+    pub fn syndrome_table(&self) -> Result<SyndromeTable, String> {
+        let n = self.length();
+        let syndromes = (self.prime as u128)
+            .checked_pow((n - self.dimension()) as u32)
+            .unwrap_or(u128::MAX);
+        if syndromes > SYNDROME_LIMIT as u128 {
+            return Err(format!(
+                "{} has more than {} syndromes",
+                self.name(),
+                SYNDROME_LIMIT
+            ));
+        }
+        let mut table = SyndromeTable {
+            leaders: Vec::new(),
+            index: HashMap::new(),
+            complete: false,
+        };
+        let mut tried = 0;
+        'weights: for w in 0..=n {
+            // positions of the nonzero symbols, in lexicographic order
+            let mut positions: Vec<usize> = (0..w).collect();
+            loop {
+                let mut values = vec![1u64; w];
+                loop {
+                    let mut error = vec![0u64; n];
+                    for (&position, &value) in positions.iter().zip(&values) {
+                        error[position] = value;
+                    }
+                    let syndrome = self.syndrome(&error);
+                    if !table.index.contains_key(&syndrome) {
+                        table.index.insert(syndrome.clone(), table.leaders.len());
+                        table.leaders.push((syndrome, error));
+                        if table.leaders.len() as u128 == syndromes {
+                            table.complete = true;
+                            break 'weights;
+                        }
+                    }
+                    tried += 1;
+                    if tried > PATTERN_LIMIT {
+                        break 'weights;
+                    }
+                    match values.iter().position(|&v| v + 1 < self.prime) {
+                        Some(i) => {
+                            values[i] += 1;
+                            values[..i].iter_mut().for_each(|v| *v = 1);
+                        }
+                        None => break,
+                    }
+                }
+                match (0..w).rev().find(|&i| positions[i] < n - w + i) {
+                    Some(i) => {
+                        positions[i] += 1;
+                        for j in i + 1..w {
+                            positions[j] = positions[j - 1] + 1;
+                        }
+                    }
+                    None => break,
+                }
+            }
+        }
+        Ok(table)
+    }
+
+    /// subtracts the coset leader of the syndrome and reads the message off the pivot columns
+    pub fn decode(&self, table: &SyndromeTable, received: &[u64]) -> Result<Decoding, String> {
+        if received.len() != self.length() {
+            return Err(format!(
+                "words have {} symbols, not {}",
+                self.length(),
+                received.len()
+            ));
+        }
+        let syndrome = self.syndrome(received);
+        let error = table
+            .leader(&syndrome)
+            .ok_or_else(|| {
+                format!(
+                    "no coset leader for syndrome {}",
+                    format_word(&syndrome, self.prime)
+                )
+            })?
+            .to_vec();
+        let codeword: Vec<u64> = received
+            .iter()
+            .zip(&error)
+            .map(|(r, e)| (r + self.prime - e) % self.prime)
+            .collect();
+        let message = self.pivots.iter().map(|&c| codeword[c]).collect();
+        Ok(Decoding {
+            syndrome,
+            error,
+            codeword,
+            message,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyndromeTable {
+    /// (syndrome, coset leader) by increasing leader weight
+    pub leaders: Vec<(Vec<u64>, Vec<u64>)>,
+    index: HashMap<Vec<u64>, usize>,
+    /// false if the pattern limit was hit before every syndrome had a leader
+    pub complete: bool,
+}
+
+impl SyndromeTable {
+    pub fn leader(&self, syndrome: &[u64]) -> Option<&[u64]> {
+        self.index
+            .get(syndrome)
+            .map(|&i| self.leaders[i].1.as_slice())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoding {
+    pub syndrome: Vec<u64>,
+    pub error: Vec<u64>,
+    pub codeword: Vec<u64>,
+    pub message: Vec<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hamming_code_is_perfect_and_corrects_one_error() {
+        let code = LinearCode::hamming(3, 2).unwrap();
+        assert_eq!((code.length(), code.dimension()), (7, 4));
+        let distribution = code.weight_distribution().unwrap();
+        assert_eq!(LinearCode::minimum_distance(&distribution), Some(3));
+        assert!(code.is_perfect(3));
+        let table = code.syndrome_table().unwrap();
+        assert!(table.complete);
+        let message = vec![1, 0, 1, 1];
+        let codeword = code.encode(&message).unwrap();
+        assert!(code.syndrome(&codeword).iter().all(|&s| s == 0));
+        for position in 0..7 {
+            let mut received = codeword.clone();
+            received[position] ^= 1;
+            let decoding = code.decode(&table, &received).unwrap();
+            assert_eq!(decoding.codeword, codeword);
+            assert_eq!(decoding.message, message);
+            assert_eq!(weight(&decoding.error), 1);
+        }
+    }
+
+    #[test]
+    fn reed_solomon_code_corrects_two_errors() {
+        let code = LinearCode::reed_solomon(6, 2, 7).unwrap();
+        let distribution = code.weight_distribution().unwrap();
+        assert_eq!(LinearCode::minimum_distance(&distribution), Some(5));
+        let table = code.syndrome_table().unwrap();
+        let message = vec![3, 5];
+        let codeword = code.encode(&message).unwrap();
+        let mut received = codeword.clone();
+        received[1] = (received[1] + 4) % 7;
+        received[4] = (received[4] + 1) % 7;
+        let decoding = code.decode(&table, &received).unwrap();
+        assert_eq!(decoding.codeword, codeword);
+        assert_eq!(decoding.message, message);
+    }
+
+    #[test]
+    fn words_of_the_wrong_length_are_rejected() {
+        let code = LinearCode::hamming(3, 2).unwrap();
+        let table = code.syndrome_table().unwrap();
+        assert!(code.decode(&table, &[1, 0, 1]).is_err());
+        assert!(code.encode(&[1, 0]).is_err());
+    }
+}
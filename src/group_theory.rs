@@ -1,21 +1,93 @@
-use glucose::group_theory::extended_euclidean_algorithm::extended_euclidean_as_dmatrix;
+use crate::group_theory::abelian::{
+    AbelianDecomposition, AbelianGroup, IcedAbelian, IcedAbelianMessage,
+};
+use crate::group_theory::cayley::{
+    table_limit_notice, IcedCayleyMessage, IcedCayleyTable, CAYLEY_TABLE_LIMIT,
+};
+use crate::group_theory::ciphers::{IcedCipherMessage, IcedCiphers};
+use crate::group_theory::continued_fraction::{
+    IcedContinuedFraction, IcedContinuedFractionMessage,
+};
+use crate::group_theory::diffie_hellman::{IcedDiffieHellman, IcedDiffieHellmanMessage};
+use crate::group_theory::dihedral::{IcedDihedral, IcedDihedralMessage, DIHEDRAL_LIMIT};
+use crate::group_theory::elliptic_curve::{IcedCurveMessage, IcedEllipticCurve};
+use crate::group_theory::factorization::{factorize, FactorizationMethod, FactorizationReport};
+use crate::group_theory::finite_field::{IcedFieldMessage, IcedFiniteField};
+use crate::group_theory::finite_group::{FiniteGroup, GroupType};
+use crate::group_theory::functions::{IcedFunctionMessage, IcedFunctions};
+use crate::group_theory::homomorphism::{IcedHomomorphismMessage, IcedHomomorphisms};
+use crate::group_theory::number_theory::*;
+use crate::group_theory::permutation::{IcedPermutationMessage, IcedPermutations};
+use crate::group_theory::polynomial::{IcedPolynomialMessage, IcedPolynomials};
+use crate::group_theory::primality::{IcedPrimality, IcedPrimalityMessage};
+use crate::group_theory::residues::{IcedResidueMessage, IcedResidues};
+use crate::group_theory::ring::RingReport;
+use crate::group_theory::rsa::{IcedRsa, IcedRsaMessage};
+use crate::group_theory::subgroups::{IcedSubgroupLattice, IcedSubgroupMessage};
 use glucose::DMatrix;
-use iced::{text_input, Align, Column, Element, Row, Text, TextInput, button, Button};
-use std::num::ParseIntError;
-use glucose::group_theory::primes::GroupType;
+use iced::{
+    button, text_input, Align, Button, Column, Command, Element, Radio, Row, Text, TextInput,
+};
+use num_bigint::BigInt;
+use num_traits::{Signed, ToPrimitive, Zero};
 
+pub mod abelian;
+pub mod cayley;
+pub mod ciphers;
+pub mod continued_fraction;
+pub mod diffie_hellman;
+pub mod dihedral;
+pub mod elliptic_curve;
+pub mod factorization;
+pub mod finite_field;
+pub mod finite_group;
+pub mod functions;
+pub mod homomorphism;
+pub mod number_theory;
+pub mod permutation;
+pub mod polynomial;
+pub mod primality;
+pub mod residues;
+pub mod ring;
+pub mod rsa;
+pub mod subgroups;
+
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
 pub enum GroupTheoryMessage {
     SwitchState(GroupTheorySubState),
     IcedEEAMessage(IcedEEAMessage),
-    IcedGroupMessage(IcedGroupMessage)
+    IcedGroupMessage(IcedGroupMessage),
+    IcedPermutationMessage(IcedPermutationMessage),
+    IcedHomomorphismMessage(IcedHomomorphismMessage),
+    IcedFunctionMessage(IcedFunctionMessage),
+    IcedResidueMessage(IcedResidueMessage),
+    IcedFieldMessage(IcedFieldMessage),
+    IcedPolynomialMessage(IcedPolynomialMessage),
+    IcedRsaMessage(IcedRsaMessage),
+    IcedDiffieHellmanMessage(IcedDiffieHellmanMessage),
+    IcedCurveMessage(IcedCurveMessage),
+    IcedCipherMessage(IcedCipherMessage),
+    IcedContinuedFractionMessage(IcedContinuedFractionMessage),
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
 pub enum GroupTheorySubState {
     None,
     EEA(IcedEEA),
     Groups(IcedGroups),
+    Permutations(IcedPermutations),
+    Homomorphisms(IcedHomomorphisms),
+    Functions(IcedFunctions),
+    Residues(IcedResidues),
+    Fields(IcedFiniteField),
+    Polynomials(IcedPolynomials),
+    Rsa(IcedRsa),
+    DiffieHellman(IcedDiffieHellman),
+    Curves(IcedEllipticCurve),
+    Ciphers(IcedCiphers),
+    ContinuedFractions(IcedContinuedFraction),
 }
 
 #[derive(Debug, Clone)]
@@ -24,10 +96,18 @@ pub enum IcedEEAMessage {
     Calculate,
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
 pub enum IcedGroupMessage {
     EditValue(String),
+    MethodSelected(FactorizationMethod),
     Calculate,
+    Calculated(GroupAnalysis),
+    CayleyMessage(IcedCayleyMessage),
+    SubgroupMessage(IcedSubgroupMessage),
+    DihedralMessage(IcedDihedralMessage),
+    AbelianMessage(IcedAbelianMessage),
+    PrimalityMessage(IcedPrimalityMessage),
 }
 
 #[derive(Debug, Clone)]
@@ -36,6 +116,12 @@ pub struct IcedGroupTheory {
     state: GroupTheorySubState,
 }
 
+impl Default for IcedGroupTheory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl IcedGroupTheory {
     pub fn new() -> Self {
         Self {
@@ -43,57 +129,132 @@ impl IcedGroupTheory {
             state: GroupTheorySubState::None,
         }
     }
-    pub fn update(&mut self, message: GroupTheoryMessage) {
+    pub fn update(&mut self, message: GroupTheoryMessage) -> Command<GroupTheoryMessage> {
         match message {
             GroupTheoryMessage::SwitchState(state) => {
                 self.state = state;
             }
             GroupTheoryMessage::IcedEEAMessage(message) => {
-                match &mut self.state {
-                    GroupTheorySubState::EEA(state) => {
-                        state.update(message)
-                    }
-                    _ => { }
+                if let GroupTheorySubState::EEA(state) = &mut self.state {
+                    state.update(message)
                 }
             }
             GroupTheoryMessage::IcedGroupMessage(message) => {
-                match &mut self.state {
-                    GroupTheorySubState::Groups(state) => {
-                        state.update(message)
-                    }
-                    _ => { }
+                if let GroupTheorySubState::Groups(state) = &mut self.state {
+                    return state
+                        .update(message)
+                        .map(GroupTheoryMessage::IcedGroupMessage);
+                }
+            }
+            GroupTheoryMessage::IcedPermutationMessage(message) => {
+                if let GroupTheorySubState::Permutations(state) = &mut self.state {
+                    state.update(message)
+                }
+            }
+            GroupTheoryMessage::IcedHomomorphismMessage(message) => {
+                if let GroupTheorySubState::Homomorphisms(state) = &mut self.state {
+                    return state
+                        .update(message)
+                        .map(GroupTheoryMessage::IcedHomomorphismMessage);
+                }
+            }
+            GroupTheoryMessage::IcedFunctionMessage(message) => {
+                if let GroupTheorySubState::Functions(state) = &mut self.state {
+                    state.update(message)
+                }
+            }
+            GroupTheoryMessage::IcedResidueMessage(message) => {
+                if let GroupTheorySubState::Residues(state) = &mut self.state {
+                    state.update(message)
+                }
+            }
+            GroupTheoryMessage::IcedFieldMessage(message) => {
+                if let GroupTheorySubState::Fields(state) = &mut self.state {
+                    return state
+                        .update(message)
+                        .map(GroupTheoryMessage::IcedFieldMessage);
+                }
+            }
+            GroupTheoryMessage::IcedPolynomialMessage(message) => {
+                if let GroupTheorySubState::Polynomials(state) = &mut self.state {
+                    state.update(message)
+                }
+            }
+            GroupTheoryMessage::IcedRsaMessage(message) => {
+                if let GroupTheorySubState::Rsa(state) = &mut self.state {
+                    return state
+                        .update(message)
+                        .map(GroupTheoryMessage::IcedRsaMessage);
+                }
+            }
+            GroupTheoryMessage::IcedDiffieHellmanMessage(message) => {
+                if let GroupTheorySubState::DiffieHellman(state) = &mut self.state {
+                    state.update(message)
+                }
+            }
+            GroupTheoryMessage::IcedCurveMessage(message) => {
+                if let GroupTheorySubState::Curves(state) = &mut self.state {
+                    state.update(message)
+                }
+            }
+            GroupTheoryMessage::IcedCipherMessage(message) => {
+                if let GroupTheorySubState::Ciphers(state) = &mut self.state {
+                    state.update(message)
+                }
+            }
+            GroupTheoryMessage::IcedContinuedFractionMessage(message) => {
+                if let GroupTheorySubState::ContinuedFractions(state) = &mut self.state {
+                    state.update(message)
                 }
             }
         }
+        Command::none()
     }
 
-    pub fn view(&mut self) -> Element<GroupTheoryMessage> {
+    pub fn view(&mut self) -> Element<'_, GroupTheoryMessage> {
         let controls = self.control.view();
         let content = match &mut self.state {
-            GroupTheorySubState::None => {
-                Column::new()
-                    .push(
-                        Text::new("Chose what you want to do")
-                    ).into()
-            }
-            GroupTheorySubState::EEA(state) => {
-                state
-                    .view()
-                    .map(move |message| GroupTheoryMessage::IcedEEAMessage(message))
-
-            }
+            GroupTheorySubState::None => Column::new()
+                .push(Text::new("Chose what you want to do"))
+                .into(),
+            GroupTheorySubState::EEA(state) => state.view().map(GroupTheoryMessage::IcedEEAMessage),
             GroupTheorySubState::Groups(state) => {
-                state
-                    .view()
-                    .map(move |message| GroupTheoryMessage::IcedGroupMessage(message))
-                    .into()
+                state.view().map(GroupTheoryMessage::IcedGroupMessage)
+            }
+            GroupTheorySubState::Permutations(state) => {
+                state.view().map(GroupTheoryMessage::IcedPermutationMessage)
+            }
+            GroupTheorySubState::Homomorphisms(state) => state
+                .view()
+                .map(GroupTheoryMessage::IcedHomomorphismMessage),
+            GroupTheorySubState::Functions(state) => {
+                state.view().map(GroupTheoryMessage::IcedFunctionMessage)
             }
+            GroupTheorySubState::Residues(state) => {
+                state.view().map(GroupTheoryMessage::IcedResidueMessage)
+            }
+            GroupTheorySubState::Fields(state) => {
+                state.view().map(GroupTheoryMessage::IcedFieldMessage)
+            }
+            GroupTheorySubState::Polynomials(state) => {
+                state.view().map(GroupTheoryMessage::IcedPolynomialMessage)
+            }
+            GroupTheorySubState::Rsa(state) => state.view().map(GroupTheoryMessage::IcedRsaMessage),
+            GroupTheorySubState::DiffieHellman(state) => state
+                .view()
+                .map(GroupTheoryMessage::IcedDiffieHellmanMessage),
+            GroupTheorySubState::Curves(state) => {
+                state.view().map(GroupTheoryMessage::IcedCurveMessage)
+            }
+            GroupTheorySubState::Ciphers(state) => {
+                state.view().map(GroupTheoryMessage::IcedCipherMessage)
+            }
+            GroupTheorySubState::ContinuedFractions(state) => state
+                .view()
+                .map(GroupTheoryMessage::IcedContinuedFractionMessage),
         };
 
-        Column::new()
-            .push(controls)
-            .push(content)
-            .into()
+        Column::new().push(controls).push(content).into()
     }
 }
 
@@ -101,7 +262,7 @@ impl IcedGroupTheory {
 pub struct IcedEEA {
     edit_text: String,
     input_text: text_input::State,
-    result: Option<DMatrix<i64>>,
+    result: Option<DMatrix<BigInt>>,
     error_message: Option<String>,
 }
 
@@ -122,25 +283,21 @@ impl IcedEEA {
             }
             IcedEEAMessage::Calculate => {
                 self.error_message = None;
-                let values: Vec<Result<i64, ParseIntError>> = self
-                    .edit_text
-                    .split(", ")
-                    .into_iter()
-                    .map(|val| val.parse::<i64>())
-                    .collect();
-
-                if values.get(0).is_some() && values.get(1).is_some() {
-                    if values[0].is_ok() && values[1].is_ok() {
-                        let a = *values[0].as_ref().unwrap();
-                        let b = *values[1].as_ref().unwrap();
-                        if a < b {
-                            let result = extended_euclidean_as_dmatrix(a, b);
-                            self.result = Some(result)
-                        } else {
-                            self.error_message = Some(String::from("a must be smaller than b"))
+                let values: Vec<Option<BigInt>> =
+                    self.edit_text.split(',').map(parse_big).collect();
+
+                if values.len() >= 2 {
+                    match (&values[0], &values[1]) {
+                        (Some(a), Some(b)) => {
+                            if !a.is_positive() {
+                                self.error_message = Some(String::from("a must be positive"))
+                            } else if a < b {
+                                self.result = Some(extended_euclidean_as_dmatrix(a, b))
+                            } else {
+                                self.error_message = Some(String::from("a must be smaller than b"))
+                            }
                         }
-                    } else {
-                        self.error_message = Some(String::from("a and/or b is not a number"))
+                        _ => self.error_message = Some(String::from("a and/or b is not a number")),
                     }
                 } else {
                     self.error_message =
@@ -150,7 +307,7 @@ impl IcedEEA {
         }
     }
 
-    pub fn view(&mut self) -> Element<IcedEEAMessage> {
+    pub fn view(&mut self) -> Element<'_, IcedEEAMessage> {
         let input = TextInput::new(
             &mut self.input_text,
             "",
@@ -161,20 +318,15 @@ impl IcedEEA {
 
         let maybe_result = match &self.result {
             None => Row::new().push(Text::new("")),
-            Some(result) => result
-                .data
-                .iter()
-                .enumerate()
-                .fold(
-                    Row::new().spacing(20).align_items(Align::Center),
-                    |row, (i, chunk)| {
-                        row.push(chunk.iter().fold(
-                            Column::new().push(Text::new(EEA_INDICES[i])),
-                            |col, item| col.push(Text::new(item.to_string())),
-                        ))
-                    },
-                )
-                .into(),
+            Some(result) => result.data.iter().enumerate().fold(
+                Row::new().spacing(20).align_items(Align::Center),
+                |row, (i, chunk)| {
+                    row.push(chunk.iter().fold(
+                        Column::new().push(Text::new(EEA_INDICES[i])),
+                        |col, item| col.push(Text::new(item.to_string())),
+                    ))
+                },
+            ),
         };
 
         let maybe_error = match &self.error_message {
@@ -195,152 +347,418 @@ impl IcedEEA {
 
 const EEA_INDICES: [char; 5] = ['a', 'b', 'k', 's', 't'];
 
+/// everything the groups screen derives from a modulo, computed off the ui thread
+#[derive(Debug, Clone)]
+pub struct GroupAnalysis {
+    group_type: GroupType,
+    modulo_factorization: Option<FactorizationReport>,
+    group_size_factorization: Option<FactorizationReport>,
+    prime_factors: Vec<BigInt>,
+    group_size: BigInt,
+    group_size_prime_factors: Vec<BigInt>,
+    group: Vec<BigInt>,
+    possible_orders: Vec<BigInt>,
+    orders: Vec<(BigInt, BigInt)>,
+    producers: Vec<BigInt>,
+    finite_group: Option<FiniteGroup>,
+    abelian: Option<(AbelianGroup, AbelianDecomposition)>,
+    ring: Option<RingReport>,
+
+    error_message: Option<String>,
+}
+
+impl GroupAnalysis {
+    pub fn new(group_type: GroupType) -> Self {
+        Self {
+            group_type,
+            modulo_factorization: None,
+            group_size_factorization: None,
+            prime_factors: vec![],
+            group_size: BigInt::zero(),
+            group_size_prime_factors: vec![],
+            group: vec![],
+            possible_orders: vec![],
+            orders: vec![],
+            producers: vec![],
+            finite_group: None,
+            abelian: None,
+            ring: None,
+            error_message: None,
+        }
+    }
+
+    pub async fn analyse(
+        modulo: BigInt,
+        group_type: GroupType,
+        method: FactorizationMethod,
+    ) -> Self {
+        let mut analysis = Self::new(group_type);
+        let modulo_factorization = factorize(&modulo, method);
+        analysis.prime_factors = modulo_factorization.factors.clone();
+        analysis.modulo_factorization = Some(modulo_factorization);
+        if !is_fully_factored(&analysis.prime_factors) {
+            analysis.error_message = Some(format!(
+                "modulo could not be factored completely with {}",
+                method
+            ));
+            return analysis;
+        }
+
+        // every type but D_n is built on the ring Z_n
+        if group_type != GroupType::Dihedral {
+            analysis.ring = Some(RingReport::new(&modulo, &analysis.prime_factors));
+        }
+
+        let abelian = match group_type {
+            GroupType::Additive => Some(AbelianGroup::z_n(modulo.clone())),
+            GroupType::MultiplicativeStar => Some(AbelianGroup::z_n_star(modulo.clone())),
+            GroupType::Multiplicative | GroupType::Dihedral => None,
+        };
+        if let Some(abelian) = abelian {
+            match abelian.decompose(method, &analysis.prime_factors) {
+                Ok(decomposition) => analysis.abelian = Some((abelian, decomposition)),
+                Err(message) => analysis.error_message = Some(message),
+            }
+        }
+
+        analysis.group_size = group_size(&modulo, &analysis.prime_factors, group_type);
+        let group_size_factorization = factorize(&analysis.group_size, method);
+        analysis.group_size_prime_factors = group_size_factorization.factors.clone();
+        analysis.group_size_factorization = Some(group_size_factorization);
+        if !is_fully_factored(&analysis.group_size_prime_factors) {
+            analysis.error_message = Some(String::from(
+                "group size could not be factored, orders are not available",
+            ));
+            return analysis;
+        }
+        analysis.possible_orders = possible_orders(&analysis.group_size_prime_factors);
+
+        // rotations and reflections are not residues, everything else comes from the table
+        if group_type == GroupType::Dihedral {
+            match modulo.to_u64() {
+                Some(n) if n <= DIHEDRAL_LIMIT => {
+                    analysis.finite_group = FiniteGroup::dihedral(n);
+                }
+                _ => {
+                    analysis.error_message =
+                        Some(format!("D_n is only built for n <= {}", DIHEDRAL_LIMIT));
+                }
+            }
+            return analysis;
+        }
+
+        if analysis.group_size > BigInt::from(ENUMERATION_LIMIT) {
+            analysis.error_message = Some(format!(
+                "group has more than {} elements, elements and orders are not listed",
+                ENUMERATION_LIMIT
+            ));
+            return analysis;
+        }
+        let phi = totient(&analysis.prime_factors);
+        let phi_factors = factorize(&phi, method).factors;
+        analysis.group = group(&modulo, group_type);
+        analysis.orders = orders(&modulo, &analysis.group, group_type, &phi, &phi_factors);
+        analysis.producers = producers(&analysis.group_size, &analysis.orders);
+        if analysis.group.len() <= CAYLEY_TABLE_LIMIT {
+            analysis.finite_group = modulo
+                .to_u64()
+                .and_then(|modulo| FiniteGroup::z_n(modulo, group_type));
+        }
+        analysis
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct IcedGroups {
     edit_text: String,
     input_text: text_input::State,
+    factorization_method: FactorizationMethod,
+    calculating: bool,
 
-    group_type: GroupType,
-    prime_factors: Vec<i64>,
-    group_size: i64,
-    group_size_prime_factors: Vec<i64>,
-    group: Vec<i64>,
-    possible_orders: Vec<i64>,
-    orders: Vec<(i64, i64)>,
-    producers: Vec<i64>,
+    analysis: GroupAnalysis,
+    primality: IcedPrimality,
+    abelian: IcedAbelian,
+    cayley: Option<IcedCayleyTable>,
+    lattice: Option<IcedSubgroupLattice>,
+    dihedral: Option<IcedDihedral>,
 
     error_message: Option<String>,
 }
 
+impl Default for IcedGroups {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl IcedGroups {
     pub fn new() -> Self {
         Self {
             edit_text: String::default(),
             input_text: text_input::State::new(),
-            group_type: GroupType::MultiplicativeStar,
-            prime_factors: vec![],
-            group_size: 0,
-            group_size_prime_factors: vec![],
-            group: vec![],
-            possible_orders: vec![],
-            orders: vec![],
-            producers: vec![],
+            factorization_method: FactorizationMethod::default(),
+            calculating: false,
+            analysis: GroupAnalysis::new(GroupType::MultiplicativeStar),
+            primality: IcedPrimality::new(),
+            abelian: IcedAbelian::new(),
+            cayley: None,
+            lattice: None,
+            dihedral: None,
             error_message: None,
         }
     }
-    pub fn update(&mut self, message: IcedGroupMessage) {
+    pub fn update(&mut self, message: IcedGroupMessage) -> Command<IcedGroupMessage> {
         match message {
             IcedGroupMessage::EditValue(content) => {
                 self.edit_text = content;
             }
+            IcedGroupMessage::MethodSelected(method) => {
+                self.factorization_method = method;
+            }
             IcedGroupMessage::Calculate => {
+                if self.calculating {
+                    return Command::none();
+                }
                 self.error_message = None;
-                let split: Vec<String> = self.edit_text.split(", ").into_iter().map(|str| str.to_string()).collect();
+                let split: Vec<&str> = self.edit_text.split(',').map(str::trim).collect();
                 if split.len() < 2 {
-                    self.error_message = Some(String::from("input is not correct: num, a; num, m or num, m*"));
-                    return;
+                    self.error_message = Some(String::from(
+                        "input is not correct: num, a; num, m; num, m* or num, d",
+                    ));
+                    return Command::none();
                 }
-                let maybe_modulo = split[0].parse::<i64>();
-                let maybe_op = split[1].clone();
-
-                if maybe_modulo.is_ok() && maybe_op == "a" || maybe_op == "m" || maybe_op == "mb" || maybe_op == "m*" || maybe_op == "m*b" {
-                    use glucose::group_theory::primes::*;
-                    let modulo = maybe_modulo.unwrap();
-                    let mut should_big = false;
-                    self.group_type = match maybe_op.as_ref() {
-                        "a" => GroupType::Additive,
-                        "m" => GroupType::Multiplicative,
-                        "mb" => {
-                            should_big = true;
-                            GroupType::Multiplicative
-                        }
-                        "m*" => GroupType::MultiplicativeStar,
-                        "m*b" => {
-                            should_big = true;
-                            GroupType::MultiplicativeStar
-                        }
-                        _ => { panic!( )}
-                    };
-                    self.prime_factors = wheel_factorization(modulo);
-                    self.group_size = group_size(modulo, self.group_type);
-                    self.group_size_prime_factors = wheel_factorization(self.group_size);
-                    self.group = group(modulo, self.group_type);
-                    self.possible_orders = possible_orders(modulo, self.group_type);
-                    self.orders = orders(modulo, &self.group, self.group_type);
-
-                    self.producers = producers(modulo, &self.group, self.group_type, should_big);
-                } else {
-                    self.error_message = Some(String::from("input is not correct: num, a or num, m"))
+                // the "b" variants used to switch glucose to a slower, overflow safe producer
+                // search, big integers make that the default so they are kept as aliases
+                let group_type = match split[1] {
+                    "a" => GroupType::Additive,
+                    "m" | "mb" => GroupType::Multiplicative,
+                    "m*" | "m*b" => GroupType::MultiplicativeStar,
+                    "d" => GroupType::Dihedral,
+                    _ => {
+                        self.error_message = Some(String::from(
+                            "input is not correct: num, a; num, m; num, m* or num, d",
+                        ));
+                        return Command::none();
+                    }
+                };
+                let modulo = match parse_big(split[0]) {
+                    Some(modulo) if modulo.is_positive() => modulo,
+                    _ => {
+                        self.error_message = Some(String::from("modulo must be a positive number"));
+                        return Command::none();
+                    }
+                };
+                if group_type == GroupType::Dihedral && modulo < BigInt::from(3) {
+                    self.error_message = Some(String::from("D_n needs an n-gon with n >= 3"));
+                    return Command::none();
                 }
+                self.calculating = true;
+                return Command::perform(
+                    GroupAnalysis::analyse(modulo, group_type, self.factorization_method),
+                    IcedGroupMessage::Calculated,
+                );
+            }
+            IcedGroupMessage::Calculated(analysis) => {
+                self.calculating = false;
+                self.error_message = analysis.error_message.clone();
+                self.cayley = analysis.finite_group.clone().map(IcedCayleyTable::new);
+                // (Z_n, *) is only a monoid, so it has no subgroup lattice
+                self.lattice = analysis
+                    .finite_group
+                    .clone()
+                    .filter(FiniteGroup::is_group)
+                    .map(IcedSubgroupLattice::new);
+                self.dihedral = match (analysis.group_type, &analysis.finite_group) {
+                    (GroupType::Dihedral, Some(group)) => {
+                        Some(IcedDihedral::new(group.order() as u64 / 2))
+                    }
+                    _ => None,
+                };
+                self.analysis = analysis;
+            }
+            IcedGroupMessage::CayleyMessage(message) => {
+                if let Some(cayley) = &mut self.cayley {
+                    cayley.update(message)
+                }
+            }
+            IcedGroupMessage::AbelianMessage(message) => {
+                self.abelian.update(message, self.factorization_method)
+            }
+            IcedGroupMessage::DihedralMessage(message) => {
+                if let Some(dihedral) = &mut self.dihedral {
+                    dihedral.update(message)
+                }
+            }
+            IcedGroupMessage::SubgroupMessage(message) => {
+                if let Some(lattice) = &mut self.lattice {
+                    lattice.update(message)
+                }
+            }
+            IcedGroupMessage::PrimalityMessage(message) => {
+                let modulo = self.edit_text.split(',').next().and_then(parse_big);
+                self.primality.update(message, modulo)
             }
         }
+        Command::none()
     }
 
-    pub fn view(&mut self) -> Element<IcedGroupMessage> {
+    pub fn view(&mut self) -> Element<'_, IcedGroupMessage> {
         let input = TextInput::new(
             &mut self.input_text,
-            "number, a or m",
+            "number, a; number, m; number, m* or number, d",
             &self.edit_text,
             IcedGroupMessage::EditValue,
-        ).on_submit(IcedGroupMessage::Calculate);
+        )
+        .on_submit(IcedGroupMessage::Calculate);
 
+        let analysis = &self.analysis;
         let content = Column::new()
-            .push(Row::new().push(Text::new(format!("Group Type: {:?}", self.group_type))))
+            .push(Row::new().push(Text::new(format!("Group Type: {:?}", analysis.group_type))))
             .push(
-                self.prime_factors.iter().fold(
-                    Row::new().push(Text::new("Prime Factors: ")), |row, num| {
+                analysis
+                    .prime_factors
+                    .iter()
+                    .fold(Row::new().push(Text::new("Prime Factors: ")), |row, num| {
                         row.push(Text::new(format!("{}, ", num)))
-                    }
-                )
+                    }),
             )
-            .push(Row::new().push(Text::new(format!("Group Size: {}", self.group_size))))
             .push(
-                self.group_size_prime_factors.iter().fold(
-                    Row::new().push(Text::new("Group Size Prime Factors: ")), |row, num| {
-                        row.push(Text::new(format!("{}, ", num)))
-                    }
-                )
-            )
-            .push(
-                self.group.iter().fold(
-                    Row::new().push(Text::new("Group Elements: ")), |row, num| {
-                        row.push(Text::new(format!("{}, ", num)))
-                    }
-                )
-            )
-            .push(
-                self.producers.iter().fold(
-                    Row::new().push(Text::new("Producers: ")), |row, num| {
-                        row.push(Text::new(format!("{:?}, ", num)))
-                    }
-                )
+                self.primality
+                    .view()
+                    .map(IcedGroupMessage::PrimalityMessage),
             )
+            .push(Row::new().push(Text::new(format!("Group Size: {}", analysis.group_size))))
+            .push(analysis.group_size_prime_factors.iter().fold(
+                Row::new().push(Text::new("Group Size Prime Factors: ")),
+                |row, num| row.push(Text::new(format!("{}, ", num))),
+            ))
+            .push(analysis.group.iter().fold(
+                Row::new().push(Text::new("Group Elements: ")),
+                |row, num| row.push(Text::new(format!("{}, ", num))),
+            ))
             .push(
-                self.possible_orders.iter().fold(
-                    Row::new().push(Text::new("Possible Orders: ")), |row, num| {
+                analysis
+                    .producers
+                    .iter()
+                    .fold(Row::new().push(Text::new("Producers: ")), |row, num| {
                         row.push(Text::new(format!("{}, ", num)))
-                    }
-                )
+                    }),
             )
-            .push(
-                self.orders.iter().fold(
-                    Column::new().push(Text::new("Actual Orders: ")), |row, num| {
-                        row.push(Text::new(format!("{:?}, ", num)))
-                    }
-                )
+            .push(analysis.possible_orders.iter().fold(
+                Row::new().push(Text::new("Possible Orders: ")),
+                |row, num| row.push(Text::new(format!("{}, ", num))),
+            ))
+            .push(analysis.orders.iter().fold(
+                Column::new().push(Text::new("Actual Orders: ")),
+                |row, (num, order)| row.push(Text::new(format!("({}, {}), ", num, order))),
+            ));
+
+        // the orders give the exponent independently of the decomposition
+        let classification = match &analysis.abelian {
+            Some((group, decomposition)) => {
+                let mut col = decomposition.view(group);
+                if let Some(largest) = analysis.orders.iter().map(|(_, order)| order).max() {
+                    col = col.push(Text::new(format!(
+                        "Largest element order: {}, last invariant factor: {}, producers: {}",
+                        largest,
+                        decomposition.exponent(),
+                        analysis.producers.len()
+                    )));
+                }
+                col
+            }
+            None => Column::new(),
+        };
+        let ring = match &analysis.ring {
+            Some(ring) => ring.view(),
+            None => Column::new(),
+        };
+        let abelian = self.abelian.view().map(IcedGroupMessage::AbelianMessage);
+
+        let factorization_steps = [
+            ("Factorization of n", &analysis.modulo_factorization),
+            (
+                "Factorization of the group size",
+                &analysis.group_size_factorization,
+            ),
+        ]
+        .iter()
+        .filter_map(|(title, report)| report.as_ref().map(|report| (title, report)))
+        .fold(Column::new().spacing(5), |col, (title, report)| {
+            let steps = report.steps.iter().fold(
+                Column::new().push(Text::new(format!(
+                    "{} ({}, {:?}):",
+                    title, report.method, report.elapsed
+                ))),
+                |col, step| {
+                    col.push(Text::new(format!(
+                        "{:?}: {}",
+                        step.elapsed, step.description
+                    )))
+                },
             );
+            col.push(steps)
+        });
+
+        let status = if self.calculating {
+            Text::new("calculating...")
+        } else {
+            Text::new("")
+        };
 
         let maybe_error = match &self.error_message {
             None => Text::new(""),
             Some(message) => Text::new(message).color([0.921, 0.039, 0.039]),
         };
 
+        let cayley: Element<'_, IcedGroupMessage> = match &mut self.cayley {
+            Some(cayley) => cayley.view().map(IcedGroupMessage::CayleyMessage),
+            None if self.analysis.group_size > BigInt::from(CAYLEY_TABLE_LIMIT) => {
+                table_limit_notice(&self.analysis.group_size)
+            }
+            None => Text::new("").into(),
+        };
+        let dihedral: Element<'_, IcedGroupMessage> = match &mut self.dihedral {
+            Some(dihedral) => dihedral.view().map(IcedGroupMessage::DihedralMessage),
+            None => Text::new("").into(),
+        };
+        let lattice: Element<'_, IcedGroupMessage> = match &mut self.lattice {
+            Some(lattice) => lattice.view().map(IcedGroupMessage::SubgroupMessage),
+            None => Text::new("").into(),
+        };
+
         Column::new()
             .push(input)
+            .push(Self::method_column(self.factorization_method))
+            .push(status)
             .push(content)
+            .push(classification)
+            .push(ring)
+            .push(abelian)
+            .push(dihedral)
+            .push(cayley)
+            .push(lattice)
+            .push(factorization_steps)
             .push(maybe_error)
             .into()
     }
+
+    fn method_column<'a>(selection: FactorizationMethod) -> Row<'a, IcedGroupMessage> {
+        FactorizationMethod::ALL.iter().cloned().fold(
+            Row::new()
+                .padding(5)
+                .spacing(10)
+                .push(Text::new("Factorization: ")),
+            |row, method| {
+                row.push(Radio::new(
+                    method,
+                    method.to_string(),
+                    Some(selection),
+                    IcedGroupMessage::MethodSelected,
+                ))
+            },
+        )
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -348,10 +766,21 @@ pub struct GroupTheoryControls {
     home_button: button::State,
     eea_button: button::State,
     groups_button: button::State,
+    permutations_button: button::State,
+    homomorphisms_button: button::State,
+    functions_button: button::State,
+    residues_button: button::State,
+    fields_button: button::State,
+    polynomials_button: button::State,
+    rsa_button: button::State,
+    diffie_hellman_button: button::State,
+    curves_button: button::State,
+    ciphers_button: button::State,
+    continued_fractions_button: button::State,
 }
 
 impl GroupTheoryControls {
-    fn view(&mut self) -> Row<GroupTheoryMessage> {
+    fn view(&mut self) -> Row<'_, GroupTheoryMessage> {
         Row::new()
             .spacing(10)
             .align_items(Align::Center)
@@ -369,5 +798,82 @@ impl GroupTheoryControls {
                     GroupTheoryMessage::SwitchState(GroupTheorySubState::Groups(IcedGroups::new())),
                 ),
             )
+            .push(
+                Button::new(&mut self.permutations_button, Text::new("Permutations")).on_press(
+                    GroupTheoryMessage::SwitchState(GroupTheorySubState::Permutations(
+                        IcedPermutations::new(),
+                    )),
+                ),
+            )
+            .push(
+                Button::new(&mut self.homomorphisms_button, Text::new("Homomorphisms")).on_press(
+                    GroupTheoryMessage::SwitchState(GroupTheorySubState::Homomorphisms(
+                        IcedHomomorphisms::new(),
+                    )),
+                ),
+            )
+            .push(
+                Button::new(&mut self.functions_button, Text::new("Functions")).on_press(
+                    GroupTheoryMessage::SwitchState(GroupTheorySubState::Functions(
+                        IcedFunctions::new(),
+                    )),
+                ),
+            )
+            .push(
+                Button::new(&mut self.residues_button, Text::new("Residues")).on_press(
+                    GroupTheoryMessage::SwitchState(GroupTheorySubState::Residues(
+                        IcedResidues::new(),
+                    )),
+                ),
+            )
+            .push(
+                Button::new(&mut self.fields_button, Text::new("Fields")).on_press(
+                    GroupTheoryMessage::SwitchState(GroupTheorySubState::Fields(
+                        IcedFiniteField::new(),
+                    )),
+                ),
+            )
+            .push(
+                Button::new(&mut self.polynomials_button, Text::new("Polynomials")).on_press(
+                    GroupTheoryMessage::SwitchState(GroupTheorySubState::Polynomials(
+                        IcedPolynomials::new(),
+                    )),
+                ),
+            )
+            .push(
+                Button::new(&mut self.rsa_button, Text::new("RSA")).on_press(
+                    GroupTheoryMessage::SwitchState(GroupTheorySubState::Rsa(IcedRsa::new())),
+                ),
+            )
+            .push(
+                Button::new(&mut self.diffie_hellman_button, Text::new("DH / ElGamal")).on_press(
+                    GroupTheoryMessage::SwitchState(GroupTheorySubState::DiffieHellman(
+                        IcedDiffieHellman::new(),
+                    )),
+                ),
+            )
+            .push(
+                Button::new(&mut self.curves_button, Text::new("Curves")).on_press(
+                    GroupTheoryMessage::SwitchState(GroupTheorySubState::Curves(
+                        IcedEllipticCurve::new(),
+                    )),
+                ),
+            )
+            .push(
+                Button::new(&mut self.ciphers_button, Text::new("Ciphers")).on_press(
+                    GroupTheoryMessage::SwitchState(GroupTheorySubState::Ciphers(
+                        IcedCiphers::new(),
+                    )),
+                ),
+            )
+            .push(
+                Button::new(
+                    &mut self.continued_fractions_button,
+                    Text::new("Continued fractions"),
+                )
+                .on_press(GroupTheoryMessage::SwitchState(
+                    GroupTheorySubState::ContinuedFractions(IcedContinuedFraction::new()),
+                )),
+            )
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,483 @@
+use crate::group_theory::factorization::{factorize, FactorizationMethod};
+use crate::group_theory::number_theory::{
+    is_fully_factored, mod_inverse, parse_big, prime_powers, primitive_root_with,
+};
+use crate::matrix::normal_form::{parse_rows, smith_normal_form, SmithForm};
+use iced::{button, text_input, Align, Button, Column, Element, Row, Text, TextInput};
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+
+/// one component of a direct product, Z_modulo under + or Z_modulo^* under *
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Slot {
+    pub modulo: BigInt,
+    pub multiplicative: bool,
+}
+
+/// a direct product of Z_a and Z_n^*, elements are tuples with one residue per slot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbelianGroup {
+    pub slots: Vec<Slot>,
+}
+
+/// a cyclic factor Z_order together with an element generating it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CyclicFactor {
+    pub order: BigInt,
+    pub generator: Vec<BigInt>,
+}
+
+/// a cyclic factor with the prime factors of its order
+type FactoredCyclic = (CyclicFactor, Vec<BigInt>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbelianDecomposition {
+    pub name: String,
+    pub order: BigInt,
+    /// prime power orders, ascending
+    pub elementary_divisors: Vec<CyclicFactor>,
+    /// d_1 | d_2 | ... | d_k
+    pub invariant_factors: Vec<CyclicFactor>,
+    /// how the group was split up before sorting out primes
+    pub cyclic_components: Vec<String>,
+}
+
+impl AbelianGroup {
+    pub fn z_n(modulo: BigInt) -> Self {
+        Self {
+            slots: vec![Slot {
+                modulo,
+                multiplicative: false,
+            }],
+        }
+    }
+
+    pub fn z_n_star(modulo: BigInt) -> Self {
+        Self {
+            slots: vec![Slot {
+                modulo,
+                multiplicative: true,
+            }],
+        }
+    }
+
+    /// "Z_4 x Z_6 x Z_15^*"
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let slots = text
+            .split(['x', '×'])
+            .map(|factor| {
+                let compact: String = factor.split_whitespace().collect();
+                let rest = compact
+                    .strip_prefix("Z_")
+                    .or_else(|| compact.strip_prefix('Z'))
+                    .ok_or_else(|| format!("{} is not Z_a or Z_n^*", factor.trim()))?;
+                let (number, multiplicative) =
+                    match rest.strip_suffix("^*").or_else(|| rest.strip_suffix('*')) {
+                        Some(number) => (number, true),
+                        None => (rest, false),
+                    };
+                match parse_big(number) {
+                    Some(modulo) if modulo.is_positive() => Ok(Slot {
+                        modulo,
+                        multiplicative,
+                    }),
+                    _ => Err(format!("{} needs a positive number", factor.trim())),
+                }
+            })
+            .collect::<Result<Vec<Slot>, String>>()?;
+        Ok(Self { slots })
+    }
+
+    pub fn name(&self) -> String {
+        let names: Vec<String> = self
+            .slots
+            .iter()
+            .map(|slot| {
+                if slot.multiplicative {
+                    format!("Z_{}^*", slot.modulo)
+                } else {
+                    format!("Z_{}", slot.modulo)
+                }
+            })
+            .collect();
+        names.join(" x ")
+    }
+
+    pub fn identity(&self) -> Vec<BigInt> {
+        self.slots
+            .iter()
+            .map(|slot| {
+                if slot.multiplicative {
+                    BigInt::one().mod_floor(&slot.modulo)
+                } else {
+                    BigInt::zero()
+                }
+            })
+            .collect()
+    }
+
+    pub fn op(&self, a: &[BigInt], b: &[BigInt]) -> Vec<BigInt> {
+        self.slots
+            .iter()
+            .zip(a.iter().zip(b.iter()))
+            .map(|(slot, (a, b))| {
+                if slot.multiplicative {
+                    (a * b).mod_floor(&slot.modulo)
+                } else {
+                    (a + b).mod_floor(&slot.modulo)
+                }
+            })
+            .collect()
+    }
+
+    pub fn pow(&self, a: &[BigInt], exp: &BigInt) -> Vec<BigInt> {
+        self.slots
+            .iter()
+            .zip(a.iter())
+            .map(|(slot, a)| {
+                if slot.multiplicative {
+                    a.modpow(exp, &slot.modulo)
+                } else {
+                    (a * exp).mod_floor(&slot.modulo)
+                }
+            })
+            .collect()
+    }
+
+    /// a single residue for one slot, "(a, b, ...)" otherwise
+    pub fn label(&self, element: &[BigInt]) -> String {
+        let residues: Vec<String> = element.iter().map(|r| r.to_string()).collect();
+        if residues.len() == 1 {
+            residues[0].clone()
+        } else {
+            format!("({})", residues.join(", "))
+        }
+    }
+
+    /// the element that is value in slot index and the identity everywhere else
+    fn embed(&self, index: usize, value: BigInt) -> Vec<BigInt> {
+        let mut element = self.identity();
+        element[index] = value;
+        element
+    }
+
+    /// splits every slot into cyclic groups with known generators and the prime factors of
+    /// their orders, Z_n^* via the chinese remainder theorem into Z_(p^k)^*
+    fn cyclic_factors(
+        &self,
+        method: FactorizationMethod,
+        known: &[BigInt],
+    ) -> Result<(Vec<FactoredCyclic>, Vec<String>), String> {
+        let mut factors = Vec::new();
+        let mut names = Vec::new();
+        for (index, slot) in self.slots.iter().enumerate() {
+            let modulo_factors = prime_factors(&slot.modulo, known, method)?;
+            if !slot.multiplicative {
+                names.push(format!("Z_{}", slot.modulo));
+                factors.push((
+                    CyclicFactor {
+                        order: slot.modulo.clone(),
+                        generator: self.embed(index, BigInt::one().mod_floor(&slot.modulo)),
+                    },
+                    modulo_factors,
+                ));
+                continue;
+            }
+            for (prime, exp) in prime_powers(&modulo_factors) {
+                let prime_power = prime.pow(exp);
+                let rest = &slot.modulo / &prime_power;
+                // x = g mod p^k and x = 1 mod n / p^k
+                let lift = |g: &BigInt| -> BigInt {
+                    let inverse = mod_inverse(&rest, &prime_power).unwrap_or_else(BigInt::one);
+                    (BigInt::one() + (g - 1u32) * &rest * inverse).mod_floor(&slot.modulo)
+                };
+                names.push(format!("Z_{}^*", prime_power));
+                if prime == BigInt::from(2) {
+                    // Z_2^* is trivial, Z_4^* = <-1> and Z_(2^k)^* = <-1> x <5>
+                    if exp >= 2 {
+                        factors.push((
+                            CyclicFactor {
+                                order: BigInt::from(2),
+                                generator: self.embed(index, lift(&(&prime_power - 1u32))),
+                            },
+                            vec![BigInt::from(2)],
+                        ));
+                    }
+                    if exp >= 3 {
+                        factors.push((
+                            CyclicFactor {
+                                order: BigInt::from(2).pow(exp - 2),
+                                generator: self.embed(index, lift(&BigInt::from(5))),
+                            },
+                            vec![BigInt::from(2); exp as usize - 2],
+                        ));
+                    }
+                } else {
+                    // |Z_(p^k)^*| = p^(k - 1) (p - 1), only p - 1 is left to factor
+                    let mut order_factors = prime_factors(&(&prime - 1u32), known, method)?;
+                    let root = primitive_root_with(&prime, exp, &order_factors);
+                    order_factors.extend(vec![prime.clone(); exp as usize - 1]);
+                    order_factors.sort();
+                    factors.push((
+                        CyclicFactor {
+                            order: prime.pow(exp - 1) * (&prime - 1u32),
+                            generator: self.embed(index, lift(&root)),
+                        },
+                        order_factors,
+                    ));
+                }
+            }
+        }
+        Ok((factors, names))
+    }
+
+    /// the invariant factors and elementary divisors, known are primes that were already found,
+    /// like the factors of n for Z_n^*, everything else is factored with method
+    pub fn decompose(
+        &self,
+        method: FactorizationMethod,
+        known: &[BigInt],
+    ) -> Result<AbelianDecomposition, String> {
+        let (cyclic, cyclic_components) = self.cyclic_factors(method, known)?;
+        let mut elementary_divisors: Vec<(BigInt, u32, CyclicFactor)> = Vec::new();
+        for (factor, order_factors) in &cyclic {
+            for (prime, exp) in prime_powers(order_factors) {
+                let order = prime.pow(exp);
+                let generator = self.pow(&factor.generator, &(&factor.order / &order));
+                elementary_divisors.push((prime, exp, CyclicFactor { order, generator }));
+            }
+        }
+        elementary_divisors.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+
+        // the i-th invariant factor from the top takes the i-th largest power of every prime
+        let mut invariant_factors: Vec<CyclicFactor> = Vec::new();
+        let mut rank = vec![0usize; elementary_divisors.len()];
+        for i in 1..elementary_divisors.len() {
+            if elementary_divisors[i].0 == elementary_divisors[i - 1].0 {
+                rank[i] = rank[i - 1] + 1;
+            }
+        }
+        for (i, (_, _, factor)) in elementary_divisors.iter().enumerate() {
+            if rank[i] == invariant_factors.len() {
+                invariant_factors.push(CyclicFactor {
+                    order: BigInt::one(),
+                    generator: self.identity(),
+                });
+            }
+            let invariant = &mut invariant_factors[rank[i]];
+            invariant.order *= &factor.order;
+            invariant.generator = self.op(&invariant.generator, &factor.generator);
+        }
+        invariant_factors.reverse();
+        elementary_divisors.sort_by(|a, b| a.2.order.cmp(&b.2.order).then(a.0.cmp(&b.0)));
+
+        Ok(AbelianDecomposition {
+            name: self.name(),
+            order: cyclic.iter().map(|(factor, _)| &factor.order).product(),
+            elementary_divisors: elementary_divisors
+                .into_iter()
+                .map(|(_, _, factor)| factor)
+                .collect(),
+            invariant_factors,
+            cyclic_components,
+        })
+    }
+}
+
+/// the prime factors of num, the known primes are divided out before method runs on the rest
+fn prime_factors(
+    num: &BigInt,
+    known: &[BigInt],
+    method: FactorizationMethod,
+) -> Result<Vec<BigInt>, String> {
+    let mut rest = num.abs();
+    let mut factors = Vec::new();
+    let mut primes = known.to_vec();
+    primes.sort();
+    primes.dedup();
+    for prime in primes.iter().filter(|prime| **prime > BigInt::one()) {
+        while !rest.is_zero() && rest.is_multiple_of(prime) {
+            factors.push(prime.clone());
+            rest /= prime;
+        }
+    }
+    if rest > BigInt::one() {
+        factors.extend(factorize(&rest, method).factors);
+    }
+    factors.sort();
+    if is_fully_factored(&factors) {
+        Ok(factors)
+    } else {
+        Err(format!("{} could not be factored", num))
+    }
+}
+
+impl AbelianDecomposition {
+    pub fn is_cyclic(&self) -> bool {
+        self.invariant_factors.len() <= 1
+    }
+
+    /// the largest element order, the last invariant factor
+    pub fn exponent(&self) -> BigInt {
+        self.invariant_factors
+            .last()
+            .map_or_else(BigInt::one, |factor| factor.order.clone())
+    }
+
+    pub fn view<'a, M: 'a>(&self, group: &AbelianGroup) -> Column<'a, M> {
+        let canonical = |factors: &[CyclicFactor]| -> String {
+            if factors.is_empty() {
+                return String::from("trivial group");
+            }
+            let names: Vec<String> = factors.iter().map(|f| format!("Z_{}", f.order)).collect();
+            names.join(" x ")
+        };
+        let generators = |factors: &[CyclicFactor]| -> String {
+            let generators: Vec<String> = factors
+                .iter()
+                .map(|f| format!("{} (order {})", group.label(&f.generator), f.order))
+                .collect();
+            generators.join(", ")
+        };
+        let components = if self.cyclic_components.is_empty() {
+            String::from("{1}")
+        } else {
+            self.cyclic_components.join(" x ")
+        };
+        let cyclic = if self.is_cyclic() {
+            String::from("cyclic")
+        } else {
+            format!("not cyclic, rank {}", self.invariant_factors.len())
+        };
+        Column::new()
+            .spacing(5)
+            .push(Text::new(format!(
+                "{} = {}, order {}, {}",
+                self.name, components, self.order, cyclic
+            )))
+            .push(Text::new(format!(
+                "Invariant factors: {}",
+                canonical(&self.invariant_factors)
+            )))
+            .push(Text::new(format!(
+                "    independent generators: {}",
+                generators(&self.invariant_factors)
+            )))
+            .push(Text::new(format!(
+                "Elementary divisors: {}",
+                canonical(&self.elementary_divisors)
+            )))
+            .push(Text::new(format!(
+                "    independent generators: {}",
+                generators(&self.elementary_divisors)
+            )))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum IcedAbelianMessage {
+    EditValue(String),
+    Classify,
+}
+
+/// classification of typed direct products like Z_4 x Z_6 x Z_15^*
+/// and of presentations Z^n / <relations> typed as a matrix [2 4; 6 8]
+#[derive(Debug, Clone, Default)]
+pub struct IcedAbelian {
+    edit_text: String,
+    input_text: text_input::State,
+    classify_button: button::State,
+    result: Option<(AbelianGroup, AbelianDecomposition)>,
+    /// the Smith form of a typed presentation, its torsion part is the result
+    presentation: Option<SmithForm>,
+    error_message: Option<String>,
+}
+
+impl IcedAbelian {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// factors that are needed along the way are found with method
+    pub fn update(&mut self, message: IcedAbelianMessage, method: FactorizationMethod) {
+        match message {
+            IcedAbelianMessage::EditValue(content) => {
+                self.edit_text = content;
+            }
+            IcedAbelianMessage::Classify => {
+                self.error_message = None;
+                self.result = None;
+                self.presentation = None;
+                let classified = if self.edit_text.trim_start().starts_with('[') {
+                    self.classify_presentation(method)
+                } else {
+                    AbelianGroup::parse(&self.edit_text).and_then(|group| {
+                        group.decompose(method, &[]).map(|result| (group, result))
+                    })
+                };
+                match classified {
+                    Ok(result) => self.result = Some(result),
+                    Err(message) => self.error_message = Some(message),
+                }
+            }
+        }
+    }
+
+    /// the rows are relations between the generators x_1 ... x_n of Z^n
+    fn classify_presentation(
+        &mut self,
+        method: FactorizationMethod,
+    ) -> Result<(AbelianGroup, AbelianDecomposition), String> {
+        let form = smith_normal_form(&parse_rows(&self.edit_text)?)?;
+        let torsion = form.torsion();
+        let mut decomposition = torsion.decompose(method, &[])?;
+        decomposition.name = String::from("Torsion part");
+        self.presentation = Some(form);
+        Ok((torsion, decomposition))
+    }
+
+    pub fn view(&mut self) -> Element<'_, IcedAbelianMessage> {
+        let input = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new("Product or relation matrix: "))
+            .push(
+                TextInput::new(
+                    &mut self.input_text,
+                    "Z_4 x Z_6 x Z_15^*",
+                    &self.edit_text,
+                    IcedAbelianMessage::EditValue,
+                )
+                .on_submit(IcedAbelianMessage::Classify),
+            )
+            .push(
+                Button::new(&mut self.classify_button, Text::new("Classify"))
+                    .on_press(IcedAbelianMessage::Classify),
+            );
+        let mut content = Column::new().spacing(5).push(input);
+        if let Some(form) = &self.presentation {
+            content = content
+                .push(Text::new(format!(
+                    "Z^{} / <rows> = {}, Smith form diagonal {:?}",
+                    form.v.len(),
+                    form.structure(),
+                    form.diagonal()
+                        .iter()
+                        .map(|d| d.to_string())
+                        .collect::<Vec<String>>()
+                )))
+                .push(Text::new(format!(
+                    "    independent generators: {}",
+                    form.generators().join(", ")
+                )));
+        }
+        if let Some((group, decomposition)) = &self.result {
+            content = content.push(decomposition.view(group));
+        }
+        if let Some(message) = &self.error_message {
+            content = content.push(Text::new(message).color([0.921, 0.039, 0.039]));
+        }
+        content.into()
+    }
+}
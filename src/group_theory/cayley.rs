@@ -0,0 +1,169 @@
+use crate::group_theory::finite_group::FiniteGroup;
+use crate::utils::{cell, element_color, CellStyle};
+use iced::{
+    button, scrollable, Align, Button, Color, Column, Element, Length, Row, Scrollable, Text,
+};
+use std::fmt::Display;
+
+/// groups above this are not turned into a table at all
+pub const CAYLEY_TABLE_LIMIT: usize = 1024;
+/// tables up to this can be expanded in full, larger ones only show the collapsed corner
+pub const CAYLEY_EXPAND_LIMIT: usize = 64;
+/// tables above this start collapsed to their first rows and columns
+pub const CAYLEY_COLLAPSED_SIZE: usize = 16;
+const CELL_SIZE: u16 = 32;
+/// expanded tables scroll inside this height
+const SCROLL_HEIGHT: u16 = 600;
+const HIGHLIGHT: Color = Color::BLACK;
+
+/// shown instead of the table for groups above CAYLEY_TABLE_LIMIT
+pub fn table_limit_notice<'a, M: 'a>(order: impl Display) -> Element<'a, M> {
+    Text::new(format!(
+        "the group has {} elements, Cayley tables are only built up to {}",
+        order, CAYLEY_TABLE_LIMIT
+    ))
+    .into()
+}
+
+#[derive(Debug, Clone)]
+pub enum IcedCayleyMessage {
+    ToggleRow(usize),
+    ToggleExpanded,
+}
+
+#[derive(Debug, Clone)]
+pub struct IcedCayleyTable {
+    group: FiniteGroup,
+    selected: Vec<bool>,
+    row_buttons: Vec<button::State>,
+    expand_button: button::State,
+    expanded: bool,
+    scroll: scrollable::State,
+}
+
+impl IcedCayleyTable {
+    pub fn new(group: FiniteGroup) -> Self {
+        let order = group.order();
+        Self {
+            group,
+            selected: vec![false; order],
+            row_buttons: vec![button::State::new(); order],
+            expand_button: button::State::new(),
+            expanded: order <= CAYLEY_COLLAPSED_SIZE,
+            scroll: scrollable::State::new(),
+        }
+    }
+
+    pub fn update(&mut self, message: IcedCayleyMessage) {
+        match message {
+            IcedCayleyMessage::ToggleRow(row) => {
+                if let Some(selected) = self.selected.get_mut(row) {
+                    *selected = !*selected;
+                }
+            }
+            IcedCayleyMessage::ToggleExpanded => {
+                self.expanded = !self.expanded && self.group.order() <= CAYLEY_EXPAND_LIMIT;
+            }
+        }
+    }
+
+    pub fn view(&mut self) -> Element<'_, IcedCayleyMessage> {
+        let group = &self.group;
+        let order = group.order();
+        let shown = if self.expanded {
+            order
+        } else {
+            order.min(CAYLEY_COLLAPSED_SIZE)
+        };
+        let header_style = CellStyle::new(Color::from_rgb(0.85, 0.85, 0.85));
+        // long labels like cycle notation widen the columns, rows keep their height
+        let longest = group.labels.iter().map(String::len).max().unwrap_or(0) as u16;
+        let width = CELL_SIZE.max(longest * 7 + 8);
+
+        let header = (0..shown).fold(
+            Row::new().push(cell(&group.symbol, width, CELL_SIZE, header_style)),
+            |row, b| {
+                let style = if b == group.identity {
+                    header_style.border(HIGHLIGHT, 2.0)
+                } else {
+                    header_style
+                };
+                row.push(cell(&group.labels[b], width, CELL_SIZE, style))
+            },
+        );
+
+        let selected = &self.selected;
+        let rows = self.row_buttons.iter_mut().take(shown).enumerate().fold(
+            Column::new().push(header),
+            |col, (a, button_state)| {
+                let row_header = Button::new(button_state, Text::new(&group.labels[a]).size(14))
+                    .width(Length::Units(width))
+                    .padding(4)
+                    .on_press(IcedCayleyMessage::ToggleRow(a));
+                let row = (0..shown).fold(Row::new().push(row_header), |row, b| {
+                    let result = group.op(a, b);
+                    let mut style = CellStyle::new(element_color(result, order));
+                    if selected[a] {
+                        style = style.border(HIGHLIGHT, 2.0);
+                    }
+                    // a op b = e marks b as the inverse of a
+                    if result == group.identity {
+                        style = style.text_color(Color::WHITE).border(HIGHLIGHT, 1.0);
+                        style.background = Color::from_rgb(0.2, 0.2, 0.2);
+                    }
+                    row.push(cell(&group.labels[result], width, CELL_SIZE, style))
+                });
+                col.push(row)
+            },
+        );
+
+        let selected_inverses =
+            (0..order)
+                .filter(|a| selected[*a])
+                .fold(Column::new(), |col, a| {
+                    let inverse = match group.inverse(a) {
+                        Some(inverse) => group.labels[inverse].clone(),
+                        None => String::from("none"),
+                    };
+                    col.push(Text::new(format!(
+                        "{}: inverse {}",
+                        group.labels[a], inverse
+                    )))
+                });
+
+        let rows: Element<'_, IcedCayleyMessage> = if shown > CAYLEY_COLLAPSED_SIZE {
+            Scrollable::new(&mut self.scroll)
+                .height(Length::Units(SCROLL_HEIGHT))
+                .push(rows)
+                .into()
+        } else {
+            rows.into()
+        };
+        let mut content = Column::new()
+            .spacing(10)
+            .align_items(Align::Start)
+            .push(Text::new(format!(
+                "Cayley table of {}, identity {}, dark cells mark inverse pairs",
+                group.name, group.labels[group.identity]
+            )))
+            .push(rows)
+            .push(selected_inverses);
+        if order > CAYLEY_EXPAND_LIMIT {
+            content = content.push(Text::new(format!(
+                "the first {} of {} rows and columns, tables are only expanded up to {} elements",
+                shown, order, CAYLEY_EXPAND_LIMIT
+            )));
+        } else if order > CAYLEY_COLLAPSED_SIZE {
+            let label = if self.expanded {
+                String::from("Collapse table")
+            } else {
+                format!("Show all {} rows", order)
+            };
+            content = content.push(
+                Button::new(&mut self.expand_button, Text::new(label))
+                    .on_press(IcedCayleyMessage::ToggleExpanded),
+            );
+        }
+        content.into()
+    }
+}
@@ -0,0 +1,877 @@
+use crate::group_theory::number_theory::{
+    determinant_mod, matrix_inverse_mod, matrix_vector_mod, mod_inverse, parse_big,
+};
+use crate::utils::CellStyle;
+use iced::{
+    button, text_input, Align, Button, Color, Column, Container, Element, Length, Radio, Row,
+    Space, Text, TextInput,
+};
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::ToPrimitive;
+use std::collections::HashMap;
+
+pub const ALPHABET_SIZE: i64 = 26;
+/// letter frequencies of English text in percent, A to Z
+pub const ENGLISH_FREQUENCIES: [f64; 26] = [
+    8.2, 1.5, 2.8, 4.3, 12.7, 2.2, 2.0, 6.1, 7.0, 0.15, 0.77, 4.0, 2.4, 6.7, 7.5, 1.9, 0.095, 6.0,
+    6.3, 9.1, 2.8, 0.98, 2.4, 0.15, 2.0, 0.074,
+];
+/// the letters of English by falling frequency
+const ENGLISH_ORDER: &str = "ETAOINSHRDLCUMWFGYPBVKJXQZ";
+/// the chance that two letters of English text agree, random text has 1/26
+pub const ENGLISH_COINCIDENCE: f64 = 0.0667;
+/// Vigenere keys are searched and Kasiski distances factored up to this length
+pub const KEY_LENGTH_LIMIT: usize = 20;
+pub const HILL_SIZE_LIMIT: usize = 4;
+const HISTOGRAM_HEIGHT: f64 = 150.0;
+const HISTOGRAM_BAR_WIDTH: u16 = 10;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Cipher {
+    #[default]
+    Caesar,
+    Affine,
+    Vigenere,
+    Hill,
+    Substitution,
+}
+
+impl From<Cipher> for String {
+    fn from(cipher: Cipher) -> Self {
+        String::from(match cipher {
+            Cipher::Caesar => "Caesar",
+            Cipher::Affine => "Affine",
+            Cipher::Vigenere => "Vigenere",
+            Cipher::Hill => "Hill",
+            Cipher::Substitution => "Substitution",
+        })
+    }
+}
+
+impl Cipher {
+    pub const ALL: [Cipher; 5] = [
+        Cipher::Caesar,
+        Cipher::Affine,
+        Cipher::Vigenere,
+        Cipher::Hill,
+        Cipher::Substitution,
+    ];
+
+    fn key_hint(self) -> &'static str {
+        match self {
+            Cipher::Caesar => "shift, a number or a letter",
+            Cipher::Affine => "a, b with gcd(a, 26) = 1",
+            Cipher::Vigenere => "key word",
+            Cipher::Hill => "matrix rows split by ;, e.g. 3 3; 2 5",
+            Cipher::Substitution => "the 26 cipher letters for A to Z",
+        }
+    }
+}
+
+fn letter_index(c: char) -> Option<i64> {
+    if c.is_ascii_alphabetic() {
+        Some((c.to_ascii_uppercase() as u8 - b'A') as i64)
+    } else {
+        None
+    }
+}
+
+fn letter(index: i64) -> char {
+    (b'A' + index.rem_euclid(ALPHABET_SIZE) as u8) as char
+}
+
+/// only the letters of a text, upper case
+pub fn letters(text: &str) -> Vec<i64> {
+    text.chars().filter_map(letter_index).collect()
+}
+
+/// maps every letter by f(number of letters before it, letter), keeps case and everything else
+fn map_letters(text: &str, mut f: impl FnMut(usize, i64) -> i64) -> String {
+    let mut position = 0;
+    text.chars()
+        .map(|c| match letter_index(c) {
+            Some(index) => {
+                let mapped = letter(f(position, index));
+                position += 1;
+                if c.is_ascii_lowercase() {
+                    mapped.to_ascii_lowercase()
+                } else {
+                    mapped
+                }
+            }
+            None => c,
+        })
+        .collect()
+}
+
+pub fn caesar(text: &str, shift: i64) -> String {
+    map_letters(text, |_, x| x + shift)
+}
+
+/// x -> ax + b
+pub fn affine(text: &str, a: i64, b: i64) -> String {
+    map_letters(text, |_, x| a * x + b)
+}
+
+/// y -> a^-1 (y - b), None if a is no unit mod 26
+pub fn affine_decrypt(text: &str, a: i64, b: i64) -> Option<String> {
+    let inverse = affine_inverse(a)?;
+    Some(map_letters(text, |_, y| inverse * (y - b)))
+}
+
+pub fn affine_inverse(a: i64) -> Option<i64> {
+    mod_inverse(&BigInt::from(a), &BigInt::from(ALPHABET_SIZE)).and_then(|inverse| inverse.to_i64())
+}
+
+/// every letter shifted by the key letter below it, backwards to decrypt
+pub fn vigenere(text: &str, key: &[i64], decrypt: bool) -> String {
+    let sign = if decrypt { -1 } else { 1 };
+    map_letters(text, |i, x| x + sign * key[i % key.len()])
+}
+
+/// key[x] is the image of the letter x
+pub fn substitution(text: &str, key: &[i64]) -> String {
+    map_letters(text, |_, x| key[x as usize])
+}
+
+pub fn inverse_substitution(key: &[i64]) -> Vec<i64> {
+    let mut inverse = vec![0; key.len()];
+    for (x, y) in key.iter().enumerate() {
+        inverse[*y as usize] = x as i64;
+    }
+    inverse
+}
+
+/// blocks of letters as vectors times the key mod 26, the last block is padded with X
+pub fn hill(text: &str, key: &[Vec<BigInt>]) -> String {
+    let modulo = BigInt::from(ALPHABET_SIZE);
+    let mut letters: Vec<BigInt> = letters(text).into_iter().map(BigInt::from).collect();
+    while !letters.len().is_multiple_of(key.len()) {
+        letters.push(BigInt::from(letter_index('X').unwrap_or(0)));
+    }
+    letters
+        .chunks(key.len())
+        .flat_map(|block| matrix_vector_mod(key, block, &modulo))
+        .map(|y| letter(y.to_i64().unwrap_or(0)))
+        .collect()
+}
+
+/// a number of any size reduced mod 26, so keys never overflow in the letter arithmetic
+fn parse_residue(text: &str) -> Option<i64> {
+    parse_big(text)?
+        .mod_floor(&BigInt::from(ALPHABET_SIZE))
+        .to_i64()
+}
+
+/// a letter or a number, as a shift in 0..26
+pub fn parse_shift(text: &str) -> Option<i64> {
+    let text = text.trim();
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_alphabetic() => letter_index(c),
+        _ => parse_residue(text),
+    }
+}
+
+/// a, b reduced mod 26
+pub fn parse_affine_key(text: &str) -> Option<(i64, i64)> {
+    let mut parts = text.split(',').map(parse_residue);
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(Some(a)), Some(Some(b)), None) => Some((a, b)),
+        _ => None,
+    }
+}
+
+pub fn parse_word_key(text: &str) -> Option<Vec<i64>> {
+    let key = letters(text);
+    if key.is_empty()
+        || text
+            .chars()
+            .any(|c| !c.is_ascii_alphabetic() && !c.is_whitespace())
+    {
+        None
+    } else {
+        Some(key)
+    }
+}
+
+/// a square matrix of numbers, rows split by ;
+pub fn parse_hill_key(text: &str) -> Option<Vec<Vec<BigInt>>> {
+    let matrix: Vec<Vec<BigInt>> = text
+        .split(';')
+        .map(|row| {
+            row.split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|entry| !entry.is_empty())
+                .map(parse_big)
+                .collect::<Option<Vec<BigInt>>>()
+        })
+        .collect::<Option<_>>()?;
+    let size = matrix.len();
+    if (1..=HILL_SIZE_LIMIT).contains(&size) && matrix.iter().all(|row| row.len() == size) {
+        Some(matrix)
+    } else {
+        None
+    }
+}
+
+/// a permutation of the 26 letters
+pub fn parse_substitution_key(text: &str) -> Option<Vec<i64>> {
+    let key = letters(text);
+    let mut seen = [false; 26];
+    for x in &key {
+        if std::mem::replace(&mut seen[*x as usize], true) {
+            return None;
+        }
+    }
+    if key.len() == ALPHABET_SIZE as usize {
+        Some(key)
+    } else {
+        None
+    }
+}
+
+pub fn letter_counts(letters: &[i64]) -> [usize; 26] {
+    let mut counts = [0; 26];
+    for x in letters {
+        counts[*x as usize] += 1;
+    }
+    counts
+}
+
+/// the chance that two letters drawn without replacement agree
+pub fn index_of_coincidence(letters: &[i64]) -> f64 {
+    let n = letters.len();
+    if n < 2 {
+        return 0.0;
+    }
+    let pairs: usize = letter_counts(letters)
+        .iter()
+        .map(|c| c * c.saturating_sub(1))
+        .sum();
+    pairs as f64 / (n * (n - 1)) as f64
+}
+
+/// sum of (observed - expected)^2 / expected against English, small for English text
+pub fn chi_squared(letters: &[i64]) -> f64 {
+    let n = letters.len() as f64;
+    letter_counts(letters)
+        .iter()
+        .zip(ENGLISH_FREQUENCIES.iter())
+        .map(|(count, frequency)| {
+            let expected = n * frequency / 100.0;
+            (*count as f64 - expected).powi(2) / expected
+        })
+        .sum()
+}
+
+/// the shift that makes the letters look most like English
+pub fn best_shift(letters: &[i64]) -> i64 {
+    (0..ALPHABET_SIZE)
+        .map(|shift| {
+            let shifted: Vec<i64> = letters
+                .iter()
+                .map(|x| (x - shift).rem_euclid(ALPHABET_SIZE))
+                .collect();
+            (shift, chi_squared(&shifted))
+        })
+        .fold(
+            (0, f64::MAX),
+            |best, next| if next.1 < best.1 { next } else { best },
+        )
+        .0
+}
+
+/// the key (a, b) among all 12 * 26 whose decryption looks most like English
+pub fn break_affine(letters: &[i64]) -> (i64, i64) {
+    let mut best = ((1, 0), f64::MAX);
+    for a in (1..ALPHABET_SIZE).filter(|a| a.gcd(&ALPHABET_SIZE) == 1) {
+        let inverse = affine_inverse(a).unwrap_or(1);
+        for b in 0..ALPHABET_SIZE {
+            let decrypted: Vec<i64> = letters
+                .iter()
+                .map(|y| (inverse * (y - b)).rem_euclid(ALPHABET_SIZE))
+                .collect();
+            let score = chi_squared(&decrypted);
+            if score < best.1 {
+                best = ((a, b), score);
+            }
+        }
+    }
+    best.0
+}
+
+/// the letters at positions offset, offset + length, ...
+fn column(letters: &[i64], length: usize, offset: usize) -> Vec<i64> {
+    letters
+        .iter()
+        .skip(offset)
+        .step_by(length)
+        .cloned()
+        .collect()
+}
+
+/// the mean index of coincidence of the columns a key of this length would shift alike
+pub fn column_coincidence(letters: &[i64], length: usize) -> f64 {
+    (0..length)
+        .map(|offset| index_of_coincidence(&column(letters, length, offset)))
+        .sum::<f64>()
+        / length as f64
+}
+
+/// Friedman's estimate of the key length from the index of coincidence
+pub fn friedman_estimate(letters: &[i64]) -> f64 {
+    let n = letters.len() as f64;
+    let random = 1.0 / ALPHABET_SIZE as f64;
+    let ic = index_of_coincidence(letters);
+    (ENGLISH_COINCIDENCE - random) * n / ((n - 1.0) * ic - random * n + ENGLISH_COINCIDENCE)
+}
+
+/// the shortest key length whose columns read like English, then every column's best shift
+pub fn break_vigenere(letters: &[i64]) -> Vec<i64> {
+    let limit = KEY_LENGTH_LIMIT.min(letters.len() / 2).max(1);
+    let scores: Vec<(usize, f64)> = (1..=limit)
+        .map(|length| (length, column_coincidence(letters, length)))
+        .collect();
+    let best = scores.iter().map(|(_, score)| *score).fold(0.0, f64::max);
+    // multiples of the key length score as well, so the first one close to the best wins
+    let length = scores
+        .iter()
+        .find(|(_, score)| *score >= 0.9 * best)
+        .map_or(1, |(length, _)| *length);
+    (0..length)
+        .map(|offset| best_shift(&column(letters, length, offset)))
+        .collect()
+}
+
+/// the cipher letters by falling frequency matched with English ones, a start for breaking
+/// a substitution by hand
+pub fn frequency_guess(letters: &[i64]) -> Vec<i64> {
+    let counts = letter_counts(letters);
+    let mut by_frequency: Vec<i64> = (0..ALPHABET_SIZE).collect();
+    by_frequency.sort_by_key(|x| std::cmp::Reverse(counts[*x as usize]));
+    let mut key = vec![0; ALPHABET_SIZE as usize];
+    for (english, cipher) in ENGLISH_ORDER.chars().zip(by_frequency) {
+        key[letter_index(english).unwrap_or(0) as usize] = cipher;
+    }
+    key
+}
+
+/// repeated trigrams, the distances between their occurrences and how many of those
+/// distances every possible key length divides
+#[derive(Debug, Clone, Default)]
+pub struct KasiskiReport {
+    pub repeats: Vec<(String, Vec<usize>)>,
+    pub factor_counts: Vec<(usize, usize)>,
+}
+
+impl KasiskiReport {
+    pub fn new(letters: &[i64]) -> Self {
+        let mut positions: HashMap<String, Vec<usize>> = HashMap::new();
+        for (i, window) in letters.windows(3).enumerate() {
+            let trigram: String = window.iter().map(|x| letter(*x)).collect();
+            positions.entry(trigram).or_default().push(i);
+        }
+        let mut repeats: Vec<(String, Vec<usize>)> = positions
+            .into_iter()
+            .filter(|(_, positions)| positions.len() > 1)
+            .map(|(trigram, positions)| {
+                let distances = positions.windows(2).map(|w| w[1] - w[0]).collect();
+                (trigram, distances)
+            })
+            .collect();
+        repeats.sort();
+        let distances: Vec<usize> = repeats
+            .iter()
+            .flat_map(|(_, distances)| distances.iter().cloned())
+            .collect();
+        let factor_counts = (2..=KEY_LENGTH_LIMIT)
+            .map(|length| {
+                let count = distances.iter().filter(|d| *d % length == 0).count();
+                (length, count)
+            })
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        Self {
+            repeats,
+            factor_counts,
+        }
+    }
+}
+
+fn key_to_string(key: &[i64]) -> String {
+    key.iter().map(|x| letter(*x)).collect()
+}
+
+fn matrix_to_string(matrix: &[Vec<BigInt>]) -> String {
+    let rows: Vec<String> = matrix
+        .iter()
+        .map(|row| {
+            let entries: Vec<String> = row.iter().map(|entry| entry.to_string()).collect();
+            entries.join(" ")
+        })
+        .collect();
+    rows.join("; ")
+}
+
+#[derive(Debug, Clone)]
+pub enum IcedCipherMessage {
+    CipherSelected(Cipher),
+    EditKey(String),
+    EditText(String),
+    Encrypt,
+    Decrypt,
+    Break,
+    Analyse,
+    UseOutput,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IcedCiphers {
+    cipher: Cipher,
+    key_text: String,
+    key_input: text_input::State,
+    text: String,
+    text_input: text_input::State,
+    encrypt_button: button::State,
+    decrypt_button: button::State,
+    break_button: button::State,
+    analyse_button: button::State,
+    use_output_button: button::State,
+
+    output: Option<String>,
+    steps: Vec<String>,
+    /// the letters of the analysed text
+    analysed: Option<Vec<i64>>,
+    error_message: Option<String>,
+}
+
+impl IcedCiphers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, message: IcedCipherMessage) {
+        self.error_message = None;
+        match message {
+            IcedCipherMessage::CipherSelected(cipher) => self.cipher = cipher,
+            IcedCipherMessage::EditKey(content) => self.key_text = content,
+            IcedCipherMessage::EditText(content) => self.text = content,
+            IcedCipherMessage::UseOutput => {
+                if let Some(output) = &self.output {
+                    self.text = output.clone();
+                }
+            }
+            IcedCipherMessage::Analyse => {
+                let letters = letters(&self.text);
+                if letters.is_empty() {
+                    self.error_message = Some(String::from("the text has no letters"));
+                } else {
+                    self.analysed = Some(letters);
+                }
+            }
+            IcedCipherMessage::Break => {
+                self.steps.clear();
+                let letters = letters(&self.text);
+                if letters.is_empty() {
+                    self.error_message = Some(String::from("the text has no letters"));
+                    return;
+                }
+                self.break_cipher(&letters);
+                self.analysed = Some(letters);
+            }
+            IcedCipherMessage::Encrypt | IcedCipherMessage::Decrypt => {
+                self.steps.clear();
+                let decrypt = matches!(message, IcedCipherMessage::Decrypt);
+                match self.apply(decrypt) {
+                    Ok(output) => self.output = Some(output),
+                    Err(message) => self.error_message = Some(message),
+                }
+            }
+        }
+    }
+
+    fn apply(&mut self, decrypt: bool) -> Result<String, String> {
+        let text = &self.text;
+        let hint = self.cipher.key_hint();
+        let invalid = || format!("the key must be: {}", hint);
+        match self.cipher {
+            Cipher::Caesar => {
+                let shift = parse_shift(&self.key_text).ok_or_else(invalid)?;
+                Ok(caesar(text, if decrypt { -shift } else { shift }))
+            }
+            Cipher::Affine => {
+                let (a, b) = parse_affine_key(&self.key_text).ok_or_else(invalid)?;
+                let inverse = affine_inverse(a).ok_or_else(|| {
+                    format!("gcd({}, 26) != 1, x -> {}x + {} is not invertible", a, a, b)
+                })?;
+                if decrypt {
+                    self.steps.push(format!(
+                        "a^-1 = {}^-1 mod 26 = {}, so y -> {} (y - {})",
+                        a, inverse, inverse, b
+                    ));
+                    affine_decrypt(text, a, b).ok_or_else(invalid)
+                } else {
+                    Ok(affine(text, a, b))
+                }
+            }
+            Cipher::Vigenere => {
+                let key = parse_word_key(&self.key_text).ok_or_else(invalid)?;
+                Ok(vigenere(text, &key, decrypt))
+            }
+            Cipher::Hill => {
+                let key = parse_hill_key(&self.key_text).ok_or_else(invalid)?;
+                let modulo = BigInt::from(ALPHABET_SIZE);
+                let det = determinant_mod(&key, &modulo);
+                let inverse = matrix_inverse_mod(&key, &modulo).ok_or_else(|| {
+                    format!("det K = {} mod 26 is no unit, K has no inverse mod 26", det)
+                })?;
+                self.steps.push(format!(
+                    "det K = {}, det^-1 = {} mod 26",
+                    det,
+                    mod_inverse(&det, &modulo).unwrap_or_default()
+                ));
+                if decrypt {
+                    self.steps.push(format!(
+                        "K^-1 = det^-1 adj(K) mod 26 = {}",
+                        matrix_to_string(&inverse)
+                    ));
+                    Ok(hill(text, &inverse))
+                } else {
+                    Ok(hill(text, &key))
+                }
+            }
+            Cipher::Substitution => {
+                let key = parse_substitution_key(&self.key_text).ok_or_else(invalid)?;
+                if decrypt {
+                    Ok(substitution(text, &inverse_substitution(&key)))
+                } else {
+                    Ok(substitution(text, &key))
+                }
+            }
+        }
+    }
+
+    /// ciphertext only attacks, the found key also goes into the key field
+    fn break_cipher(&mut self, letters: &[i64]) {
+        let text = &self.text;
+        match self.cipher {
+            Cipher::Caesar => {
+                let shift = best_shift(letters);
+                self.steps.push(format!(
+                    "the shift {} gives the smallest chi-squared distance to English",
+                    shift
+                ));
+                self.key_text = shift.to_string();
+                self.output = Some(caesar(text, -shift));
+            }
+            Cipher::Affine => {
+                let (a, b) = break_affine(letters);
+                self.steps.push(format!(
+                    "of all 12 * 26 keys ({}, {}) gives the decryption closest to English",
+                    a, b
+                ));
+                self.key_text = format!("{}, {}", a, b);
+                self.output = affine_decrypt(text, a, b);
+            }
+            Cipher::Vigenere => {
+                let key = break_vigenere(letters);
+                self.steps.push(format!(
+                    "Friedman estimates a key length of {:.1}",
+                    friedman_estimate(letters)
+                ));
+                self.steps.push(format!(
+                    "key length {}: its columns have a mean index of coincidence of {:.4}, \
+                     each column is then broken like a Caesar cipher",
+                    key.len(),
+                    column_coincidence(letters, key.len())
+                ));
+                self.key_text = key_to_string(&key);
+                self.output = Some(vigenere(text, &key, true));
+            }
+            Cipher::Hill => {
+                self.error_message = Some(String::from(
+                    "a Hill cipher is linear, it falls to known plaintext rather than to letter counts",
+                ));
+            }
+            Cipher::Substitution => {
+                let key = frequency_guess(letters);
+                self.steps.push(String::from(
+                    "letters matched by frequency rank, a first guess to refine by hand",
+                ));
+                self.key_text = key_to_string(&key);
+                self.output = Some(substitution(text, &inverse_substitution(&key)));
+            }
+        }
+    }
+
+    pub fn view(&mut self) -> Element<'_, IcedCipherMessage> {
+        let selected = self.cipher;
+        let ciphers = Cipher::ALL.iter().cloned().fold(
+            Row::new().spacing(10).push(Text::new("Cipher: ")),
+            |row, cipher| {
+                row.push(Radio::new(
+                    cipher,
+                    cipher,
+                    Some(selected),
+                    IcedCipherMessage::CipherSelected,
+                ))
+            },
+        );
+        let input = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new("Key: "))
+            .push(
+                TextInput::new(
+                    &mut self.key_input,
+                    self.cipher.key_hint(),
+                    &self.key_text,
+                    IcedCipherMessage::EditKey,
+                )
+                .width(Length::Units(300)),
+            )
+            .push(Text::new("Text: "))
+            .push(TextInput::new(
+                &mut self.text_input,
+                "plaintext or ciphertext",
+                &self.text,
+                IcedCipherMessage::EditText,
+            ));
+        let buttons = Row::new()
+            .spacing(10)
+            .push(
+                Button::new(&mut self.encrypt_button, Text::new("Encrypt"))
+                    .on_press(IcedCipherMessage::Encrypt),
+            )
+            .push(
+                Button::new(&mut self.decrypt_button, Text::new("Decrypt"))
+                    .on_press(IcedCipherMessage::Decrypt),
+            )
+            .push(
+                Button::new(&mut self.break_button, Text::new("Break"))
+                    .on_press(IcedCipherMessage::Break),
+            )
+            .push(
+                Button::new(&mut self.analyse_button, Text::new("Analyse text"))
+                    .on_press(IcedCipherMessage::Analyse),
+            )
+            .push(
+                Button::new(&mut self.use_output_button, Text::new("Output to text"))
+                    .on_press(IcedCipherMessage::UseOutput),
+            );
+
+        let mut content = Column::new()
+            .spacing(10)
+            .push(ciphers)
+            .push(input)
+            .push(buttons);
+        content = self
+            .steps
+            .iter()
+            .fold(content, |col, step| col.push(Text::new(step)));
+        if let Some(output) = &self.output {
+            content = content.push(Text::new(format!("Output: {}", output)));
+        }
+        if let Some(letters) = &self.analysed {
+            content = content.push(analysis_view(letters));
+        }
+
+        let maybe_error = match &self.error_message {
+            None => Text::new(""),
+            Some(message) => Text::new(message).color([0.921, 0.039, 0.039]),
+        };
+        content.push(maybe_error).into()
+    }
+}
+
+/// histogram against English, index of coincidence and the Kasiski examination
+fn analysis_view<'a>(letters: &[i64]) -> Column<'a, IcedCipherMessage> {
+    let counts = letter_counts(letters);
+    let n = letters.len() as f64;
+    let frequencies: Vec<f64> = counts.iter().map(|c| *c as f64 / n * 100.0).collect();
+    let max = frequencies
+        .iter()
+        .chain(ENGLISH_FREQUENCIES.iter())
+        .cloned()
+        .fold(1.0, f64::max);
+    let scale = HISTOGRAM_HEIGHT / max;
+    let text_color = Color::from_rgb(0.2, 0.4, 0.8);
+    let english_color = Color::from_rgb(0.7, 0.7, 0.7);
+    let bar = |value: f64, color: Color| {
+        let height = (value * scale).round() as u16;
+        Column::new()
+            .push(Space::new(
+                Length::Units(HISTOGRAM_BAR_WIDTH),
+                Length::Units(HISTOGRAM_HEIGHT as u16 - height),
+            ))
+            .push(
+                Container::new(Space::new(
+                    Length::Units(HISTOGRAM_BAR_WIDTH),
+                    Length::Units(height),
+                ))
+                .style(CellStyle::new(color)),
+            )
+    };
+    let histogram = (0..ALPHABET_SIZE as usize).fold(
+        Row::new().spacing(6).align_items(Align::End),
+        |row, x| {
+            row.push(
+                Column::new()
+                    .align_items(Align::Center)
+                    .push(
+                        Row::new()
+                            .push(bar(frequencies[x], text_color))
+                            .push(bar(ENGLISH_FREQUENCIES[x], english_color)),
+                    )
+                    .push(Text::new(letter(x as i64).to_string()).size(14)),
+            )
+        },
+    );
+
+    let ic = index_of_coincidence(letters);
+    let kasiski = KasiskiReport::new(letters);
+    let repeats: Vec<String> = kasiski
+        .repeats
+        .iter()
+        .take(15)
+        .map(|(trigram, distances)| {
+            let distances: Vec<String> = distances.iter().map(|d| d.to_string()).collect();
+            format!("{} ({})", trigram, distances.join(", "))
+        })
+        .collect();
+    let factors: Vec<String> = kasiski
+        .factor_counts
+        .iter()
+        .map(|(length, count)| format!("{}: {}", length, count))
+        .collect();
+
+    Column::new()
+        .spacing(5)
+        .push(Text::new(format!(
+            "{} letters, frequencies in blue against English in grey",
+            letters.len()
+        )))
+        .push(histogram)
+        .push(Text::new(format!(
+            "Index of coincidence {:.4}, English {:.4}, random {:.4}, chi-squared to English {:.1}",
+            ic,
+            ENGLISH_COINCIDENCE,
+            1.0 / ALPHABET_SIZE as f64,
+            chi_squared(letters)
+        )))
+        .push(Text::new(format!(
+            "Friedman key length estimate {:.1}",
+            friedman_estimate(letters)
+        )))
+        .push(Text::new(if repeats.is_empty() {
+            String::from("Kasiski: no repeated trigrams")
+        } else {
+            format!(
+                "Kasiski, repeated trigrams (distances): {}",
+                repeats.join(", ")
+            )
+        }))
+        .push(Text::new(format!(
+            "Distances divisible by each key length: {}",
+            factors.join(", ")
+        )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAIN: &str = "It was the best of times, it was the worst of times, it was the age of \
+        wisdom, it was the age of foolishness, it was the epoch of belief, it was the epoch of \
+        incredulity, it was the season of Light, it was the season of Darkness, it was the spring \
+        of hope, it was the winter of despair, we had everything before us, we had nothing before \
+        us, we were all going direct to Heaven, we were all going direct the other way";
+
+    #[test]
+    fn every_cipher_round_trips() {
+        assert_eq!(caesar(&caesar(PLAIN, 7), -7), PLAIN);
+        assert_eq!(caesar("Hello, World", 3), "Khoor, Zruog");
+        assert_eq!(affine_decrypt(&affine(PLAIN, 5, 8), 5, 8).unwrap(), PLAIN);
+        assert_eq!(affine_decrypt("x", 13, 0), None);
+        let key = parse_word_key("LEMON").unwrap();
+        assert_eq!(vigenere("ATTACKATDAWN", &key, false), "LXFOPVEFRNHR");
+        assert_eq!(vigenere(&vigenere(PLAIN, &key, false), &key, true), PLAIN);
+        let key = parse_substitution_key("QWERTYUIOPASDFGHJKLZXCVBNM").unwrap();
+        assert_eq!(
+            substitution(&substitution(PLAIN, &key), &inverse_substitution(&key)),
+            PLAIN
+        );
+    }
+
+    #[test]
+    fn large_keys_are_reduced_mod_26() {
+        assert_eq!(parse_shift("9223372036854775807"), Some(7));
+        assert_eq!(parse_shift("-1"), Some(25));
+        assert_eq!(parse_shift("d"), Some(3));
+        assert_eq!(parse_affine_key("4611686018427387903, 0"), Some((3, 0)));
+        assert_eq!(
+            parse_affine_key("-21, 100000000000000000000"),
+            Some((5, 22))
+        );
+        assert_eq!(
+            caesar("xyz", parse_shift("9223372036854775807").unwrap()),
+            "efg"
+        );
+    }
+
+    #[test]
+    fn hill_key_and_its_inverse_cancel() {
+        let key = parse_hill_key("3 3; 2 5").unwrap();
+        let modulo = BigInt::from(ALPHABET_SIZE);
+        let inverse = matrix_inverse_mod(&key, &modulo).unwrap();
+        let expected: Vec<Vec<BigInt>> = vec![
+            vec![BigInt::from(15), BigInt::from(17)],
+            vec![BigInt::from(20), BigInt::from(9)],
+        ];
+        assert_eq!(inverse, expected);
+        assert_eq!(hill("HELP", &key), "HIAT");
+        assert_eq!(hill(&hill("HELP", &key), &inverse), "HELP");
+        assert!(matrix_inverse_mod(&parse_hill_key("2 0; 0 1").unwrap(), &modulo).is_none());
+        assert!(parse_hill_key("1 2; 3").is_none());
+    }
+
+    #[test]
+    fn substitution_keys_must_be_permutations() {
+        assert!(parse_substitution_key("ABCDEFGHIJKLMNOPQRSTUVWXYZ").is_some());
+        assert!(parse_substitution_key("ABCDEFGHIJKLMNOPQRSTUVWXYY").is_none());
+        assert!(parse_substitution_key("ABC").is_none());
+    }
+
+    #[test]
+    fn index_of_coincidence_counts_equal_pairs() {
+        assert_eq!(index_of_coincidence(&letters("AAAA")), 1.0);
+        assert_eq!(index_of_coincidence(&letters("ABCD")), 0.0);
+        assert!((index_of_coincidence(&letters("AABB")) - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(index_of_coincidence(&letters("A")), 0.0);
+        assert!(index_of_coincidence(&letters(PLAIN)) > 0.055);
+    }
+
+    #[test]
+    fn kasiski_finds_the_distances_of_repeated_trigrams() {
+        let report = KasiskiReport::new(&letters("ABCXXABCYZYWABC"));
+        assert_eq!(report.repeats, vec![(String::from("ABC"), vec![5, 7])]);
+        assert!(report.factor_counts.contains(&(5, 1)));
+        assert!(report.factor_counts.contains(&(7, 1)));
+        assert!(!report.factor_counts.iter().any(|(length, _)| *length == 2));
+    }
+
+    #[test]
+    fn classical_ciphers_are_broken() {
+        let plain = letters(PLAIN);
+        assert_eq!(best_shift(&letters(&caesar(PLAIN, 11))), 11);
+        assert_eq!(break_affine(&letters(&affine(PLAIN, 7, 3))), (7, 3));
+        let key = parse_word_key("KEY").unwrap();
+        let cipher = letters(&vigenere(PLAIN, &key, false));
+        assert_eq!(break_vigenere(&cipher), key);
+        assert_eq!(
+            letters(&vigenere(&key_to_string(&cipher), &key, true)),
+            plain
+        );
+    }
+}
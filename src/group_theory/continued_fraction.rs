@@ -0,0 +1,633 @@
+use crate::group_theory::number_theory::{extended_euclidean_as_dmatrix, parse_big};
+use crate::group_theory::EEA_INDICES;
+use glucose::DMatrix;
+use iced::{button, text_input, Align, Button, Column, Element, Radio, Row, Text, TextInput};
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use std::collections::HashMap;
+
+/// the period of a quadratic irrational is searched for this many terms
+pub const PERIOD_LIMIT: usize = 10_000;
+/// convergent tables are cut off after this many rows
+const CONVERGENT_ROW_LIMIT: usize = 40;
+const PELL_SOLUTION_COUNT: u32 = 4;
+const CONVERGENT_INDICES: [&str; 4] = ["n", "a_n", "p_n", "q_n"];
+
+/// p_n / q_n for every prefix of the partial quotients
+pub fn convergents(quotients: &[BigInt]) -> Vec<(BigInt, BigInt)> {
+    // p_-1 = 1, q_-1 = 0 and p_-2 = 0, q_-2 = 1
+    let (mut p, mut p_prev) = (BigInt::one(), BigInt::zero());
+    let (mut q, mut q_prev) = (BigInt::zero(), BigInt::one());
+    quotients
+        .iter()
+        .map(|a| {
+            let p_next = a * &p + &p_prev;
+            let q_next = a * &q + &q_prev;
+            p_prev = std::mem::replace(&mut p, p_next);
+            q_prev = std::mem::replace(&mut q, q_next);
+            (p.clone(), q.clone())
+        })
+        .collect()
+}
+
+/// columns n, a_n, p_n, q_n as the EEA screen lays out a, b, k, s, t
+pub fn convergent_table(quotients: &[BigInt]) -> DMatrix<BigInt> {
+    let rows = quotients.len().min(CONVERGENT_ROW_LIMIT);
+    let convergents = convergents(&quotients[..rows]);
+    DMatrix::new(vec![
+        (0..rows).map(BigInt::from).collect(),
+        quotients[..rows].to_vec(),
+        convergents.iter().map(|(p, _)| p.clone()).collect(),
+        convergents.into_iter().map(|(_, q)| q).collect(),
+    ])
+}
+
+/// the expansion of p / q; the quotients after a_0 are the k column of the EEA of r = p mod q
+/// and q, whose last k is the sentinel -1 and stands for the final quotient b / a
+pub fn rational_expansion(p: &BigInt, q: &BigInt) -> (Vec<BigInt>, Option<DMatrix<BigInt>>) {
+    let (a0, r) = p.div_mod_floor(q);
+    let mut quotients = vec![a0];
+    if r.is_zero() {
+        return (quotients, None);
+    }
+    let eea = extended_euclidean_as_dmatrix(&r, q);
+    let steps = eea.data[2].len();
+    quotients.extend(eea.data[2][..steps - 1].iter().cloned());
+    quotients.push(&eea.data[1][steps - 1] / &eea.data[0][steps - 1]);
+    (quotients, Some(eea))
+}
+
+/// (P + sqrt(D)) / Q with D > 0 no square, expanded by m_n+1 = a_n d_n - m_n and
+/// d_n+1 = (D - m_n+1^2) / d_n, which needs d_0 | D - m_0^2
+#[derive(Debug, Clone)]
+pub struct QuadraticExpansion {
+    pub p: BigInt,
+    pub d: BigInt,
+    pub q: BigInt,
+    pub preperiod: Vec<BigInt>,
+    /// empty if no repeat was found within PERIOD_LIMIT terms
+    pub period: Vec<BigInt>,
+}
+
+impl QuadraticExpansion {
+    pub fn new(p: &BigInt, d: &BigInt, q: &BigInt) -> Result<Self, String> {
+        if !d.is_positive() {
+            return Err(String::from("D must be positive"));
+        }
+        let root = d.sqrt();
+        if &(&root * &root) == d {
+            return Err(format!(
+                "{} = {}^2 is a square, the number is rational",
+                d, root
+            ));
+        }
+        if q.is_zero() {
+            return Err(String::from("Q must not be 0"));
+        }
+        // (P + sqrt(D)) / Q = (P|Q| + sqrt(DQ^2)) / (Q|Q|) makes Q divide D - P^2
+        let (mut m, mut denominator, radicand) = if ((d - p * p) % q).is_zero() {
+            (p.clone(), q.clone(), d.clone())
+        } else {
+            (p * q.abs(), q * q.abs(), d * q * q)
+        };
+        let root = radicand.sqrt();
+
+        let mut seen: HashMap<(BigInt, BigInt), usize> = HashMap::new();
+        let mut quotients = Vec::new();
+        while quotients.len() < PERIOD_LIMIT {
+            if let Some(start) = seen.insert((m.clone(), denominator.clone()), quotients.len()) {
+                let period = quotients.split_off(start);
+                return Ok(Self {
+                    p: p.clone(),
+                    d: d.clone(),
+                    q: q.clone(),
+                    preperiod: quotients,
+                    period,
+                });
+            }
+            // sqrt(D) is irrational, so floor((m + sqrt(D)) / d) follows from floor(sqrt(D))
+            let a = if denominator.is_positive() {
+                (&m + &root).div_floor(&denominator)
+            } else {
+                -((&m + &root).div_floor(&-&denominator) + 1u32)
+            };
+            m = &a * &denominator - &m;
+            denominator = (&radicand - &m * &m) / &denominator;
+            quotients.push(a);
+        }
+        Ok(Self {
+            p: p.clone(),
+            d: d.clone(),
+            q: q.clone(),
+            preperiod: quotients,
+            period: Vec::new(),
+        })
+    }
+
+    /// the preperiod and then the period repeated until there are at least count terms
+    pub fn quotients(&self, count: usize) -> Vec<BigInt> {
+        let mut quotients = self.preperiod.clone();
+        while quotients.len() < count && !self.period.is_empty() {
+            quotients.extend(self.period.iter().cloned());
+        }
+        quotients
+    }
+
+    pub fn value(&self) -> f64 {
+        let to_f64 = |x: &BigInt| x.to_f64().unwrap_or(f64::NAN);
+        (to_f64(&self.p) + to_f64(&self.d).sqrt()) / to_f64(&self.q)
+    }
+}
+
+/// [a_0; a_1, ..., (period)]
+pub fn format_expansion(preperiod: &[BigInt], period: &[BigInt]) -> String {
+    let join = |terms: &[BigInt]| {
+        let terms: Vec<String> = terms.iter().map(|a| a.to_string()).collect();
+        terms.join(", ")
+    };
+    let (head, tail) = preperiod
+        .split_first()
+        .map_or((String::new(), &[][..]), |(a0, rest)| {
+            (a0.to_string(), rest)
+        });
+    let mut body = join(tail);
+    if !period.is_empty() {
+        if !body.is_empty() {
+            body.push_str(", ");
+        }
+        body.push_str(&format!("({})", join(period)));
+    }
+    if body.is_empty() {
+        format!("[{}]", head)
+    } else if head.is_empty() {
+        format!("[{}]", body)
+    } else {
+        format!("[{}; {}]", head, body)
+    }
+}
+
+/// the solutions of x^2 - Dy^2 = +1 and -1 from the expansion of sqrt(D)
+#[derive(Debug, Clone)]
+pub struct PellSolution {
+    pub d: BigInt,
+    pub expansion: QuadraticExpansion,
+    /// (x, y) with x^2 - Dy^2 = -1, exists iff the period length is odd
+    pub negative: Option<(BigInt, BigInt)>,
+    /// the fundamental solution of x^2 - Dy^2 = 1 and its first powers
+    pub positive: Vec<(BigInt, BigInt)>,
+}
+
+impl PellSolution {
+    pub fn new(d: &BigInt) -> Result<Self, String> {
+        let expansion = QuadraticExpansion::new(&BigInt::zero(), d, &BigInt::one())?;
+        let r = expansion.period.len();
+        if r == 0 {
+            return Err(format!("no period found within {} terms", PERIOD_LIMIT));
+        }
+        // p_r-1^2 - D q_r-1^2 = (-1)^r, one more period squares it to +1
+        let quotients = expansion.quotients(2 * r);
+        let convergents = convergents(&quotients[..2 * r]);
+        let (x, y) = convergents[r - 1].clone();
+        let (negative, fundamental) = if r % 2 == 1 {
+            (Some((x, y)), convergents[2 * r - 1].clone())
+        } else {
+            (None, (x, y))
+        };
+        let mut positive = vec![fundamental.clone()];
+        let (x1, y1) = fundamental;
+        for _ in 1..PELL_SOLUTION_COUNT {
+            // (x + y sqrt(D)) (x1 + y1 sqrt(D))
+            let (x, y) = positive[positive.len() - 1].clone();
+            positive.push((&x * &x1 + d * &y * &y1, &x * &y1 + &y * &x1));
+        }
+        Ok(Self {
+            d: d.clone(),
+            expansion,
+            negative,
+            positive,
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ExpansionKind {
+    #[default]
+    Rational,
+    Quadratic,
+    Pell,
+}
+
+impl From<ExpansionKind> for String {
+    fn from(kind: ExpansionKind) -> Self {
+        String::from(match kind {
+            ExpansionKind::Rational => "p / q",
+            ExpansionKind::Quadratic => "(P + sqrt(D)) / Q",
+            ExpansionKind::Pell => "x^2 - Dy^2 = +-1",
+        })
+    }
+}
+
+impl ExpansionKind {
+    pub const ALL: [ExpansionKind; 3] = [
+        ExpansionKind::Rational,
+        ExpansionKind::Quadratic,
+        ExpansionKind::Pell,
+    ];
+}
+
+#[derive(Debug, Clone)]
+pub enum IcedContinuedFractionMessage {
+    KindSelected(ExpansionKind),
+    EditFirst(String),
+    EditSecond(String),
+    EditThird(String),
+    Calculate,
+}
+
+#[derive(Debug, Clone)]
+enum ExpansionResult {
+    Rational {
+        p: BigInt,
+        q: BigInt,
+        quotients: Vec<BigInt>,
+        eea: Option<DMatrix<BigInt>>,
+    },
+    Quadratic(QuadraticExpansion),
+    Pell(PellSolution),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IcedContinuedFraction {
+    kind: ExpansionKind,
+    first_text: String,
+    first_input: text_input::State,
+    second_text: String,
+    second_input: text_input::State,
+    third_text: String,
+    third_input: text_input::State,
+    calculate_button: button::State,
+
+    result: Option<ExpansionResult>,
+    error_message: Option<String>,
+}
+
+impl IcedContinuedFraction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, message: IcedContinuedFractionMessage) {
+        match message {
+            IcedContinuedFractionMessage::KindSelected(kind) => {
+                self.kind = kind;
+                self.result = None;
+            }
+            IcedContinuedFractionMessage::EditFirst(content) => self.first_text = content,
+            IcedContinuedFractionMessage::EditSecond(content) => self.second_text = content,
+            IcedContinuedFractionMessage::EditThird(content) => self.third_text = content,
+            IcedContinuedFractionMessage::Calculate => {
+                self.error_message = None;
+                match self.calculate() {
+                    Ok(result) => self.result = Some(result),
+                    Err(message) => {
+                        self.result = None;
+                        self.error_message = Some(message)
+                    }
+                }
+            }
+        }
+    }
+
+    fn calculate(&self) -> Result<ExpansionResult, String> {
+        let first = parse_big(&self.first_text);
+        let second = parse_big(&self.second_text);
+        match self.kind {
+            ExpansionKind::Rational => match (first, second) {
+                (Some(p), Some(q)) if q.is_positive() => {
+                    let (quotients, eea) = rational_expansion(&p, &q);
+                    Ok(ExpansionResult::Rational {
+                        p,
+                        q,
+                        quotients,
+                        eea,
+                    })
+                }
+                (Some(_), Some(_)) => Err(String::from("q must be positive")),
+                _ => Err(String::from("p and/or q is not a number")),
+            },
+            ExpansionKind::Quadratic => {
+                let third = parse_big(&self.third_text);
+                match (first, second, third) {
+                    (Some(p), Some(d), Some(q)) => {
+                        QuadraticExpansion::new(&p, &d, &q).map(ExpansionResult::Quadratic)
+                    }
+                    _ => Err(String::from("P, D and Q must be numbers")),
+                }
+            }
+            ExpansionKind::Pell => {
+                let d = first.ok_or("D is not a number")?;
+                PellSolution::new(&d).map(ExpansionResult::Pell)
+            }
+        }
+    }
+
+    pub fn view(&mut self) -> Element<'_, IcedContinuedFractionMessage> {
+        let selected = self.kind;
+        let kinds = ExpansionKind::ALL.iter().cloned().fold(
+            Row::new().spacing(10).push(Text::new("Expand: ")),
+            |row, kind| {
+                row.push(Radio::new(
+                    kind,
+                    kind,
+                    Some(selected),
+                    IcedContinuedFractionMessage::KindSelected,
+                ))
+            },
+        );
+
+        let labels: &[&str] = match self.kind {
+            ExpansionKind::Rational => &["p", "q"],
+            ExpansionKind::Quadratic => &["P", "D", "Q"],
+            ExpansionKind::Pell => &["D"],
+        };
+        let mut input = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new(format!("{}: ", labels[0])))
+            .push(
+                TextInput::new(
+                    &mut self.first_input,
+                    labels[0],
+                    &self.first_text,
+                    IcedContinuedFractionMessage::EditFirst,
+                )
+                .on_submit(IcedContinuedFractionMessage::Calculate),
+            );
+        if labels.len() > 1 {
+            input = input.push(Text::new(format!("{}: ", labels[1]))).push(
+                TextInput::new(
+                    &mut self.second_input,
+                    labels[1],
+                    &self.second_text,
+                    IcedContinuedFractionMessage::EditSecond,
+                )
+                .on_submit(IcedContinuedFractionMessage::Calculate),
+            );
+        }
+        if labels.len() > 2 {
+            input = input.push(Text::new(format!("{}: ", labels[2]))).push(
+                TextInput::new(
+                    &mut self.third_input,
+                    labels[2],
+                    &self.third_text,
+                    IcedContinuedFractionMessage::EditThird,
+                )
+                .on_submit(IcedContinuedFractionMessage::Calculate),
+            );
+        }
+        input = input.push(
+            Button::new(&mut self.calculate_button, Text::new("Calculate"))
+                .on_press(IcedContinuedFractionMessage::Calculate),
+        );
+
+        let result = match &self.result {
+            None => Column::new(),
+            Some(ExpansionResult::Rational {
+                p,
+                q,
+                quotients,
+                eea,
+            }) => {
+                let column = Column::new()
+                    .spacing(10)
+                    .push(Text::new(format!(
+                        "{} / {} = {}",
+                        p,
+                        q,
+                        format_expansion(quotients, &[])
+                    )))
+                    .push(table_view(
+                        &convergent_table(quotients),
+                        &CONVERGENT_INDICES,
+                    ));
+                match eea {
+                    Some(eea) => column
+                        .push(Text::new(format!(
+                            "a_0 = floor(p / q) = {}, the rest are the quotients k of the EEA of \
+                             p mod q = {} and q, the final -1 standing for b / a",
+                            quotients[0], eea.data[0][0]
+                        )))
+                        .push(table_view(eea, &EEA_INDICES)),
+                    None => column,
+                }
+            }
+            Some(ExpansionResult::Quadratic(expansion)) => {
+                let quotients = expansion.quotients(CONVERGENT_ROW_LIMIT);
+                Column::new()
+                    .spacing(10)
+                    .push(Text::new(format!(
+                        "({} + sqrt({})) / {} = {} ~ {}",
+                        expansion.p,
+                        expansion.d,
+                        expansion.q,
+                        format_expansion(&expansion.preperiod, &expansion.period),
+                        expansion.value()
+                    )))
+                    .push(Text::new(if expansion.period.is_empty() {
+                        format!("no period within {} terms", PERIOD_LIMIT)
+                    } else {
+                        format!(
+                            "preperiod length {}, period length {}",
+                            expansion.preperiod.len(),
+                            expansion.period.len()
+                        )
+                    }))
+                    .push(table_view(
+                        &convergent_table(&quotients),
+                        &CONVERGENT_INDICES,
+                    ))
+            }
+            Some(ExpansionResult::Pell(solution)) => pell_view(solution),
+        };
+
+        let maybe_error = match &self.error_message {
+            None => Text::new(""),
+            Some(message) => Text::new(message).color([0.921, 0.039, 0.039]),
+        };
+
+        Column::new()
+            .spacing(20)
+            .push(kinds)
+            .push(input)
+            .push(result)
+            .push(maybe_error)
+            .into()
+    }
+}
+
+/// the columns of a matrix under their headers, like the EEA screen
+fn table_view<'a, H: ToString>(
+    table: &DMatrix<BigInt>,
+    headers: &[H],
+) -> Row<'a, IcedContinuedFractionMessage> {
+    table.data.iter().enumerate().fold(
+        Row::new().spacing(20).align_items(Align::Center),
+        |row, (i, chunk)| {
+            row.push(chunk.iter().fold(
+                Column::new().push(Text::new(headers[i].to_string())),
+                |col, item| col.push(Text::new(item.to_string())),
+            ))
+        },
+    )
+}
+
+fn pell_view<'a>(solution: &PellSolution) -> Column<'a, IcedContinuedFractionMessage> {
+    let expansion = &solution.expansion;
+    let r = expansion.period.len();
+    let d = &solution.d;
+    let negative = match &solution.negative {
+        Some((x, y)) => format!(
+            "the period length {} is odd: x^2 - {}y^2 = -1 has (x, y) = ({}, {}) = (p_{}, q_{})",
+            r,
+            d,
+            x,
+            y,
+            r - 1,
+            r - 1
+        ),
+        None => format!(
+            "the period length {} is even: x^2 - {}y^2 = -1 has no solution",
+            r, d
+        ),
+    };
+    let fundamental_index = if r % 2 == 1 { 2 * r - 1 } else { r - 1 };
+    let quotients = expansion.quotients(CONVERGENT_ROW_LIMIT.max(fundamental_index + 1));
+    let positive = solution.positive.iter().enumerate().fold(
+        Column::new().spacing(5).push(Text::new(format!(
+            "x^2 - {}y^2 = 1, the fundamental solution is (p_{}, q_{}) and the others are \
+             its powers x + y sqrt({}) = (x_1 + y_1 sqrt({}))^k:",
+            d, fundamental_index, fundamental_index, d, d
+        ))),
+        |col, (k, (x, y))| col.push(Text::new(format!("    k = {}: ({}, {})", k + 1, x, y))),
+    );
+    Column::new()
+        .spacing(10)
+        .push(Text::new(format!(
+            "sqrt({}) = {}",
+            d,
+            format_expansion(&expansion.preperiod, &expansion.period)
+        )))
+        .push(Text::new(negative))
+        .push(positive)
+        .push(table_view(
+            &convergent_table(&quotients),
+            &CONVERGENT_INDICES,
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bigs(nums: &[i64]) -> Vec<BigInt> {
+        nums.iter().map(|&x| BigInt::from(x)).collect()
+    }
+
+    fn pair(x: u64, y: u64) -> (BigInt, BigInt) {
+        (BigInt::from(x), BigInt::from(y))
+    }
+
+    /// the first terms of the expansion of a float, exact enough for a few terms
+    fn float_quotients(mut x: f64, count: usize) -> Vec<BigInt> {
+        (0..count)
+            .map(|_| {
+                let a = x.floor();
+                x = 1.0 / (x - a);
+                BigInt::from(a as i64)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rational_expansion_and_convergents() {
+        let (quotients, eea) = rational_expansion(&BigInt::from(415), &BigInt::from(93));
+        assert_eq!(quotients, bigs(&[4, 2, 6, 7]));
+        assert!(eea.is_some());
+        assert_eq!(convergents(&quotients).last(), Some(&pair(415, 93)));
+        let (quotients, _) = rational_expansion(&BigInt::from(-7), &BigInt::from(3));
+        assert_eq!(quotients, bigs(&[-3, 1, 2]));
+        let (quotients, eea) = rational_expansion(&BigInt::from(12), &BigInt::from(4));
+        assert_eq!((quotients, eea.is_none()), (bigs(&[3]), true));
+    }
+
+    #[test]
+    fn quadratic_expansions_of_square_roots() {
+        for (d, a0, period) in [
+            (2, 1, vec![2]),
+            (13, 3, vec![1, 1, 1, 1, 6]),
+            (61, 7, vec![1, 4, 3, 1, 2, 2, 1, 3, 4, 1, 14]),
+        ] {
+            let expansion =
+                QuadraticExpansion::new(&BigInt::zero(), &BigInt::from(d), &BigInt::one()).unwrap();
+            assert_eq!(expansion.preperiod, bigs(&[a0]), "sqrt({})", d);
+            assert_eq!(expansion.period, bigs(&period), "sqrt({})", d);
+        }
+        assert!(
+            QuadraticExpansion::new(&BigInt::zero(), &BigInt::from(49), &BigInt::one()).is_err()
+        );
+        assert!(
+            QuadraticExpansion::new(&BigInt::one(), &BigInt::from(2), &BigInt::zero()).is_err()
+        );
+    }
+
+    #[test]
+    fn quadratic_expansion_when_q_does_not_divide() {
+        // Q = 3 does not divide D - P^2 = 1, and Q = -2 is negative
+        for (p, d, q) in [(1, 2, 3), (1, 5, -2), (2, 7, -5)] {
+            let expansion =
+                QuadraticExpansion::new(&BigInt::from(p), &BigInt::from(d), &BigInt::from(q))
+                    .unwrap();
+            assert!(!expansion.period.is_empty());
+            let value = (p as f64 + (d as f64).sqrt()) / q as f64;
+            assert!((expansion.value() - value).abs() < 1e-12);
+            assert_eq!(
+                expansion.quotients(8)[..8],
+                float_quotients(value, 8)[..],
+                "({} + sqrt({})) / {}",
+                p,
+                d,
+                q
+            );
+        }
+        let golden =
+            QuadraticExpansion::new(&BigInt::one(), &BigInt::from(5), &BigInt::from(-2)).unwrap();
+        assert_eq!(
+            (golden.preperiod, golden.period),
+            (bigs(&[-2, 2]), bigs(&[1]))
+        );
+    }
+
+    #[test]
+    fn pell_solutions() {
+        for (d, negative, fundamental) in [
+            (2, Some(pair(1, 1)), pair(3, 2)),
+            (13, Some(pair(18, 5)), pair(649, 180)),
+            (
+                61,
+                Some(pair(29_718, 3_805)),
+                pair(1_766_319_049, 226_153_980),
+            ),
+            (7, None, pair(8, 3)),
+        ] {
+            let d = BigInt::from(d);
+            let solution = PellSolution::new(&d).unwrap();
+            assert_eq!(solution.negative, negative, "D = {}", d);
+            assert_eq!(solution.positive[0], fundamental, "D = {}", d);
+            for (x, y) in &solution.positive {
+                assert!((x * x - &d * y * y).is_one());
+            }
+        }
+        assert!(PellSolution::new(&BigInt::from(16)).is_err());
+    }
+}
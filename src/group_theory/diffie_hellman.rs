@@ -0,0 +1,477 @@
+use crate::group_theory::finite_group::GroupType;
+use crate::group_theory::number_theory::{
+    divisors, group, is_prime, mod_inverse, multiplicative_order, orders, parse_big, producers,
+    wheel_factorization, XorShift, ENUMERATION_LIMIT,
+};
+use iced::{button, text_input, Align, Button, Column, Element, Row, Text, TextInput};
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive};
+
+/// key spaces up to this size list their elements
+const KEY_SPACE_LIST_LIMIT: usize = 50;
+
+/// Z_p^* with the orders of all its elements, small enough to enumerate
+#[derive(Debug, Clone)]
+pub struct DiffieHellmanGroup {
+    pub prime: BigInt,
+    pub phi: BigInt,
+    pub phi_factors: Vec<BigInt>,
+    /// (element, order) for every element of Z_p^*
+    pub orders: Vec<(BigInt, BigInt)>,
+    /// the elements of order p - 1
+    pub generators: Vec<BigInt>,
+}
+
+impl DiffieHellmanGroup {
+    pub fn new(prime: &BigInt) -> Result<Self, String> {
+        if prime <= &BigInt::from(2) || prime > &BigInt::from(ENUMERATION_LIMIT) {
+            return Err(format!("p must lie between 3 and {}", ENUMERATION_LIMIT));
+        }
+        if !is_prime(prime) {
+            return Err(format!("{} is not prime", prime));
+        }
+        let phi: BigInt = prime - 1u32;
+        let phi_factors = wheel_factorization(&phi);
+        let elements = group(prime, GroupType::MultiplicativeStar);
+        let orders = orders(
+            prime,
+            &elements,
+            GroupType::MultiplicativeStar,
+            &phi,
+            &phi_factors,
+        );
+        let generators = producers(&phi, &orders);
+        Ok(Self {
+            prime: prime.clone(),
+            phi,
+            phi_factors,
+            orders,
+            generators,
+        })
+    }
+
+    pub fn order(&self, base: &BigInt) -> Option<BigInt> {
+        multiplicative_order(&self.prime, base, &self.phi, &self.phi_factors)
+    }
+
+    /// the subgroup <base>, every value base^x can take
+    pub fn key_space(&self, base: &BigInt) -> Vec<BigInt> {
+        let mut space: Vec<BigInt> = Vec::new();
+        let mut power = BigInt::one();
+        loop {
+            space.push(power.clone());
+            power = power * base % &self.prime;
+            if power.is_one() {
+                break;
+            }
+        }
+        space.sort();
+        space
+    }
+
+    /// (d, number of elements of order d) for every divisor d of p - 1
+    pub fn order_counts(&self) -> Vec<(BigInt, usize)> {
+        divisors(&self.phi_factors)
+            .into_iter()
+            .map(|d| {
+                let count = self.orders.iter().filter(|(_, order)| order == &d).count();
+                (d, count)
+            })
+            .collect()
+    }
+}
+
+/// one side of an exchange, a private exponent and the public base^private
+#[derive(Debug, Clone)]
+pub struct KeyPair {
+    pub private: BigInt,
+    pub public: BigInt,
+}
+
+impl KeyPair {
+    pub fn new(base: &BigInt, private: BigInt, prime: &BigInt) -> Self {
+        Self {
+            public: base.modpow(&private, prime),
+            private,
+        }
+    }
+}
+
+/// ElGamal with an ephemeral exponent k: (g^k, m * B^k), where B is the receiver's public key
+#[derive(Debug, Clone)]
+pub struct ElGamalCipher {
+    pub c1: BigInt,
+    pub c2: BigInt,
+    pub shared: BigInt,
+}
+
+pub fn elgamal_encrypt(
+    base: &BigInt,
+    public: &BigInt,
+    m: &BigInt,
+    k: &BigInt,
+    prime: &BigInt,
+) -> ElGamalCipher {
+    let shared = public.modpow(k, prime);
+    ElGamalCipher {
+        c1: base.modpow(k, prime),
+        c2: m * &shared % prime,
+        shared,
+    }
+}
+
+/// m = c2 * (c1^b)^-1, returns (c1^b, m)
+pub fn elgamal_decrypt(
+    c1: &BigInt,
+    c2: &BigInt,
+    private: &BigInt,
+    prime: &BigInt,
+) -> Option<(BigInt, BigInt)> {
+    let shared = c1.modpow(private, prime);
+    let inverse = mod_inverse(&shared, prime)?;
+    Some((shared, c2 * inverse % prime))
+}
+
+#[derive(Debug, Clone)]
+pub enum IcedDiffieHellmanMessage {
+    EditPrime(String),
+    EditBase(String),
+    EditAlicePrivate(String),
+    EditBobPrivate(String),
+    EditMessage(String),
+    EditEphemeral(String),
+    Setup,
+    RandomKeys,
+    Exchange,
+    Encrypt,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IcedDiffieHellman {
+    prime_text: String,
+    prime_input: text_input::State,
+    base_text: String,
+    base_input: text_input::State,
+    setup_button: button::State,
+    alice_text: String,
+    alice_input: text_input::State,
+    bob_text: String,
+    bob_input: text_input::State,
+    random_button: button::State,
+    exchange_button: button::State,
+    message_text: String,
+    message_input: text_input::State,
+    ephemeral_text: String,
+    ephemeral_input: text_input::State,
+    encrypt_button: button::State,
+
+    group: Option<DiffieHellmanGroup>,
+    base: Option<BigInt>,
+    alice: Option<KeyPair>,
+    bob: Option<KeyPair>,
+    cipher: Option<(BigInt, BigInt, ElGamalCipher)>,
+    error_message: Option<String>,
+}
+
+impl IcedDiffieHellman {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, message: IcedDiffieHellmanMessage) {
+        self.error_message = None;
+        let result = match message {
+            IcedDiffieHellmanMessage::EditPrime(content) => {
+                self.prime_text = content;
+                Ok(())
+            }
+            IcedDiffieHellmanMessage::EditBase(content) => {
+                self.base_text = content;
+                Ok(())
+            }
+            IcedDiffieHellmanMessage::EditAlicePrivate(content) => {
+                self.alice_text = content;
+                Ok(())
+            }
+            IcedDiffieHellmanMessage::EditBobPrivate(content) => {
+                self.bob_text = content;
+                Ok(())
+            }
+            IcedDiffieHellmanMessage::EditMessage(content) => {
+                self.message_text = content;
+                Ok(())
+            }
+            IcedDiffieHellmanMessage::EditEphemeral(content) => {
+                self.ephemeral_text = content;
+                Ok(())
+            }
+            IcedDiffieHellmanMessage::Setup => self.setup(),
+            IcedDiffieHellmanMessage::RandomKeys => self.random_keys(),
+            IcedDiffieHellmanMessage::Exchange => self.exchange(),
+            IcedDiffieHellmanMessage::Encrypt => self.encrypt(),
+        };
+        if let Err(message) = result {
+            self.error_message = Some(message);
+        }
+    }
+
+    /// the group from p and the base from its input, the first generator if left empty
+    fn setup(&mut self) -> Result<(), String> {
+        self.group = None;
+        self.alice = None;
+        self.bob = None;
+        self.cipher = None;
+        let prime = parse_big(&self.prime_text).ok_or("p must be a number")?;
+        let group = DiffieHellmanGroup::new(&prime)?;
+        let base = if self.base_text.trim().is_empty() {
+            group.generators[0].clone()
+        } else {
+            parse_big(&self.base_text).ok_or("g must be a number")?
+        };
+        if base.is_negative() || base >= prime || group.order(&base).is_none() {
+            return Err(format!("g must be a unit of Z_{}^*", prime));
+        }
+        self.base_text = base.to_string();
+        self.base = Some(base);
+        self.group = Some(group);
+        Ok(())
+    }
+
+    fn random_keys(&mut self) -> Result<(), String> {
+        let group = self.group.as_ref().ok_or("set up the group first")?;
+        let bound = group.phi.to_u64().unwrap_or(1);
+        let mut random = XorShift::from_time();
+        // private exponents from 1 to p - 2, p - 1 would act like 0
+        let mut private = || (random.next_u64() % (bound - 1).max(1) + 1).to_string();
+        self.alice_text = private();
+        self.bob_text = private();
+        self.ephemeral_text = private();
+        self.exchange()
+    }
+
+    fn exchange(&mut self) -> Result<(), String> {
+        let (group, base) = match (&self.group, &self.base) {
+            (Some(group), Some(base)) => (group, base),
+            _ => return Err(String::from("set up the group first")),
+        };
+        let private = |text: &str, name: &str| {
+            parse_big(text)
+                .filter(|x| x.is_positive() && x < &group.phi)
+                .ok_or_else(|| format!("{}'s private key must lie in 1..{}", name, group.phi))
+        };
+        let alice = private(&self.alice_text, "Alice")?;
+        let bob = private(&self.bob_text, "Bob")?;
+        self.alice = Some(KeyPair::new(base, alice, &group.prime));
+        self.bob = Some(KeyPair::new(base, bob, &group.prime));
+        Ok(())
+    }
+
+    /// Alice encrypts for Bob with his public key
+    fn encrypt(&mut self) -> Result<(), String> {
+        self.exchange()?;
+        let (group, base, bob) = match (&self.group, &self.base, &self.bob) {
+            (Some(group), Some(base), Some(bob)) => (group, base, bob),
+            _ => return Err(String::from("set up the group first")),
+        };
+        let m = parse_big(&self.message_text)
+            .filter(|m| m.is_positive() && m < &group.prime)
+            .ok_or_else(|| format!("m must lie in 1..{}", group.prime))?;
+        let k = parse_big(&self.ephemeral_text)
+            .filter(|k| k.is_positive() && k < &group.phi)
+            .ok_or_else(|| format!("k must lie in 1..{}", group.phi))?;
+        let cipher = elgamal_encrypt(base, &bob.public, &m, &k, &group.prime);
+        self.cipher = Some((m, k, cipher));
+        Ok(())
+    }
+
+    pub fn view(&mut self) -> Element<'_, IcedDiffieHellmanMessage> {
+        let setup = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new("p: "))
+            .push(TextInput::new(
+                &mut self.prime_input,
+                "prime",
+                &self.prime_text,
+                IcedDiffieHellmanMessage::EditPrime,
+            ))
+            .push(Text::new("g: "))
+            .push(TextInput::new(
+                &mut self.base_input,
+                "empty for a generator",
+                &self.base_text,
+                IcedDiffieHellmanMessage::EditBase,
+            ))
+            .push(
+                Button::new(&mut self.setup_button, Text::new("Set up"))
+                    .on_press(IcedDiffieHellmanMessage::Setup),
+            );
+        let keys = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new("a: "))
+            .push(TextInput::new(
+                &mut self.alice_input,
+                "Alice's private key",
+                &self.alice_text,
+                IcedDiffieHellmanMessage::EditAlicePrivate,
+            ))
+            .push(Text::new("b: "))
+            .push(TextInput::new(
+                &mut self.bob_input,
+                "Bob's private key",
+                &self.bob_text,
+                IcedDiffieHellmanMessage::EditBobPrivate,
+            ))
+            .push(
+                Button::new(&mut self.random_button, Text::new("Random keys"))
+                    .on_press(IcedDiffieHellmanMessage::RandomKeys),
+            )
+            .push(
+                Button::new(&mut self.exchange_button, Text::new("Exchange"))
+                    .on_press(IcedDiffieHellmanMessage::Exchange),
+            );
+        let elgamal = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new("m: "))
+            .push(TextInput::new(
+                &mut self.message_input,
+                "message",
+                &self.message_text,
+                IcedDiffieHellmanMessage::EditMessage,
+            ))
+            .push(Text::new("k: "))
+            .push(TextInput::new(
+                &mut self.ephemeral_input,
+                "ephemeral key",
+                &self.ephemeral_text,
+                IcedDiffieHellmanMessage::EditEphemeral,
+            ))
+            .push(
+                Button::new(&mut self.encrypt_button, Text::new("ElGamal: Alice to Bob"))
+                    .on_press(IcedDiffieHellmanMessage::Encrypt),
+            );
+
+        let mut content = Column::new()
+            .spacing(10)
+            .push(setup)
+            .push(keys)
+            .push(elgamal);
+
+        if let (Some(group), Some(base)) = (&self.group, &self.base) {
+            content = content.push(group_view(group, base));
+            if let (Some(alice), Some(bob)) = (&self.alice, &self.bob) {
+                let p = &group.prime;
+                let mut alice_column = Column::new()
+                    .spacing(5)
+                    .push(Text::new("Alice"))
+                    .push(Text::new(format!("private a = {}", alice.private)))
+                    .push(Text::new(format!(
+                        "public A = g^a = {}^{} = {}",
+                        base, alice.private, alice.public
+                    )))
+                    .push(Text::new(format!(
+                        "shared B^a = {}^{} = {}",
+                        bob.public,
+                        alice.private,
+                        bob.public.modpow(&alice.private, p)
+                    )));
+                let mut bob_column = Column::new()
+                    .spacing(5)
+                    .push(Text::new("Bob"))
+                    .push(Text::new(format!("private b = {}", bob.private)))
+                    .push(Text::new(format!(
+                        "public B = g^b = {}^{} = {}",
+                        base, bob.private, bob.public
+                    )))
+                    .push(Text::new(format!(
+                        "shared A^b = {}^{} = {}",
+                        alice.public,
+                        bob.private,
+                        alice.public.modpow(&bob.private, p)
+                    )));
+                if let Some((m, k, cipher)) = &self.cipher {
+                    alice_column = alice_column
+                        .push(Text::new(format!("ElGamal with m = {}, k = {}", m, k)))
+                        .push(Text::new(format!("c1 = g^k = {}", cipher.c1)))
+                        .push(Text::new(format!("s = B^k = {}", cipher.shared)))
+                        .push(Text::new(format!("c2 = m s = {}", cipher.c2)));
+                    bob_column = bob_column.push(Text::new(format!(
+                        "receives (c1, c2) = ({}, {})",
+                        cipher.c1, cipher.c2
+                    )));
+                    bob_column = match elgamal_decrypt(&cipher.c1, &cipher.c2, &bob.private, p) {
+                        Some((shared, m)) => bob_column
+                            .push(Text::new(format!("s = c1^b = {}", shared)))
+                            .push(Text::new(format!(
+                                "m = c2 s^-1 = {} * {} = {}",
+                                cipher.c2,
+                                mod_inverse(&shared, p).unwrap_or_default(),
+                                m
+                            ))),
+                        None => bob_column.push(Text::new("s has no inverse")),
+                    };
+                }
+                content = content.push(Row::new().spacing(40).push(alice_column).push(bob_column));
+            }
+        }
+
+        let maybe_error = match &self.error_message {
+            None => Text::new(""),
+            Some(message) => Text::new(message).color([0.921, 0.039, 0.039]),
+        };
+        content.push(maybe_error).into()
+    }
+}
+
+/// the generators of Z_p^* and what a weaker base does to the key space
+fn group_view<'a>(
+    group: &DiffieHellmanGroup,
+    base: &BigInt,
+) -> Column<'a, IcedDiffieHellmanMessage> {
+    let order = group.order(base).unwrap_or_else(BigInt::one);
+    let generators: Vec<String> = group
+        .generators
+        .iter()
+        .take(KEY_SPACE_LIST_LIMIT)
+        .map(|g| g.to_string())
+        .collect();
+    let more = if group.generators.len() > KEY_SPACE_LIST_LIMIT {
+        ", ..."
+    } else {
+        ""
+    };
+    let mut column = Column::new()
+        .spacing(5)
+        .push(Text::new(format!(
+            "Z_{}^* has {} generators: {}{}",
+            group.prime,
+            group.generators.len(),
+            generators.join(", "),
+            more
+        )))
+        .push(Text::new(format!(
+            "g = {} has order {}, so g^x takes {} of {} values",
+            base, order, order, group.phi
+        )));
+    if order != group.phi {
+        let (cofactor, _) = group.phi.div_rem(&order);
+        column = column.push(Text::new(format!(
+            "g is no generator, the key space shrinks by a factor of {} and every shared secret lies in <g>",
+            cofactor
+        )));
+        let space = group.key_space(base);
+        if space.len() <= KEY_SPACE_LIST_LIMIT {
+            let space: Vec<String> = space.iter().map(|x| x.to_string()).collect();
+            column = column.push(Text::new(format!("<g> = {{{}}}", space.join(", "))));
+        }
+    }
+    group.order_counts().into_iter().fold(
+        column.push(Text::new(
+            "Orders in Z_p^*, a base of order d gives d keys:",
+        )),
+        |col, (d, count)| col.push(Text::new(format!("    order {}: {} elements", d, count))),
+    )
+}
@@ -0,0 +1,244 @@
+use crate::group_theory::finite_group::DihedralElement;
+use crate::utils::{canvas_label, element_color};
+use iced::canvas::{Canvas, Cursor, Frame, Geometry, Path, Program, Stroke};
+use iced::{button, Align, Button, Color, Column, Element, Length, Point, Rectangle, Row, Text};
+use num_integer::Integer;
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+/// largest n for which D_n is built and drawn, so that |D_n| fits the Cayley table
+pub const DIHEDRAL_LIMIT: u64 = 32;
+const POLYGON_HEIGHT: u16 = 220;
+/// vertices are drawn as circles of at most this radius, smaller once they would touch
+const VERTEX_RADIUS: f32 = 11.0;
+const EDGE_COLOR: Color = Color::from_rgb(0.6, 0.6, 0.6);
+const ROTATION_COLOR: Color = Color::from_rgb(0.2, 0.4, 0.8);
+const AXIS_COLOR: Color = Color::from_rgb(0.8, 0.2, 0.2);
+const ARROW_LENGTH: f32 = 8.0;
+const ARROW_SPREAD: f32 = 5.0;
+
+#[derive(Debug, Clone)]
+pub enum IcedDihedralMessage {
+    Select(usize),
+}
+
+/// a regular n-gon with labelled vertices, showing what the selected elements do to it
+#[derive(Debug, Clone)]
+pub struct IcedDihedral {
+    n: u64,
+    /// rotations e, r, ..., then reflections s, rs, ...
+    elements: Vec<DihedralElement>,
+    element_buttons: Vec<button::State>,
+    /// at most two elements, the product is shown once both are picked
+    selection: Vec<usize>,
+}
+
+impl IcedDihedral {
+    pub fn new(n: u64) -> Self {
+        let elements: Vec<DihedralElement> = [false, true]
+            .iter()
+            .flat_map(|reflection| (0..n).map(move |k| DihedralElement::new(n, k, *reflection)))
+            .collect();
+        let count = elements.len();
+        Self {
+            n,
+            elements,
+            element_buttons: vec![button::State::new(); count],
+            selection: Vec::new(),
+        }
+    }
+
+    pub fn update(&mut self, message: IcedDihedralMessage) {
+        match message {
+            IcedDihedralMessage::Select(index) => {
+                if self.selection.len() == 2 {
+                    self.selection.clear();
+                }
+                self.selection.push(index);
+            }
+        }
+    }
+
+    pub fn view(&mut self) -> Element<'_, IcedDihedralMessage> {
+        let n = self.n;
+        let elements = &self.elements;
+        let selection = &self.selection;
+
+        let (rotations, reflections) = self.element_buttons.iter_mut().enumerate().fold(
+            (Row::new().spacing(5), Row::new().spacing(5)),
+            |(rotations, reflections), (i, state)| {
+                let element = elements[i];
+                let label = if selection.contains(&i) {
+                    format!("[{}]", element)
+                } else {
+                    element.to_string()
+                };
+                let button = Button::new(state, Text::new(label).size(14))
+                    .padding(4)
+                    .on_press(IcedDihedralMessage::Select(i));
+                if element.reflection {
+                    (rotations, reflections.push(button))
+                } else {
+                    (rotations.push(button), reflections)
+                }
+            },
+        );
+
+        let identity = DihedralElement::new(n, 0, false);
+        let mut polygons = Row::new().spacing(30).push(polygon_view("e", &identity));
+        let picked: Vec<DihedralElement> = selection.iter().map(|i| elements[*i]).collect();
+        for element in &picked {
+            polygons = polygons.push(polygon_view(&element.to_string(), element));
+        }
+        let mut content = Column::new()
+            .spacing(10)
+            .push(Text::new(format!(
+                "D_{} acting on a regular {}-gon, pick two elements a and b for a * b (b is applied first)",
+                n, n
+            )))
+            .push(rotations)
+            .push(reflections);
+        for element in &picked {
+            content = content.push(Text::new(format!("{}: {}", element, describe(element))));
+        }
+        if let [a, b] = picked[..] {
+            let product = a.compose(&b);
+            polygons = polygons.push(polygon_view(
+                &format!("{} * {} = {}", a, b, product),
+                &product,
+            ));
+            content = content.push(Text::new(format!(
+                "{} * {} = {}: {}",
+                a,
+                b,
+                product,
+                describe(&product)
+            )));
+        }
+        content.push(polygons).into()
+    }
+}
+
+/// rotation angle or reflection axis, plus the order of the element
+fn describe(element: &DihedralElement) -> String {
+    let n = element.n;
+    if element.reflection {
+        // x -> k - x fixes the axis through the angle k/2 of a vertex step
+        format!(
+            "reflection across the axis {:.1} degrees from vertex 1, order 2",
+            180.0 * element.rotation as f64 / n as f64
+        )
+    } else {
+        format!(
+            "rotation by {:.1} degrees, order {}",
+            360.0 * element.rotation as f64 / n as f64,
+            n / element.rotation.gcd(&n)
+        )
+    }
+}
+
+/// the n-gon with a title, every position shows the vertex that was moved there
+fn polygon_view<'a, M: 'static>(title: &str, element: &DihedralElement) -> Column<'a, M> {
+    Column::new()
+        .spacing(5)
+        .width(Length::Fill)
+        .align_items(Align::Center)
+        .push(Text::new(title.to_string()))
+        .push(
+            Canvas::new(Polygon(*element))
+                .width(Length::Fill)
+                .height(Length::Units(POLYGON_HEIGHT)),
+        )
+}
+
+/// a regular n-gon after the element acted on it: vertex 1 starts on top and the labels
+/// run counter clockwise, every vertex keeps its colour so the motion is visible, a rotation
+/// is marked by an arrow from the top to where vertex 1 went, a reflection by its axis
+struct Polygon(DihedralElement);
+
+impl Polygon {
+    /// the angle of a position, counter clockwise from the positive x axis
+    fn angle(&self, position: f32) -> f32 {
+        FRAC_PI_2 + TAU * position / self.0.n as f32
+    }
+}
+
+impl<M> Program<M> for Polygon {
+    fn draw(&self, bounds: Rectangle, _cursor: Cursor) -> Vec<Geometry> {
+        let element = self.0;
+        let n = element.n;
+        let mut frame = Frame::new(bounds.size());
+        let center = frame.center();
+        let vertex_radius = VERTEX_RADIUS.min(bounds.height * PI / (2.0 * n as f32) * 0.8);
+        let radius = (bounds.width.min(bounds.height) / 2.0 - vertex_radius - 2.0).max(1.0);
+        // the y axis of the canvas points down
+        let point =
+            |angle: f32, r: f32| Point::new(center.x + r * angle.cos(), center.y - r * angle.sin());
+        let corners: Vec<Point> = (0..n)
+            .map(|p| point(self.angle(p as f32), radius))
+            .collect();
+
+        frame.stroke(
+            &Path::new(|builder| {
+                builder.move_to(corners[0]);
+                for corner in &corners[1..] {
+                    builder.line_to(*corner);
+                }
+                builder.close();
+            }),
+            Stroke::default().with_color(EDGE_COLOR).with_width(2.0),
+        );
+
+        if element.reflection {
+            // x -> k - x fixes the direction half way between the positions 0 and k
+            let axis = self.angle(element.rotation as f32 / 2.0);
+            let reach = radius + vertex_radius;
+            frame.stroke(
+                &Path::line(point(axis, reach), point(axis + PI, reach)),
+                Stroke::default().with_color(AXIS_COLOR).with_width(2.0),
+            );
+        } else if element.rotation > 0 {
+            let start = self.angle(0.0);
+            let end = self.angle(element.rotation as f32);
+            let arc_radius = radius * 0.5;
+            let steps = 48;
+            let arc = Path::new(|builder| {
+                builder.move_to(point(start, arc_radius));
+                for step in 1..=steps {
+                    let angle = start + (end - start) * step as f32 / steps as f32;
+                    builder.line_to(point(angle, arc_radius));
+                }
+                // the arrow head points along the counter clockwise tangent (-sin, -cos) of
+                // the canvas, its barbs spread along the radius (cos, -sin)
+                let tip = point(end, arc_radius);
+                let (sin, cos) = end.sin_cos();
+                for side in &[-1.0, 1.0] {
+                    builder.move_to(tip);
+                    builder.line_to(Point::new(
+                        tip.x + ARROW_LENGTH * sin + side * ARROW_SPREAD * cos,
+                        tip.y + ARROW_LENGTH * cos - side * ARROW_SPREAD * sin,
+                    ));
+                }
+            });
+            frame.stroke(
+                &arc,
+                Stroke::default().with_color(ROTATION_COLOR).with_width(2.0),
+            );
+        }
+
+        let inverse = element.inverse();
+        for (position, corner) in corners.iter().enumerate() {
+            let vertex = inverse.apply(position as u64);
+            let circle = Path::circle(*corner, vertex_radius);
+            frame.fill(&circle, element_color(vertex as usize, n as usize));
+            if vertex == 0 {
+                frame.stroke(&circle, Stroke::default().with_width(2.0));
+            }
+            frame.fill_text(canvas_label(
+                (vertex + 1).to_string(),
+                *corner,
+                (vertex_radius * 1.2).max(8.0),
+            ));
+        }
+        vec![frame.into_geometry()]
+    }
+}
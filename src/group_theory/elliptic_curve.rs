@@ -0,0 +1,856 @@
+use crate::group_theory::number_theory::{
+    is_prime, mul_mod_u64, parse_big, pow_mod_u64, prime_powers, sqrt_mod_prime_u64,
+    wheel_factorization, ENUMERATION_LIMIT,
+};
+use iced::canvas::{Canvas, Cursor, Frame, Geometry, Program};
+use iced::{
+    button, text_input, Align, Button, Color, Column, Element, Length, Rectangle, Row, Size, Text,
+    TextInput,
+};
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{Signed, ToPrimitive, Zero};
+use std::fmt;
+
+/// more points than this are only counted, not listed with their orders
+const POINT_LIST_LIMIT: usize = 100;
+const PLOT_SIZE: u16 = 640;
+/// room around the plot so that the points at the edges are drawn whole
+const PLOT_MARGIN: f32 = 8.0;
+/// the largest side of a point, they shrink with the cells for larger p
+const DOT_SIZE: f32 = 6.0;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Point {
+    /// the point at infinity, the identity of the group
+    Infinity,
+    Affine(u64, u64),
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Point::Infinity => write!(f, "O"),
+            Point::Affine(x, y) => write!(f, "({}, {})", x, y),
+        }
+    }
+}
+
+/// y^2 = x^3 + ax + b over F_p for a prime p > 3
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EllipticCurve {
+    pub a: u64,
+    pub b: u64,
+    pub prime: u64,
+}
+
+impl EllipticCurve {
+    pub fn new(a: &BigInt, b: &BigInt, prime: u64) -> Result<Self, String> {
+        if prime <= 3 || !is_prime(&BigInt::from(prime)) {
+            return Err(String::from(
+                "p must be a prime above 3, fields of characteristic 2 and 3 need another curve form",
+            ));
+        }
+        let modulo = BigInt::from(prime);
+        let a = a.mod_floor(&modulo).to_u64().unwrap_or(0);
+        let b = b.mod_floor(&modulo).to_u64().unwrap_or(0);
+        let curve = Self { a, b, prime };
+        if curve.discriminant() == 0 {
+            return Err(format!(
+                "4a^3 + 27b^2 = 0 mod {}, the curve is singular",
+                prime
+            ));
+        }
+        Ok(curve)
+    }
+
+    fn add_mod(&self, a: u64, b: u64) -> u64 {
+        ((a as u128 + b as u128) % self.prime as u128) as u64
+    }
+
+    fn sub_mod(&self, a: u64, b: u64) -> u64 {
+        self.add_mod(a, self.prime - b % self.prime)
+    }
+
+    fn mul_mod(&self, a: u64, b: u64) -> u64 {
+        mul_mod_u64(a, b, self.prime)
+    }
+
+    /// by Fermat, p is prime
+    fn inverse(&self, a: u64) -> u64 {
+        pow_mod_u64(a, self.prime - 2, self.prime)
+    }
+
+    /// 4a^3 + 27b^2 mod p, zero iff the cubic has a repeated root
+    pub fn discriminant(&self) -> u64 {
+        let a3 = pow_mod_u64(self.a, 3, self.prime);
+        let b2 = self.mul_mod(self.b, self.b);
+        self.add_mod(self.mul_mod(4, a3), self.mul_mod(27, b2))
+    }
+
+    /// x^3 + ax + b
+    pub fn rhs(&self, x: u64) -> u64 {
+        let x3 = pow_mod_u64(x, 3, self.prime);
+        self.add_mod(self.add_mod(x3, self.mul_mod(self.a, x)), self.b)
+    }
+
+    pub fn contains(&self, point: &Point) -> bool {
+        match *point {
+            Point::Infinity => true,
+            Point::Affine(x, y) => {
+                x < self.prime && y < self.prime && self.mul_mod(y, y) == self.rhs(x)
+            }
+        }
+    }
+
+    /// every point, O first, one square root per x gives the two points (x, y) and (x, -y)
+    pub fn points(&self) -> Vec<Point> {
+        let mut points = vec![Point::Infinity];
+        for x in 0..self.prime {
+            if let Some(y) = sqrt_mod_prime_u64(self.rhs(x), self.prime) {
+                points.push(Point::Affine(x, y));
+                if y != 0 {
+                    points.push(Point::Affine(x, self.prime - y));
+                }
+            }
+        }
+        points.sort();
+        points
+    }
+
+    pub fn neg(&self, point: &Point) -> Point {
+        match *point {
+            Point::Infinity => Point::Infinity,
+            Point::Affine(x, y) => Point::Affine(x, self.sub_mod(0, y)),
+        }
+    }
+
+    pub fn add(&self, p: &Point, q: &Point) -> Point {
+        self.chord_and_tangent(p, q, None)
+    }
+
+    pub fn add_with_steps(&self, p: &Point, q: &Point) -> (Point, Vec<String>) {
+        let mut steps = Vec::new();
+        let sum = self.chord_and_tangent(p, q, Some(&mut steps));
+        (sum, steps)
+    }
+
+    /// P + Q by the line through both points, the tangent when P = Q
+    fn chord_and_tangent(
+        &self,
+        p: &Point,
+        q: &Point,
+        mut steps: Option<&mut Vec<String>>,
+    ) -> Point {
+        let mut log = |step: String| {
+            if let Some(steps) = steps.as_mut() {
+                steps.push(step)
+            }
+        };
+        let (x1, y1, x2, y2) = match (*p, *q) {
+            (Point::Infinity, _) => {
+                log(format!("O + Q = Q = {}", q));
+                return *q;
+            }
+            (_, Point::Infinity) => {
+                log(format!("P + O = P = {}", p));
+                return *p;
+            }
+            (Point::Affine(x1, y1), Point::Affine(x2, y2)) => (x1, y1, x2, y2),
+        };
+        if x1 == x2 && self.add_mod(y1, y2) == 0 {
+            log(format!(
+                "{} and {} are inverse, the line is vertical: P + Q = O",
+                p, q
+            ));
+            return Point::Infinity;
+        }
+        let (kind, numerator, denominator) = if x1 == x2 {
+            (
+                "doubling: lambda = (3x^2 + a) / 2y",
+                self.add_mod(self.mul_mod(3, self.mul_mod(x1, x1)), self.a),
+                self.mul_mod(2, y1),
+            )
+        } else {
+            (
+                "chord: lambda = (y2 - y1) / (x2 - x1)",
+                self.sub_mod(y2, y1),
+                self.sub_mod(x2, x1),
+            )
+        };
+        let inverse = self.inverse(denominator);
+        let lambda = self.mul_mod(numerator, inverse);
+        let x3 = self.sub_mod(self.sub_mod(self.mul_mod(lambda, lambda), x1), x2);
+        let y3 = self.sub_mod(self.mul_mod(lambda, self.sub_mod(x1, x3)), y1);
+        log(format!(
+            "{} = {} / {} = {} * {} = {}",
+            kind, numerator, denominator, numerator, inverse, lambda
+        ));
+        log(format!("x3 = lambda^2 - x1 - x2 = {}", x3));
+        log(format!("y3 = lambda (x1 - x3) - y1 = {}", y3));
+        Point::Affine(x3, y3)
+    }
+
+    pub fn mul(&self, k: &BigInt, point: &Point) -> Point {
+        self.double_and_add(k, point, None)
+    }
+
+    pub fn mul_with_steps(&self, k: &BigInt, point: &Point) -> (Point, Vec<String>) {
+        let mut steps = Vec::new();
+        let product = self.double_and_add(k, point, Some(&mut steps));
+        (product, steps)
+    }
+
+    /// over the bits of k from the top, a negative k multiplies -P
+    fn double_and_add(
+        &self,
+        k: &BigInt,
+        point: &Point,
+        mut steps: Option<&mut Vec<String>>,
+    ) -> Point {
+        let (k, point) = if k.is_negative() {
+            (-k, self.neg(point))
+        } else {
+            (k.clone(), *point)
+        };
+        let mut result = Point::Infinity;
+        let mut multiple = BigInt::zero();
+        for i in (0..k.bits()).rev() {
+            result = self.add(&result, &result);
+            multiple *= 2;
+            let step = if k.bit(i) {
+                result = self.add(&result, &point);
+                multiple += 1;
+                "bit 1: double and add"
+            } else {
+                "bit 0: double"
+            };
+            if let Some(steps) = steps.as_mut() {
+                steps.push(format!("{}, {}P = {}", step, multiple, result));
+            }
+        }
+        result
+    }
+}
+
+/// the group E(F_p), only for p up to ENUMERATION_LIMIT
+#[derive(Debug, Clone)]
+pub struct CurveAnalysis {
+    pub curve: EllipticCurve,
+    pub points: Vec<Point>,
+    /// the order of every point, in the order of points
+    pub orders: Vec<BigInt>,
+    pub order_factors: Vec<BigInt>,
+    /// E(F_p) = Z_n1 x Z_n2 with n1 | n2, n2 is the largest point order
+    pub structure: (BigInt, BigInt),
+}
+
+impl CurveAnalysis {
+    pub fn new(curve: EllipticCurve) -> Result<Self, String> {
+        if curve.prime > ENUMERATION_LIMIT {
+            return Err(format!(
+                "points are only enumerated for p up to {}",
+                ENUMERATION_LIMIT
+            ));
+        }
+        let points = curve.points();
+        let size = BigInt::from(points.len());
+        let order_factors = wheel_factorization(&size);
+        let orders: Vec<BigInt> = points
+            .iter()
+            .map(|point| point_order(&curve, point, &size, &order_factors))
+            .collect();
+        let exponent = orders
+            .iter()
+            .max()
+            .cloned()
+            .unwrap_or_else(|| BigInt::from(1));
+        Ok(Self {
+            curve,
+            structure: (&size / &exponent, exponent),
+            points,
+            orders,
+            order_factors,
+        })
+    }
+
+    pub fn size(&self) -> BigInt {
+        BigInt::from(self.points.len())
+    }
+
+    pub fn is_cyclic(&self) -> bool {
+        self.structure.1 == self.size()
+    }
+
+    pub fn order(&self, point: &Point) -> Option<BigInt> {
+        self.points
+            .binary_search(point)
+            .ok()
+            .map(|i| self.orders[i].clone())
+    }
+}
+
+/// the order divides the group size, so primes are divided out of it while the multiple stays O
+pub fn point_order(
+    curve: &EllipticCurve,
+    point: &Point,
+    group_size: &BigInt,
+    group_size_factors: &[BigInt],
+) -> BigInt {
+    let mut order = group_size.clone();
+    for (prime, _) in prime_powers(group_size_factors) {
+        while (&order % &prime).is_zero() && curve.mul(&(&order / &prime), point) == Point::Infinity
+        {
+            order /= &prime;
+        }
+    }
+    order
+}
+
+/// "O", "x, y" or "(x, y)"
+pub fn parse_point(text: &str) -> Option<Point> {
+    let text = text.trim();
+    if text.eq_ignore_ascii_case("o") || text.eq_ignore_ascii_case("inf") {
+        return Some(Point::Infinity);
+    }
+    let text = text.trim_start_matches('(').trim_end_matches(')');
+    let mut coordinates = text.split(',').map(|c| c.trim().parse::<u64>().ok());
+    match (coordinates.next(), coordinates.next(), coordinates.next()) {
+        (Some(Some(x)), Some(Some(y)), None) => Some(Point::Affine(x, y)),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum IcedCurveMessage {
+    EditA(String),
+    EditB(String),
+    EditPrime(String),
+    EditP(String),
+    EditQ(String),
+    EditK(String),
+    Analyse,
+    Add,
+    Double,
+    Multiply,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IcedEllipticCurve {
+    a_text: String,
+    a_input: text_input::State,
+    b_text: String,
+    b_input: text_input::State,
+    prime_text: String,
+    prime_input: text_input::State,
+    analyse_button: button::State,
+    p_text: String,
+    p_input: text_input::State,
+    q_text: String,
+    q_input: text_input::State,
+    k_text: String,
+    k_input: text_input::State,
+    add_button: button::State,
+    double_button: button::State,
+    multiply_button: button::State,
+
+    curve: Option<EllipticCurve>,
+    analysis: Option<CurveAnalysis>,
+    /// the operands and the result of the last operation, highlighted in the plot
+    highlight: Vec<Point>,
+    result: Option<Point>,
+    steps: Vec<String>,
+    error_message: Option<String>,
+}
+
+impl IcedEllipticCurve {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, message: IcedCurveMessage) {
+        self.error_message = None;
+        let result = match message {
+            IcedCurveMessage::EditA(content) => {
+                self.a_text = content;
+                Ok(())
+            }
+            IcedCurveMessage::EditB(content) => {
+                self.b_text = content;
+                Ok(())
+            }
+            IcedCurveMessage::EditPrime(content) => {
+                self.prime_text = content;
+                Ok(())
+            }
+            IcedCurveMessage::EditP(content) => {
+                self.p_text = content;
+                Ok(())
+            }
+            IcedCurveMessage::EditQ(content) => {
+                self.q_text = content;
+                Ok(())
+            }
+            IcedCurveMessage::EditK(content) => {
+                self.k_text = content;
+                Ok(())
+            }
+            IcedCurveMessage::Analyse => self.analyse(),
+            message => self.calculate(message),
+        };
+        if let Err(message) = result {
+            self.error_message = Some(message);
+        }
+    }
+
+    fn analyse(&mut self) -> Result<(), String> {
+        self.curve = None;
+        self.analysis = None;
+        self.highlight.clear();
+        self.result = None;
+        self.steps.clear();
+        let (a, b) = match (parse_big(&self.a_text), parse_big(&self.b_text)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return Err(String::from("a and b must be numbers")),
+        };
+        let prime = self
+            .prime_text
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| String::from("p must be a positive number"))?;
+        let curve = EllipticCurve::new(&a, &b, prime)?;
+        self.curve = Some(curve);
+        // larger curves still get point arithmetic, only the group is not enumerated
+        self.analysis = CurveAnalysis::new(curve).ok();
+        Ok(())
+    }
+
+    fn calculate(&mut self, message: IcedCurveMessage) -> Result<(), String> {
+        let curve = self.curve.ok_or("analyse a curve first")?;
+        let point = |text: &str, name: &str| {
+            parse_point(text)
+                .filter(|point| curve.contains(point))
+                .ok_or_else(|| format!("{} must be O or a point (x, y) on the curve", name))
+        };
+        let p = point(&self.p_text, "P")?;
+        let (result, steps, highlight) = match message {
+            IcedCurveMessage::Add => {
+                let q = point(&self.q_text, "Q")?;
+                let (result, steps) = curve.add_with_steps(&p, &q);
+                (result, steps, vec![p, q])
+            }
+            IcedCurveMessage::Double => {
+                let (result, steps) = curve.add_with_steps(&p, &p);
+                (result, steps, vec![p])
+            }
+            IcedCurveMessage::Multiply => {
+                let k = parse_big(&self.k_text).ok_or("k must be a number")?;
+                let (result, steps) = curve.mul_with_steps(&k, &p);
+                (result, steps, vec![p])
+            }
+            _ => return Ok(()),
+        };
+        self.result = Some(result);
+        self.steps = steps;
+        self.highlight = highlight;
+        Ok(())
+    }
+
+    pub fn view(&mut self) -> Element<'_, IcedCurveMessage> {
+        let curve_input = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new("y^2 = x^3 + "))
+            .push(
+                TextInput::new(
+                    &mut self.a_input,
+                    "a",
+                    &self.a_text,
+                    IcedCurveMessage::EditA,
+                )
+                .width(Length::Units(80)),
+            )
+            .push(Text::new("x + "))
+            .push(
+                TextInput::new(
+                    &mut self.b_input,
+                    "b",
+                    &self.b_text,
+                    IcedCurveMessage::EditB,
+                )
+                .width(Length::Units(80)),
+            )
+            .push(Text::new(" over F_"))
+            .push(
+                TextInput::new(
+                    &mut self.prime_input,
+                    "p",
+                    &self.prime_text,
+                    IcedCurveMessage::EditPrime,
+                )
+                .width(Length::Units(120)),
+            )
+            .push(
+                Button::new(&mut self.analyse_button, Text::new("Analyse"))
+                    .on_press(IcedCurveMessage::Analyse),
+            );
+        let operation_input = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new("P: "))
+            .push(TextInput::new(
+                &mut self.p_input,
+                "x, y or O",
+                &self.p_text,
+                IcedCurveMessage::EditP,
+            ))
+            .push(Text::new("Q: "))
+            .push(TextInput::new(
+                &mut self.q_input,
+                "x, y or O",
+                &self.q_text,
+                IcedCurveMessage::EditQ,
+            ))
+            .push(Text::new("k: "))
+            .push(TextInput::new(
+                &mut self.k_input,
+                "k",
+                &self.k_text,
+                IcedCurveMessage::EditK,
+            ))
+            .push(
+                Button::new(&mut self.add_button, Text::new("P + Q"))
+                    .on_press(IcedCurveMessage::Add),
+            )
+            .push(
+                Button::new(&mut self.double_button, Text::new("2P"))
+                    .on_press(IcedCurveMessage::Double),
+            )
+            .push(
+                Button::new(&mut self.multiply_button, Text::new("kP"))
+                    .on_press(IcedCurveMessage::Multiply),
+            );
+
+        let mut content = Column::new()
+            .spacing(10)
+            .push(curve_input)
+            .push(operation_input);
+
+        if let Some(curve) = &self.curve {
+            content = content.push(Text::new(format!(
+                "y^2 = x^3 + {}x + {} over F_{}, 4a^3 + 27b^2 = {}, non-singular",
+                curve.a,
+                curve.b,
+                curve.prime,
+                curve.discriminant()
+            )));
+        }
+        if let Some(result) = &self.result {
+            let order = match self
+                .analysis
+                .as_ref()
+                .and_then(|analysis| analysis.order(result))
+            {
+                Some(order) => format!(" of order {}", order),
+                None => String::new(),
+            };
+            content = self
+                .steps
+                .iter()
+                .fold(content, |col, step| col.push(Text::new(step)))
+                .push(Text::new(format!("result: {}{}", result, order)));
+        }
+        match &self.analysis {
+            Some(analysis) => {
+                let mut highlight = self.highlight.clone();
+                highlight.extend(self.result);
+                content = content
+                    .push(analysis_view(analysis))
+                    .push(plot_view(analysis, &highlight));
+            }
+            None if self.curve.is_some() => {
+                content = content.push(Text::new(format!(
+                    "the group is only enumerated for p up to {}",
+                    ENUMERATION_LIMIT
+                )))
+            }
+            None => {}
+        }
+
+        let maybe_error = match &self.error_message {
+            None => Text::new(""),
+            Some(message) => Text::new(message).color([0.921, 0.039, 0.039]),
+        };
+        content.push(maybe_error).into()
+    }
+}
+
+fn analysis_view<'a>(analysis: &CurveAnalysis) -> Column<'a, IcedCurveMessage> {
+    let p = analysis.curve.prime;
+    let size = analysis.size();
+    let trace = BigInt::from(p + 1) - &size;
+    let (n1, n2) = &analysis.structure;
+    let structure = if analysis.is_cyclic() {
+        format!("E(F_{}) = Z_{}, cyclic", p, n2)
+    } else {
+        format!("E(F_{}) = Z_{} x Z_{}", p, n1, n2)
+    };
+    let mut column = Column::new()
+        .spacing(5)
+        .push(Text::new(format!(
+            "#E(F_{}) = {}, trace t = p + 1 - #E = {}, Hasse: |t| <= 2 sqrt(p) = {:.2}",
+            p,
+            size,
+            trace,
+            2.0 * (p as f64).sqrt()
+        )))
+        .push(Text::new(structure));
+
+    let mut counts: Vec<(BigInt, usize)> = Vec::new();
+    for order in &analysis.orders {
+        match counts.iter_mut().find(|(o, _)| o == order) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((order.clone(), 1)),
+        }
+    }
+    counts.sort();
+    let counts: Vec<String> = counts
+        .iter()
+        .map(|(order, count)| format!("{} of order {}", count, order))
+        .collect();
+    column = column.push(Text::new(format!("Point orders: {}", counts.join(", "))));
+
+    if analysis.points.len() <= POINT_LIST_LIMIT {
+        let points: Vec<String> = analysis
+            .points
+            .iter()
+            .zip(&analysis.orders)
+            .map(|(point, order)| format!("{}: {}", point, order))
+            .collect();
+        for chunk in points.chunks(10) {
+            column = column.push(Text::new(chunk.join(",  ")));
+        }
+    }
+    column
+}
+
+/// the affine points as a scatter plot, y grows upwards
+fn plot_view<'a>(analysis: &'a CurveAnalysis, highlight: &[Point]) -> Column<'a, IcedCurveMessage> {
+    let p = analysis.curve.prime;
+    Column::new()
+        .spacing(5)
+        .push(Text::new(format!(
+            "Points in F_{} x F_{}, x to the right and y upwards, operands and result in red",
+            p, p
+        )))
+        .push(
+            Canvas::new(CurvePlot {
+                analysis,
+                highlight: highlight.to_vec(),
+            })
+            .width(Length::Fill)
+            .height(Length::Units(PLOT_SIZE)),
+        )
+}
+
+/// F_p x F_p as a square as large as the canvas allows, every point is a dot in its cell
+struct CurvePlot<'a> {
+    analysis: &'a CurveAnalysis,
+    highlight: Vec<Point>,
+}
+
+impl CurvePlot<'_> {
+    /// top left corner and side length of the square
+    fn square(&self, size: Size) -> (iced::Point, f32) {
+        let side = (size.width.min(size.height) - 2.0 * PLOT_MARGIN).max(1.0);
+        (
+            iced::Point::new((size.width - side) / 2.0, (size.height - side) / 2.0),
+            side,
+        )
+    }
+
+    /// the centre of the cell of (x, y)
+    fn position(&self, size: Size, x: u64, y: u64) -> iced::Point {
+        let (corner, side) = self.square(size);
+        let cell = side / self.analysis.curve.prime as f32;
+        let row = self.analysis.curve.prime - 1 - y;
+        iced::Point::new(
+            corner.x + (x as f32 + 0.5) * cell,
+            corner.y + (row as f32 + 0.5) * cell,
+        )
+    }
+}
+
+impl<M> Program<M> for CurvePlot<'_> {
+    fn draw(&self, bounds: Rectangle, _cursor: Cursor) -> Vec<Geometry> {
+        let size = bounds.size();
+        let mut frame = Frame::new(size);
+        let (corner, side) = self.square(size);
+        frame.fill_rectangle(
+            corner,
+            Size::new(side, side),
+            Color::from_rgb(0.95, 0.95, 0.95),
+        );
+        let cell = side / self.analysis.curve.prime as f32;
+        let dot = |frame: &mut Frame, x: u64, y: u64, width: f32, color: Color| {
+            let center = self.position(size, x, y);
+            frame.fill_rectangle(
+                iced::Point::new(center.x - width / 2.0, center.y - width / 2.0),
+                Size::new(width, width),
+                color,
+            );
+        };
+        let width = (cell * 0.8).clamp(1.0, DOT_SIZE);
+        for point in &self.analysis.points {
+            if let Point::Affine(x, y) = *point {
+                dot(&mut frame, x, y, width, Color::from_rgb(0.2, 0.4, 0.8));
+            }
+        }
+        // drawn last and at least DOT_SIZE wide so that they stay visible between the others
+        for point in &self.highlight {
+            if let Point::Affine(x, y) = *point {
+                dot(
+                    &mut frame,
+                    x,
+                    y,
+                    width.max(DOT_SIZE),
+                    Color::from_rgb(0.8, 0.2, 0.2),
+                );
+            }
+        }
+        vec![frame.into_geometry()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// y^2 = x^3 + 2x + 3 over F_97
+    fn curve() -> EllipticCurve {
+        EllipticCurve::new(&BigInt::from(2), &BigInt::from(3), 97).unwrap()
+    }
+
+    #[test]
+    fn rejects_singular_curves_and_small_fields() {
+        let zero = BigInt::zero();
+        assert!(EllipticCurve::new(&zero, &zero, 97).is_err());
+        assert!(EllipticCurve::new(&BigInt::from(2), &BigInt::from(3), 3).is_err());
+        assert!(EllipticCurve::new(&BigInt::from(2), &BigInt::from(3), 91).is_err());
+        let reduced = EllipticCurve::new(&BigInt::from(-95), &BigInt::from(100), 97).unwrap();
+        assert_eq!(reduced, curve());
+    }
+
+    #[test]
+    fn group_law() {
+        let curve = curve();
+        let p = Point::Affine(3, 6);
+        let q = Point::Affine(0, 10);
+        assert!(curve.contains(&p) && curve.contains(&q));
+        assert!(!curve.contains(&Point::Affine(3, 7)));
+        assert_eq!(curve.add(&p, &q), Point::Affine(85, 71));
+        assert_eq!(curve.add(&p, &q), curve.add(&q, &p));
+        assert_eq!(curve.add(&p, &p), Point::Affine(80, 10));
+        assert_eq!(curve.add(&p, &Point::Infinity), p);
+        assert_eq!(curve.add(&Point::Infinity, &q), q);
+        assert_eq!(curve.neg(&p), Point::Affine(3, 91));
+        assert_eq!(curve.add(&p, &curve.neg(&p)), Point::Infinity);
+        // the tangent at a point with y = 0 is vertical
+        let two_torsion = Point::Affine(30, 0);
+        assert!(curve.contains(&two_torsion));
+        assert_eq!(curve.neg(&two_torsion), two_torsion);
+        assert_eq!(curve.add(&two_torsion, &two_torsion), Point::Infinity);
+        let (sum, steps) = curve.add_with_steps(&p, &q);
+        assert_eq!(sum, Point::Affine(85, 71));
+        assert_eq!(steps.len(), 3);
+    }
+
+    #[test]
+    fn scalar_multiplication() {
+        let curve = curve();
+        let p = Point::Affine(3, 6);
+        let mul = |k: i64| curve.mul(&BigInt::from(k), &p);
+        assert_eq!(mul(0), Point::Infinity);
+        assert_eq!(mul(1), p);
+        assert_eq!(mul(2), Point::Affine(80, 10));
+        assert_eq!(mul(3), Point::Affine(80, 87));
+        assert_eq!(mul(5), Point::Infinity);
+        assert_eq!(mul(-1), curve.neg(&p));
+        assert_eq!(mul(-2), mul(3));
+        assert_eq!(mul(-7), mul(3));
+        assert_eq!(
+            curve.mul(&BigInt::from(-4), &Point::Infinity),
+            Point::Infinity
+        );
+        let (product, steps) = curve.mul_with_steps(&BigInt::from(3), &p);
+        assert_eq!(product, mul(3));
+        assert_eq!(steps.len(), 2);
+    }
+
+    #[test]
+    fn point_orders() {
+        let curve = curve();
+        let size = BigInt::from(100);
+        let factors = wheel_factorization(&size);
+        let order = |point: Point| point_order(&curve, &point, &size, &factors);
+        assert_eq!(order(Point::Infinity), BigInt::from(1));
+        assert_eq!(order(Point::Affine(30, 0)), BigInt::from(2));
+        assert_eq!(order(Point::Affine(3, 6)), BigInt::from(5));
+        assert_eq!(order(Point::Affine(80, 10)), BigInt::from(5));
+    }
+
+    #[test]
+    fn structure_of_the_group() {
+        let analysis = CurveAnalysis::new(curve()).unwrap();
+        assert_eq!(analysis.size(), BigInt::from(100));
+        assert_eq!(analysis.structure, (BigInt::from(2), BigInt::from(50)));
+        assert!(!analysis.is_cyclic());
+        assert_eq!(analysis.points[0], Point::Infinity);
+        let count = |order: i64| {
+            analysis
+                .orders
+                .iter()
+                .filter(|o| **o == BigInt::from(order))
+                .count()
+        };
+        assert_eq!(
+            [1, 2, 5, 10, 25, 50]
+                .iter()
+                .map(|o| count(*o))
+                .collect::<Vec<_>>(),
+            vec![1, 3, 4, 12, 20, 60]
+        );
+        assert_eq!(analysis.order(&Point::Affine(3, 6)), Some(BigInt::from(5)));
+        assert_eq!(analysis.order(&Point::Affine(3, 7)), None);
+    }
+
+    #[test]
+    fn plot_fits_the_canvas() {
+        let analysis = CurveAnalysis::new(curve()).unwrap();
+        let plot = CurvePlot {
+            analysis: &analysis,
+            highlight: Vec::new(),
+        };
+        for size in &[Size::new(900.0, 640.0), Size::new(300.0, 640.0)] {
+            let bounds = Rectangle::new(iced::Point::ORIGIN, *size);
+            let (corner, side) = plot.square(*size);
+            assert!(side <= size.width.min(size.height));
+            let low = plot.position(*size, 0, 0);
+            let high = plot.position(*size, 96, 96);
+            assert!(bounds.contains(low) && bounds.contains(high));
+            // x to the right, y upwards
+            assert!(low.x < high.x && low.y > high.y);
+            assert!(low.x > corner.x && high.y > corner.y);
+        }
+    }
+
+    #[test]
+    fn parses_points() {
+        assert_eq!(parse_point(" O "), Some(Point::Infinity));
+        assert_eq!(parse_point("inf"), Some(Point::Infinity));
+        assert_eq!(parse_point("(3, 6)"), Some(Point::Affine(3, 6)));
+        assert_eq!(parse_point("3,6"), Some(Point::Affine(3, 6)));
+        assert_eq!(parse_point("3, 6, 1"), None);
+        assert_eq!(parse_point("3, -6"), None);
+    }
+}
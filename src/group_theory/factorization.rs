@@ -0,0 +1,550 @@
+use crate::group_theory::number_theory::{
+    is_prime, perfect_power, pow_mod_u64, primes_up_to, sqrt_mod_prime_u64, TRIAL_DIVISION_LIMIT,
+};
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use std::time::{Duration, Instant};
+
+/// every method starts by dividing out primes below this
+pub const SMALL_PRIME_BOUND: u64 = 1_000;
+
+pub const POLLARD_RHO_MAX_ITERATIONS: u64 = 5_000_000;
+pub const POLLARD_RHO_MAX_CONSTANTS: u64 = 5;
+pub const POLLARD_P_MINUS_ONE_BOUND: u64 = 1_000_000;
+/// the sieve works on u128, which keeps (sqrt(n) + x)^2 in range up to this size
+pub const QUADRATIC_SIEVE_MAX_BITS: u64 = 100;
+const QUADRATIC_SIEVE_BLOCK: usize = 1 << 16;
+const QUADRATIC_SIEVE_MAX_BLOCKS: usize = 4_000;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum FactorizationMethod {
+    #[default]
+    Wheel,
+    PollardRho,
+    PollardPMinusOne,
+    QuadraticSieve,
+}
+
+impl std::fmt::Display for FactorizationMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                FactorizationMethod::Wheel => "Wheel factorization",
+                FactorizationMethod::PollardRho => "Pollard's rho",
+                FactorizationMethod::PollardPMinusOne => "Pollard's p - 1",
+                FactorizationMethod::QuadraticSieve => "Quadratic sieve",
+            }
+        )
+    }
+}
+
+impl FactorizationMethod {
+    pub const ALL: [FactorizationMethod; 4] = [
+        FactorizationMethod::Wheel,
+        FactorizationMethod::PollardRho,
+        FactorizationMethod::PollardPMinusOne,
+        FactorizationMethod::QuadraticSieve,
+    ];
+}
+
+#[derive(Debug, Clone)]
+pub struct FactorizationStep {
+    pub description: String,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct FactorizationReport {
+    pub method: FactorizationMethod,
+    /// sorted, a factor that could not be split is kept as is and may be composite
+    pub factors: Vec<BigInt>,
+    pub steps: Vec<FactorizationStep>,
+    pub elapsed: Duration,
+}
+
+/// factors num completely with the given method, splitting composites until only primes remain
+pub fn factorize(num: &BigInt, method: FactorizationMethod) -> FactorizationReport {
+    let start = Instant::now();
+    let mut steps = Vec::new();
+    let mut factors = Vec::new();
+
+    let (small_factors, cofactor) = trial_division(&num.abs(), SMALL_PRIME_BOUND);
+    steps.push(FactorizationStep {
+        description: format!(
+            "trial division up to {}: {} small factors, cofactor {}",
+            SMALL_PRIME_BOUND,
+            small_factors.len(),
+            cofactor
+        ),
+        elapsed: start.elapsed(),
+    });
+    factors.extend(small_factors);
+
+    let mut pending = Vec::new();
+    if !cofactor.is_one() || factors.is_empty() {
+        pending.push(cofactor);
+    }
+    while let Some(composite) = pending.pop() {
+        let step_start = Instant::now();
+        if composite.is_one() || is_prime(&composite) {
+            factors.push(composite);
+            continue;
+        }
+        if let Some((root, k)) = perfect_power(&composite) {
+            steps.push(FactorizationStep {
+                description: format!("{} = {}^{}", composite, root, k),
+                elapsed: step_start.elapsed(),
+            });
+            pending.extend(std::iter::repeat_n(root, k as usize));
+            continue;
+        }
+        match find_factor(&composite, method) {
+            Ok((divisor, detail)) => {
+                steps.push(FactorizationStep {
+                    description: format!(
+                        "{} = {} * {}, {}",
+                        composite,
+                        divisor,
+                        &composite / &divisor,
+                        detail
+                    ),
+                    elapsed: step_start.elapsed(),
+                });
+                pending.push(&composite / &divisor);
+                pending.push(divisor);
+            }
+            Err(reason) => {
+                steps.push(FactorizationStep {
+                    description: format!("{} could not be split: {}", composite, reason),
+                    elapsed: step_start.elapsed(),
+                });
+                factors.push(composite);
+            }
+        }
+    }
+    factors.sort();
+
+    FactorizationReport {
+        method,
+        factors,
+        steps,
+        elapsed: start.elapsed(),
+    }
+}
+
+/// returns a nontrivial divisor of the composite num and a short description of how it was found
+pub fn find_factor(num: &BigInt, method: FactorizationMethod) -> Result<(BigInt, String), String> {
+    match method {
+        FactorizationMethod::Wheel => {
+            let (factors, _) = trial_division(num, TRIAL_DIVISION_LIMIT);
+            factors
+                .into_iter()
+                .next()
+                .map(|factor| (factor, String::from("trial division")))
+                .ok_or(format!("no divisor below {}", TRIAL_DIVISION_LIMIT))
+        }
+        FactorizationMethod::PollardRho => {
+            // a closed cycle without factor is worth another constant, the budget is shared
+            let mut budget = POLLARD_RHO_MAX_ITERATIONS;
+            for c in 1..=POLLARD_RHO_MAX_CONSTANTS {
+                match pollard_rho(num, c, budget) {
+                    Ok((divisor, iterations)) => {
+                        let detail = format!("x^2 + {}, {} iterations", c, iterations);
+                        return Ok((divisor, detail));
+                    }
+                    Err(iterations) => budget = budget.saturating_sub(iterations),
+                }
+                if budget == 0 {
+                    break;
+                }
+            }
+            Err(format!(
+                "no factor within {} iterations",
+                POLLARD_RHO_MAX_ITERATIONS
+            ))
+        }
+        FactorizationMethod::PollardPMinusOne => {
+            pollard_p_minus_one(num, POLLARD_P_MINUS_ONE_BOUND).map(|(divisor, bound)| {
+                let detail = format!("p - 1 is {}-smooth", bound);
+                (divisor, detail)
+            })
+        }
+        FactorizationMethod::QuadraticSieve => quadratic_sieve(num),
+    }
+}
+
+/// divides out every prime up to bound, returns the primes found and the remaining cofactor
+pub fn trial_division(num: &BigInt, bound: u64) -> (Vec<BigInt>, BigInt) {
+    let mut num = num.clone();
+    let mut factors = Vec::new();
+    let wheel: [u64; 11] = [1, 2, 2, 4, 2, 4, 2, 4, 6, 2, 6];
+    let (mut f, mut w) = (2u64, 0);
+    let mut root = num.sqrt();
+    while f <= bound && BigInt::from(f) <= root {
+        if (&num % f).is_zero() {
+            factors.push(BigInt::from(f));
+            num /= f;
+            root = num.sqrt();
+        } else {
+            f += wheel[w];
+            w = if w == 10 { 3 } else { w + 1 };
+        }
+    }
+    // the loop stops at sqrt(num), a prime cofactor below the bound still counts as found
+    if num > BigInt::one() && num <= BigInt::from(bound) {
+        factors.push(num);
+        num = BigInt::one();
+    }
+    (factors, num)
+}
+
+/// Brent's variant of pollard's rho with f(x) = x^2 + c,
+/// returns (divisor, iterations) or the iterations spent without success
+pub fn pollard_rho(num: &BigInt, c: u64, max_iterations: u64) -> Result<(BigInt, u64), u64> {
+    let c = BigInt::from(c);
+    let batch = 128u64;
+    let mut iterations = 0u64;
+    let f = |x: &BigInt| (x * x + &c) % num;
+    let mut y = BigInt::from(2);
+    let mut x = y.clone();
+    let mut ys = y.clone();
+    let mut q = BigInt::one();
+    let mut g = BigInt::one();
+    let mut r = 1u64;
+
+    while g.is_one() {
+        if iterations + r > max_iterations {
+            return Err(iterations);
+        }
+        x = y.clone();
+        for _ in 0..r {
+            y = f(&y);
+        }
+        iterations += r;
+        let mut k = 0;
+        while k < r && g.is_one() {
+            ys = y.clone();
+            for _ in 0..batch.min(r - k) {
+                y = f(&y);
+                q = (q * (&x - &y).abs()) % num;
+                iterations += 1;
+            }
+            g = q.gcd(num);
+            k += batch;
+        }
+        r *= 2;
+    }
+    // the batch overshot and multiplied the whole cycle into q, walk it again one step at a time
+    if &g == num {
+        loop {
+            ys = f(&ys);
+            iterations += 1;
+            g = (&x - &ys).abs().gcd(num);
+            if !g.is_one() {
+                break;
+            }
+        }
+    }
+    if &g == num {
+        Err(iterations)
+    } else {
+        Ok((g, iterations))
+    }
+}
+
+/// pollard's p - 1 with stage one bound, returns (divisor, largest prime used)
+pub fn pollard_p_minus_one(num: &BigInt, bound: u64) -> Result<(BigInt, u64), String> {
+    let mut a = BigInt::from(2);
+    let primes = primes_up_to(bound);
+    for (i, prime) in primes.iter().enumerate() {
+        let mut power = *prime;
+        while power <= bound / prime {
+            power *= prime;
+        }
+        a = a.modpow(&BigInt::from(power), num);
+        if i % 100 == 99 || i == primes.len() - 1 {
+            let g = (&a - 1u32).gcd(num);
+            if &g == num {
+                return Err(format!("every prime factor p has a {}-smooth p - 1", prime));
+            }
+            if !g.is_one() {
+                return Ok((g, *prime));
+            }
+        }
+    }
+    Err(format!("no prime factor p has a {}-smooth p - 1", bound))
+}
+
+struct Relation {
+    x: u128,
+    /// exponent of every factor base prime in (sqrt(n) + x)^2 - n
+    exponents: Vec<u32>,
+}
+
+/// single polynomial quadratic sieve for num < 2^QUADRATIC_SIEVE_MAX_BITS
+pub fn quadratic_sieve(num: &BigInt) -> Result<(BigInt, String), String> {
+    if num.bits() > QUADRATIC_SIEVE_MAX_BITS {
+        return Err(format!(
+            "the small quadratic sieve only handles up to {} bits",
+            QUADRATIC_SIEVE_MAX_BITS
+        ));
+    }
+    let n = num.to_u128().unwrap();
+    let ln_n = num.bits() as f64 * std::f64::consts::LN_2;
+    let smoothness_bound = ((0.5 * (ln_n * ln_n.ln()).sqrt()).exp() as u64).clamp(1_000, 100_000);
+
+    // primes p with (n / p) = 1, 2 always divides some (sqrt(n) + x)^2 - n
+    let mut factor_base = Vec::new();
+    for prime in primes_up_to(smoothness_bound) {
+        let residue = (n % prime as u128) as u64;
+        if residue == 0 {
+            return Ok((
+                BigInt::from(prime),
+                String::from("factor base prime divides n"),
+            ));
+        }
+        if prime == 2 || pow_mod_u64(residue, (prime - 1) / 2, prime) == 1 {
+            factor_base.push(prime);
+        }
+    }
+
+    let m = num.sqrt().to_u128().unwrap() + 1;
+    // offsets x with p | (m + x)^2 - n, two per odd prime
+    let offsets: Vec<Vec<u64>> = factor_base
+        .iter()
+        .map(|&prime| {
+            let m_mod = (m % prime as u128) as u64;
+            if prime == 2 {
+                let q0 = ((m * m - n) % 2) as u64;
+                return vec![if q0 == 0 { 0 } else { 1 }];
+            }
+            let root = sqrt_mod_prime_u64((n % prime as u128) as u64, prime).unwrap();
+            let mut roots = vec![
+                (root + prime - m_mod) % prime,
+                (2 * prime - root - m_mod) % prime,
+            ];
+            roots.dedup();
+            roots
+        })
+        .collect();
+    let logs: Vec<f32> = factor_base.iter().map(|&p| (p as f32).log2()).collect();
+    let slack = (*factor_base.last().unwrap() as f32).log2() * 2.0;
+    let wanted = factor_base.len() + 10;
+
+    let mut relations: Vec<Relation> = Vec::new();
+    let mut sieved = 0usize;
+    let mut sieve = vec![0f32; QUADRATIC_SIEVE_BLOCK];
+    for block in 0..QUADRATIC_SIEVE_MAX_BLOCKS {
+        let block_start = (block * QUADRATIC_SIEVE_BLOCK) as u128;
+        sieve.iter_mut().for_each(|value| *value = 0.0);
+        for (i, &prime) in factor_base.iter().enumerate() {
+            for &offset in &offsets[i] {
+                let start = ((offset as u128 + prime as u128 - block_start % prime as u128)
+                    % prime as u128) as usize;
+                let mut j = start;
+                while j < QUADRATIC_SIEVE_BLOCK {
+                    sieve[j] += logs[i];
+                    j += prime as usize;
+                }
+            }
+        }
+        for (j, value) in sieve.iter().enumerate() {
+            let x = block_start + j as u128;
+            let mut q = (m + x) * (m + x) - n;
+            // compared with the size of q itself, a bound for the whole block would
+            // throw away the small values at its start, which are the likeliest to be smooth
+            if *value < (q as f64).log2() as f32 - slack {
+                continue;
+            }
+            let mut exponents = vec![0u32; factor_base.len()];
+            for (i, &prime) in factor_base.iter().enumerate() {
+                while q.is_multiple_of(prime as u128) {
+                    q /= prime as u128;
+                    exponents[i] += 1;
+                }
+            }
+            if q == 1 {
+                relations.push(Relation { x, exponents });
+            }
+        }
+        sieved += QUADRATIC_SIEVE_BLOCK;
+        if relations.len() >= wanted {
+            break;
+        }
+    }
+    if relations.len() <= factor_base.len() {
+        return Err(format!(
+            "only {} of {} relations after sieving {} values",
+            relations.len(),
+            wanted,
+            sieved
+        ));
+    }
+
+    let dependencies = gf2_dependencies(&relations, factor_base.len());
+    for (tried, dependency) in dependencies.iter().enumerate() {
+        let mut x_product = BigInt::one();
+        let mut exponents = vec![0u32; factor_base.len()];
+        for &r in dependency {
+            x_product = (x_product * BigInt::from(m + relations[r].x)) % num;
+            for (total, e) in exponents.iter_mut().zip(&relations[r].exponents) {
+                *total += e;
+            }
+        }
+        let y_product = factor_base
+            .iter()
+            .zip(&exponents)
+            .fold(BigInt::one(), |y, (&prime, &e)| {
+                y * BigInt::from(prime).modpow(&BigInt::from(e / 2), num) % num
+            });
+        let g = (&x_product - &y_product).gcd(num);
+        if !g.is_one() && &g != num {
+            let detail = format!(
+                "{} primes up to {}, {} relations from {} sieved values, dependency {} of {}",
+                factor_base.len(),
+                smoothness_bound,
+                relations.len(),
+                sieved,
+                tried + 1,
+                dependencies.len()
+            );
+            return Ok((g, detail));
+        }
+    }
+    Err(format!(
+        "all {} dependencies gave trivial divisors",
+        dependencies.len()
+    ))
+}
+
+/// gaussian elimination over GF(2) on the exponent parities, every result is a set of
+/// relations whose product is a perfect square
+fn gf2_dependencies(relations: &[Relation], columns: usize) -> Vec<Vec<usize>> {
+    let words = |bits: usize| bits.div_ceil(64);
+    let rows = relations.len();
+    // left part: exponent parities, right part: which relations were combined
+    let mut matrix: Vec<(Vec<u64>, Vec<u64>)> = relations
+        .iter()
+        .enumerate()
+        .map(|(r, relation)| {
+            let mut parity = vec![0u64; words(columns)];
+            for (c, e) in relation.exponents.iter().enumerate() {
+                if e % 2 == 1 {
+                    parity[c / 64] |= 1 << (c % 64);
+                }
+            }
+            let mut history = vec![0u64; words(rows)];
+            history[r / 64] |= 1 << (r % 64);
+            (parity, history)
+        })
+        .collect();
+
+    let mut pivot_row = 0;
+    for c in 0..columns {
+        let bit = |row: &(Vec<u64>, Vec<u64>)| row.0[c / 64] >> (c % 64) & 1 == 1;
+        if let Some(found) = (pivot_row..rows).find(|&r| bit(&matrix[r])) {
+            matrix.swap(pivot_row, found);
+            let pivot = matrix[pivot_row].clone();
+            for (r, row) in matrix.iter_mut().enumerate() {
+                if r != pivot_row && bit(row) {
+                    row.0.iter_mut().zip(&pivot.0).for_each(|(a, b)| *a ^= b);
+                    row.1.iter_mut().zip(&pivot.1).for_each(|(a, b)| *a ^= b);
+                }
+            }
+            pivot_row += 1;
+        }
+    }
+
+    matrix[pivot_row..]
+        .iter()
+        .map(|(_, history)| {
+            (0..rows)
+                .filter(|r| history[r / 64] >> (r % 64) & 1 == 1)
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(num: u64) -> BigInt {
+        BigInt::from(num)
+    }
+
+    #[test]
+    fn every_method_splits_semiprimes() {
+        // both factors lie above SMALL_PRIME_BOUND, so trial division leaves the work to the method,
+        // p - 1 is smooth and q is a safe prime, as Pollard's p - 1 needs
+        for (p, q) in [
+            (1_009, 2_000_303),
+            (65_537, 2_000_387),
+            (999_983, 1_000_000_000_547),
+        ] {
+            for method in FactorizationMethod::ALL.iter() {
+                let report = factorize(&(big(p) * big(q)), *method);
+                assert_eq!(report.factors, vec![big(p), big(q)], "{}", method);
+            }
+        }
+    }
+
+    #[test]
+    fn factorize_collects_repeated_and_small_factors() {
+        let report = factorize(
+            &big(2 * 2 * 3 * 1_009 * 1_009),
+            FactorizationMethod::PollardRho,
+        );
+        assert_eq!(
+            report.factors,
+            vec![big(2), big(2), big(3), big(1_009), big(1_009)]
+        );
+        assert_eq!(
+            factorize(&big(1_000_003), FactorizationMethod::QuadraticSieve).factors,
+            vec![big(1_000_003)]
+        );
+    }
+
+    #[test]
+    fn pollard_p_minus_one_needs_a_smooth_p_minus_one() {
+        // 1_000_003 - 1 = 2 * 3 * 166_667 is not smooth, 65_537 - 1 = 2^16 is
+        let (divisor, _) = pollard_p_minus_one(&(big(65_537) * big(1_000_003)), 1_000).unwrap();
+        assert_eq!(divisor, big(65_537));
+        assert!(pollard_p_minus_one(&(big(1_000_003) * big(2_000_003)), 1_000).is_err());
+    }
+
+    #[test]
+    fn quadratic_sieve_finds_a_proper_divisor() {
+        let n = big(4_294_967_291) * big(4_294_967_279);
+        let (divisor, _) = quadratic_sieve(&n).unwrap();
+        assert!(divisor > BigInt::one() && divisor < n);
+        assert!((&n % &divisor).is_zero());
+    }
+
+    #[test]
+    fn gf2_dependencies_multiply_to_squares() {
+        let relations: Vec<Relation> = [[1, 0, 1], [1, 1, 0], [0, 1, 1], [2, 0, 0]]
+            .iter()
+            .enumerate()
+            .map(|(x, exponents)| Relation {
+                x: x as u128,
+                exponents: exponents.to_vec(),
+            })
+            .collect();
+        let dependencies = gf2_dependencies(&relations, 3);
+        assert!(!dependencies.is_empty());
+        for dependency in dependencies {
+            assert!(!dependency.is_empty());
+            for column in 0..3 {
+                let sum: u32 = dependency
+                    .iter()
+                    .map(|r| relations[*r].exponents[column])
+                    .sum();
+                assert_eq!(sum % 2, 0);
+            }
+        }
+    }
+}
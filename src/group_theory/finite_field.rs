@@ -0,0 +1,703 @@
+use crate::group_theory::cayley::{
+    table_limit_notice, IcedCayleyMessage, IcedCayleyTable, CAYLEY_TABLE_LIMIT,
+};
+use crate::group_theory::factorization::{factorize, FactorizationMethod, FactorizationReport};
+use crate::group_theory::finite_group::FiniteGroup;
+use crate::group_theory::number_theory::{
+    divisors, is_fully_factored, parse_big, prime_powers, ENUMERATION_LIMIT,
+};
+use crate::group_theory::polynomial::{
+    format_poly, is_irreducible, make_monic, parse_poly, poly_add, poly_div_rem, poly_extended_gcd,
+    poly_from_index, poly_mul, poly_pow_mod, poly_sub, trim, Zp,
+};
+use crate::group_theory::primality::is_prime_u64;
+use crate::group_theory::subgroups::{IcedSubgroupLattice, IcedSubgroupMessage};
+use iced::{button, text_input, Align, Button, Column, Command, Element, Row, Text, TextInput};
+use num_bigint::BigInt;
+use num_traits::{Signed, ToPrimitive, Zero};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// fields are only built up to degree n = 64 over Z_p
+pub const FIELD_DEGREE_LIMIT: usize = 64;
+/// the automatic modulus search and the primitive element search give up after this many candidates
+const SEARCH_LIMIT: u64 = 100_000;
+
+/// the first monic irreducible polynomial of the given degree, counting the lower coefficients
+/// up in base p, or in base 16 for larger p so that more than the constant term changes
+pub fn find_irreducible(prime: u64, degree: usize) -> Option<Vec<u64>> {
+    let base = prime.min(16);
+    (0..SEARCH_LIMIT).find_map(|index| {
+        let mut poly = poly_from_index(index, base, degree);
+        poly.resize(degree, 0);
+        poly.push(1);
+        if is_irreducible(&poly, prime) {
+            Some(poly)
+        } else {
+            None
+        }
+    })
+}
+
+/// an element of GF(p^n) as its coefficients mod the field polynomial, lowest first
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct FieldElement {
+    pub coefficients: Vec<u64>,
+}
+
+impl Display for FieldElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", format_poly(&self.coefficients, "x"))
+    }
+}
+
+/// GF(p^n) = Z_p[x]/(f) for a monic irreducible f of degree n
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FiniteField {
+    pub prime: u64,
+    pub modulus: Vec<u64>,
+}
+
+impl FiniteField {
+    /// the modulus is made monic, None if it is not irreducible
+    pub fn new(prime: u64, modulus: &[u64]) -> Option<Self> {
+        let modulus = make_monic(&trim(modulus.to_vec()), &Zp(prime));
+        if is_irreducible(&modulus, prime) {
+            Some(Self { prime, modulus })
+        } else {
+            None
+        }
+    }
+
+    pub fn degree(&self) -> usize {
+        self.modulus.len() - 1
+    }
+
+    /// p^n
+    pub fn size(&self) -> BigInt {
+        BigInt::from(self.prime).pow(self.degree() as u32)
+    }
+
+    /// "GF(2^8) = Z_2[x]/(x^8 + x^4 + x^3 + x + 1)"
+    pub fn name(&self) -> String {
+        format!(
+            "GF({}^{}) = Z_{}[x]/({})",
+            self.prime,
+            self.degree(),
+            self.prime,
+            format_poly(&self.modulus, "x")
+        )
+    }
+
+    pub fn element(&self, poly: &[u64]) -> FieldElement {
+        FieldElement {
+            coefficients: poly_div_rem(poly, &self.modulus, &Zp(self.prime)).1,
+        }
+    }
+
+    pub fn one(&self) -> FieldElement {
+        self.element(&[1])
+    }
+
+    /// the element with index written in base p as coefficients, 0 <= index < p^n
+    pub fn element_from_index(&self, index: u64) -> FieldElement {
+        FieldElement {
+            coefficients: poly_from_index(index, self.prime, self.degree()),
+        }
+    }
+
+    /// every element, 0 first, only meant for fields below ENUMERATION_LIMIT
+    pub fn elements(&self) -> Vec<FieldElement> {
+        let size = self.size().to_u64().unwrap_or(0);
+        (0..size).map(|i| self.element_from_index(i)).collect()
+    }
+
+    pub fn add(&self, a: &FieldElement, b: &FieldElement) -> FieldElement {
+        FieldElement {
+            coefficients: poly_add(&a.coefficients, &b.coefficients, &Zp(self.prime)),
+        }
+    }
+
+    pub fn sub(&self, a: &FieldElement, b: &FieldElement) -> FieldElement {
+        FieldElement {
+            coefficients: poly_sub(&a.coefficients, &b.coefficients, &Zp(self.prime)),
+        }
+    }
+
+    pub fn mul(&self, a: &FieldElement, b: &FieldElement) -> FieldElement {
+        self.element(&poly_mul(&a.coefficients, &b.coefficients, &Zp(self.prime)))
+    }
+
+    /// the extended euclidean algorithm on a and the modulus, None for 0
+    pub fn inverse(&self, a: &FieldElement) -> Option<FieldElement> {
+        let rows = poly_extended_gcd(&self.modulus, &a.coefficients, &Zp(self.prime));
+        let last = rows.last()?;
+        if last.r.len() != 1 {
+            return None;
+        }
+        Some(self.element(&last.t))
+    }
+
+    /// negative exponents go through the inverse, None for 0 to a negative power
+    pub fn pow(&self, a: &FieldElement, exp: &BigInt) -> Option<FieldElement> {
+        let base = if exp.is_negative() {
+            self.inverse(a)?
+        } else {
+            a.clone()
+        };
+        Some(FieldElement {
+            coefficients: poly_pow_mod(
+                &base.coefficients,
+                &exp.abs(),
+                &self.modulus,
+                &Zp(self.prime),
+            ),
+        })
+    }
+
+    /// order of a nonzero element, dividing primes out of p^n - 1
+    pub fn element_order(
+        &self,
+        a: &FieldElement,
+        group_size: &BigInt,
+        group_size_factors: &[BigInt],
+    ) -> Option<BigInt> {
+        if a.coefficients.is_empty() {
+            return None;
+        }
+        let one = self.one();
+        let mut order = group_size.clone();
+        for (prime, _) in prime_powers(group_size_factors) {
+            while (&order % &prime).is_zero() && self.pow(a, &(&order / &prime))? == one {
+                order /= &prime;
+            }
+        }
+        Some(order)
+    }
+}
+
+/// the order analysis of GF(p^n)^*, computed off the ui thread like GroupAnalysis
+#[derive(Debug, Clone)]
+pub struct FieldAnalysis {
+    pub field: FiniteField,
+    pub group_size: BigInt,
+    pub group_size_factorization: Option<FactorizationReport>,
+    pub group_size_prime_factors: Vec<BigInt>,
+    pub possible_orders: Vec<BigInt>,
+    /// (element, order) for every nonzero element, empty above ENUMERATION_LIMIT
+    pub orders: Vec<(FieldElement, BigInt)>,
+    pub producers: Vec<FieldElement>,
+    /// g, a generator of GF(p^n)^*
+    pub primitive: Option<FieldElement>,
+    /// discrete logarithms to the base g, empty above ENUMERATION_LIMIT
+    pub logarithms: HashMap<FieldElement, u64>,
+    pub finite_group: Option<FiniteGroup>,
+
+    pub error_message: Option<String>,
+}
+
+impl FieldAnalysis {
+    pub fn new(field: FiniteField) -> Self {
+        Self {
+            field,
+            group_size: BigInt::zero(),
+            group_size_factorization: None,
+            group_size_prime_factors: vec![],
+            possible_orders: vec![],
+            orders: vec![],
+            producers: vec![],
+            primitive: None,
+            logarithms: HashMap::new(),
+            finite_group: None,
+            error_message: None,
+        }
+    }
+
+    pub async fn analyse(field: FiniteField) -> Self {
+        let mut analysis = Self::new(field);
+        let field = analysis.field.clone();
+        analysis.group_size = field.size() - 1u32;
+        let factorization = factorize(&analysis.group_size, FactorizationMethod::default());
+        analysis.group_size_prime_factors = factorization.factors.clone();
+        analysis.group_size_factorization = Some(factorization);
+        if !is_fully_factored(&analysis.group_size_prime_factors) {
+            analysis.error_message = Some(String::from(
+                "p^n - 1 could not be factored, orders are not available",
+            ));
+            return analysis;
+        }
+        analysis.possible_orders = divisors(&analysis.group_size_prime_factors);
+
+        let group_size = analysis.group_size.clone();
+        let factors = analysis.group_size_prime_factors.clone();
+        let order = |a: &FieldElement| field.element_order(a, &group_size, &factors);
+
+        if group_size > BigInt::from(ENUMERATION_LIMIT) {
+            analysis.primitive = (1..SEARCH_LIMIT)
+                .map(|i| field.element_from_index(i))
+                .find(|a| order(a).as_ref() == Some(&group_size));
+            analysis.error_message = Some(match analysis.primitive {
+                Some(_) => format!(
+                    "the field has more than {} elements, elements and orders are not listed",
+                    ENUMERATION_LIMIT
+                ),
+                None => String::from("no primitive element was found"),
+            });
+            return analysis;
+        }
+
+        let units: Vec<FieldElement> = field.elements().into_iter().skip(1).collect();
+        analysis.orders = units
+            .iter()
+            .filter_map(|a| order(a).map(|order| (a.clone(), order)))
+            .collect();
+        analysis.producers = analysis
+            .orders
+            .iter()
+            .filter(|(_, order)| order == &group_size)
+            .map(|(a, _)| a.clone())
+            .collect();
+        analysis.primitive = analysis.producers.first().cloned();
+        if let Some(g) = &analysis.primitive {
+            let mut power = field.one();
+            for k in 0..units.len() as u64 {
+                analysis.logarithms.insert(power.clone(), k);
+                power = field.mul(&power, g);
+            }
+        }
+        if units.len() <= CAYLEY_TABLE_LIMIT {
+            analysis.finite_group = FiniteGroup::from_elements(
+                &format!("GF({}^{})^*", field.prime, field.degree()),
+                "*",
+                &units,
+                |a, b| field.mul(a, b),
+            );
+        }
+        analysis
+    }
+
+    /// "x^2 + 1 = g^5" if the logarithm is known
+    pub fn describe(&self, a: &FieldElement) -> String {
+        let mut text = a.to_string();
+        if self.field.prime == 2 && self.field.degree() <= 32 {
+            let bits = a
+                .coefficients
+                .iter()
+                .rev()
+                .fold(0u64, |bits, c| (bits << 1) | c);
+            text.push_str(&format!(" = 0x{:02x}", bits));
+        }
+        if let Some(k) = self.logarithms.get(a) {
+            text.push_str(&format!(" = g^{}", k));
+        }
+        text
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone)]
+pub enum IcedFieldMessage {
+    EditPrime(String),
+    EditModulus(String),
+    EditDegree(String),
+    Build,
+    Analysed(FieldAnalysis),
+    EditA(String),
+    EditB(String),
+    EditExponent(String),
+    Add,
+    Multiply,
+    Invert,
+    Power,
+    CayleyMessage(IcedCayleyMessage),
+    SubgroupMessage(IcedSubgroupMessage),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IcedFiniteField {
+    prime_text: String,
+    prime_input: text_input::State,
+    degree_text: String,
+    degree_input: text_input::State,
+    modulus_text: String,
+    modulus_input: text_input::State,
+    build_button: button::State,
+    calculating: bool,
+
+    a_text: String,
+    a_input: text_input::State,
+    b_text: String,
+    b_input: text_input::State,
+    exponent_text: String,
+    exponent_input: text_input::State,
+    add_button: button::State,
+    multiply_button: button::State,
+    invert_button: button::State,
+    power_button: button::State,
+
+    analysis: Option<FieldAnalysis>,
+    output: Vec<String>,
+    cayley: Option<IcedCayleyTable>,
+    lattice: Option<IcedSubgroupLattice>,
+
+    error_message: Option<String>,
+}
+
+impl IcedFiniteField {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, message: IcedFieldMessage) -> Command<IcedFieldMessage> {
+        match message {
+            IcedFieldMessage::EditPrime(content) => self.prime_text = content,
+            IcedFieldMessage::EditDegree(content) => self.degree_text = content,
+            IcedFieldMessage::EditModulus(content) => self.modulus_text = content,
+            IcedFieldMessage::EditA(content) => self.a_text = content,
+            IcedFieldMessage::EditB(content) => self.b_text = content,
+            IcedFieldMessage::EditExponent(content) => self.exponent_text = content,
+            IcedFieldMessage::Build => {
+                if self.calculating {
+                    return Command::none();
+                }
+                self.error_message = None;
+                self.output.clear();
+                match self.build() {
+                    Ok(field) => {
+                        self.modulus_text = format_poly(&field.modulus, "x");
+                        self.degree_text = field.degree().to_string();
+                        self.calculating = true;
+                        return Command::perform(
+                            FieldAnalysis::analyse(field),
+                            IcedFieldMessage::Analysed,
+                        );
+                    }
+                    Err(message) => self.error_message = Some(message),
+                }
+            }
+            IcedFieldMessage::Analysed(analysis) => {
+                self.calculating = false;
+                self.error_message = analysis.error_message.clone();
+                self.cayley = analysis.finite_group.clone().map(IcedCayleyTable::new);
+                self.lattice = analysis.finite_group.clone().map(IcedSubgroupLattice::new);
+                self.analysis = Some(analysis);
+            }
+            IcedFieldMessage::Add => self.calculate(|analysis, a, b, _| {
+                let sum = analysis.field.add(a, b);
+                Ok(format!("({}) + ({}) = {}", a, b, analysis.describe(&sum)))
+            }),
+            IcedFieldMessage::Multiply => self.calculate(|analysis, a, b, _| {
+                let product = analysis.field.mul(a, b);
+                Ok(format!(
+                    "({}) * ({}) = {}",
+                    a,
+                    b,
+                    analysis.describe(&product)
+                ))
+            }),
+            IcedFieldMessage::Invert => self.calculate(|analysis, a, _, _| {
+                let inverse = analysis
+                    .field
+                    .inverse(a)
+                    .ok_or_else(|| String::from("0 has no inverse"))?;
+                Ok(format!("({})^-1 = {}", a, analysis.describe(&inverse)))
+            }),
+            IcedFieldMessage::Power => self.calculate(|analysis, a, _, exp| {
+                let exp = exp.ok_or_else(|| String::from("k must be a number"))?;
+                let power = analysis
+                    .field
+                    .pow(a, &exp)
+                    .ok_or_else(|| String::from("0 has no inverse"))?;
+                Ok(format!("({})^{} = {}", a, exp, analysis.describe(&power)))
+            }),
+            IcedFieldMessage::CayleyMessage(message) => {
+                if let Some(cayley) = &mut self.cayley {
+                    cayley.update(message)
+                }
+            }
+            IcedFieldMessage::SubgroupMessage(message) => {
+                if let Some(lattice) = &mut self.lattice {
+                    lattice.update(message)
+                }
+            }
+        }
+        Command::none()
+    }
+
+    /// the field from p and either the given modulus or the first irreducible one of degree n
+    fn build(&self) -> Result<FiniteField, String> {
+        let prime = match self.prime_text.trim().parse::<u64>() {
+            Ok(prime) if is_prime_u64(prime) => prime,
+            _ => return Err(String::from("p must be a prime below 2^64")),
+        };
+        if !self.modulus_text.trim().is_empty() {
+            let modulus = parse_poly(&self.modulus_text, &Zp(prime))?;
+            if modulus.len() < 2 || modulus.len() > FIELD_DEGREE_LIMIT + 1 {
+                return Err(format!(
+                    "the modulus needs a degree from 1 to {}",
+                    FIELD_DEGREE_LIMIT
+                ));
+            }
+            return FiniteField::new(prime, &modulus).ok_or_else(|| {
+                format!(
+                    "{} is reducible over Z_{}, the quotient is not a field",
+                    format_poly(&modulus, "x"),
+                    prime
+                )
+            });
+        }
+        let degree = match self.degree_text.trim().parse::<usize>() {
+            Ok(degree) if (1..=FIELD_DEGREE_LIMIT).contains(&degree) => degree,
+            _ => {
+                return Err(format!(
+                    "give a modulus or a degree n from 1 to {}",
+                    FIELD_DEGREE_LIMIT
+                ))
+            }
+        };
+        find_irreducible(prime, degree)
+            .and_then(|modulus| FiniteField::new(prime, &modulus))
+            .ok_or_else(|| String::from("no irreducible polynomial was found"))
+    }
+
+    /// polynomials like "x^2 + 1", powers of the primitive element like "g^5"
+    /// or, over GF(2^n), bit patterns like "0x53"
+    fn parse_element(analysis: &FieldAnalysis, text: &str) -> Result<FieldElement, String> {
+        let field = &analysis.field;
+        let text = text.trim();
+        if let Some(exp) = text.strip_prefix("g^") {
+            let g = analysis
+                .primitive
+                .as_ref()
+                .ok_or_else(|| String::from("no primitive element g is known"))?;
+            let exp = parse_big(exp).ok_or_else(|| format!("{} is not an exponent", exp))?;
+            return field
+                .pow(g, &exp)
+                .ok_or_else(|| String::from("g has no inverse"));
+        }
+        if let Some(hex) = text.strip_prefix("0x") {
+            if field.prime != 2 {
+                return Err(String::from("bit patterns are only supported over GF(2^n)"));
+            }
+            let bits =
+                u64::from_str_radix(hex, 16).map_err(|_| format!("{} is not hexadecimal", text))?;
+            let poly: Vec<u64> = (0..64).map(|i| (bits >> i) & 1).collect();
+            return Ok(field.element(&trim(poly)));
+        }
+        parse_poly(text, &Zp(field.prime)).map(|poly| field.element(&poly))
+    }
+
+    fn calculate(
+        &mut self,
+        operation: impl Fn(
+            &FieldAnalysis,
+            &FieldElement,
+            &FieldElement,
+            Option<BigInt>,
+        ) -> Result<String, String>,
+    ) {
+        self.error_message = None;
+        let analysis = match &self.analysis {
+            Some(analysis) => analysis,
+            None => {
+                self.error_message = Some(String::from("build a field first"));
+                return;
+            }
+        };
+        let b_text = if self.b_text.trim().is_empty() {
+            "0"
+        } else {
+            &self.b_text
+        };
+        let result = Self::parse_element(analysis, &self.a_text).and_then(|a| {
+            let b = Self::parse_element(analysis, b_text)?;
+            operation(analysis, &a, &b, parse_big(&self.exponent_text))
+        });
+        match result {
+            Ok(line) => self.output.push(line),
+            Err(message) => self.error_message = Some(message),
+        }
+    }
+
+    pub fn view(&mut self) -> Element<'_, IcedFieldMessage> {
+        let field_input = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new("p: "))
+            .push(TextInput::new(
+                &mut self.prime_input,
+                "2",
+                &self.prime_text,
+                IcedFieldMessage::EditPrime,
+            ))
+            .push(Text::new("n: "))
+            .push(TextInput::new(
+                &mut self.degree_input,
+                "8",
+                &self.degree_text,
+                IcedFieldMessage::EditDegree,
+            ))
+            .push(Text::new("f: "))
+            .push(
+                TextInput::new(
+                    &mut self.modulus_input,
+                    "x^8 + x^4 + x^3 + x + 1, empty to search",
+                    &self.modulus_text,
+                    IcedFieldMessage::EditModulus,
+                )
+                .on_submit(IcedFieldMessage::Build),
+            )
+            .push(
+                Button::new(&mut self.build_button, Text::new("Build"))
+                    .on_press(IcedFieldMessage::Build),
+            );
+
+        let element_input = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new("a: "))
+            .push(TextInput::new(
+                &mut self.a_input,
+                "x^6 + x^4 + x + 1, g^5 or 0x53",
+                &self.a_text,
+                IcedFieldMessage::EditA,
+            ))
+            .push(Text::new("b: "))
+            .push(TextInput::new(
+                &mut self.b_input,
+                "b",
+                &self.b_text,
+                IcedFieldMessage::EditB,
+            ))
+            .push(Text::new("k: "))
+            .push(TextInput::new(
+                &mut self.exponent_input,
+                "k",
+                &self.exponent_text,
+                IcedFieldMessage::EditExponent,
+            ));
+        let operations = Row::new()
+            .spacing(10)
+            .push(
+                Button::new(&mut self.add_button, Text::new("a + b"))
+                    .on_press(IcedFieldMessage::Add),
+            )
+            .push(
+                Button::new(&mut self.multiply_button, Text::new("a * b"))
+                    .on_press(IcedFieldMessage::Multiply),
+            )
+            .push(
+                Button::new(&mut self.invert_button, Text::new("a^-1"))
+                    .on_press(IcedFieldMessage::Invert),
+            )
+            .push(
+                Button::new(&mut self.power_button, Text::new("a^k"))
+                    .on_press(IcedFieldMessage::Power),
+            );
+
+        let output = self
+            .output
+            .iter()
+            .fold(Column::new().spacing(5), |col, line| {
+                col.push(Text::new(line))
+            });
+
+        let content = match &self.analysis {
+            Some(analysis) => Self::analysis_column(analysis),
+            None => Column::new(),
+        };
+
+        let status = if self.calculating {
+            Text::new("calculating...")
+        } else {
+            Text::new("")
+        };
+
+        let maybe_error = match &self.error_message {
+            None => Text::new(""),
+            Some(message) => Text::new(message).color([0.921, 0.039, 0.039]),
+        };
+
+        let cayley: Element<'_, IcedFieldMessage> = match (&mut self.cayley, &self.analysis) {
+            (Some(cayley), _) => cayley.view().map(IcedFieldMessage::CayleyMessage),
+            (None, Some(analysis)) if analysis.group_size > BigInt::from(CAYLEY_TABLE_LIMIT) => {
+                table_limit_notice(&analysis.group_size)
+            }
+            _ => Text::new("").into(),
+        };
+        let lattice: Element<'_, IcedFieldMessage> = match &mut self.lattice {
+            Some(lattice) => lattice.view().map(IcedFieldMessage::SubgroupMessage),
+            None => Text::new("").into(),
+        };
+
+        Column::new()
+            .spacing(10)
+            .push(field_input)
+            .push(status)
+            .push(element_input)
+            .push(operations)
+            .push(output)
+            .push(maybe_error)
+            .push(content)
+            .push(cayley)
+            .push(lattice)
+            .into()
+    }
+
+    fn analysis_column<'a>(analysis: &FieldAnalysis) -> Column<'a, IcedFieldMessage> {
+        let primitive = match &analysis.primitive {
+            Some(g) => format!("Primitive element g = {}", g),
+            None => String::from("Primitive element: unknown"),
+        };
+        let steps = match &analysis.group_size_factorization {
+            Some(report) => report.steps.iter().fold(
+                Column::new().push(Text::new(format!(
+                    "Factorization of p^n - 1 ({}, {:?}):",
+                    report.method, report.elapsed
+                ))),
+                |col, step| {
+                    col.push(Text::new(format!(
+                        "{:?}: {}",
+                        step.elapsed, step.description
+                    )))
+                },
+            ),
+            None => Column::new(),
+        };
+        Column::new()
+            .push(Text::new(analysis.field.name()))
+            .push(Text::new(format!(
+                "Group Size: {} (GF(q)^* is cyclic, isomorphic to Z_{})",
+                analysis.group_size, analysis.group_size
+            )))
+            .push(analysis.group_size_prime_factors.iter().fold(
+                Row::new().push(Text::new("Group Size Prime Factors: ")),
+                |row, num| row.push(Text::new(format!("{}, ", num))),
+            ))
+            .push(Text::new(primitive))
+            .push(Text::new(format!(
+                "Producers: {}",
+                analysis
+                    .producers
+                    .iter()
+                    .map(|a| a.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )))
+            .push(analysis.possible_orders.iter().fold(
+                Row::new().push(Text::new("Possible Orders: ")),
+                |row, num| row.push(Text::new(format!("{}, ", num))),
+            ))
+            .push(analysis.orders.iter().fold(
+                Column::new().push(Text::new("Actual Orders: ")),
+                |col, (a, order)| {
+                    col.push(Text::new(format!(
+                        "({}, {}), ",
+                        analysis.describe(a),
+                        order
+                    )))
+                },
+            ))
+            .push(steps)
+    }
+}
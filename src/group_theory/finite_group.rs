@@ -0,0 +1,453 @@
+use num_integer::Integer;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::hash::Hash;
+
+/// the groups built from a number, glucose's GroupType extended by the dihedral groups
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GroupType {
+    Additive,
+    Multiplicative,
+    MultiplicativeStar,
+    /// D_n, the symmetries of a regular n-gon
+    Dihedral,
+}
+
+/// a subgroup as its sorted element indices and a set of elements generating it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subgroup {
+    pub elements: Vec<usize>,
+    pub generators: Vec<usize>,
+}
+
+impl Subgroup {
+    pub fn order(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn contains(&self, other: &Subgroup) -> bool {
+        other
+            .elements
+            .iter()
+            .all(|e| self.elements.binary_search(e).is_ok())
+    }
+}
+
+/// a finite set with an associative operation and identity, stored as its full operation table,
+/// elements are referred to by their index
+#[derive(Debug, Clone)]
+pub struct FiniteGroup {
+    pub name: String,
+    /// the operation symbol, "+" or "*"
+    pub symbol: String,
+    pub labels: Vec<String>,
+    /// table[a][b] = a op b
+    pub table: Vec<Vec<usize>>,
+    pub identity: usize,
+}
+
+impl FiniteGroup {
+    /// None if the operation leaves the elements or has no identity
+    pub fn from_elements<T: Eq + Hash + Clone + Display>(
+        name: &str,
+        symbol: &str,
+        elements: &[T],
+        op: impl Fn(&T, &T) -> T,
+    ) -> Option<Self> {
+        let index: HashMap<&T, usize> = elements.iter().enumerate().map(|(i, e)| (e, i)).collect();
+        let mut table = Vec::with_capacity(elements.len());
+        for a in elements {
+            let mut row = Vec::with_capacity(elements.len());
+            for b in elements {
+                row.push(*index.get(&op(a, b))?);
+            }
+            table.push(row);
+        }
+        let labels = elements.iter().map(|e| e.to_string()).collect();
+        Self::from_table(name, symbol, labels, table)
+    }
+
+    pub fn from_table(
+        name: &str,
+        symbol: &str,
+        labels: Vec<String>,
+        table: Vec<Vec<usize>>,
+    ) -> Option<Self> {
+        let n = labels.len();
+        let identity = (0..n).find(|&e| (0..n).all(|a| table[e][a] == a && table[a][e] == a))?;
+        Some(Self {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            labels,
+            table,
+            identity,
+        })
+    }
+
+    /// Z_n under +, Z_n under * (only a monoid), Z_n^* or D_n
+    pub fn z_n(modulo: u64, group_type: GroupType) -> Option<Self> {
+        match group_type {
+            GroupType::Additive => {
+                let elements: Vec<u64> = (0..modulo).collect();
+                Self::from_elements(&format!("Z_{}", modulo), "+", &elements, |a, b| {
+                    (a + b) % modulo
+                })
+            }
+            GroupType::Multiplicative => {
+                let elements: Vec<u64> = (0..modulo).collect();
+                Self::from_elements(&format!("(Z_{}, *)", modulo), "*", &elements, |a, b| {
+                    a * b % modulo
+                })
+            }
+            GroupType::MultiplicativeStar => {
+                let elements: Vec<u64> = (0..modulo).filter(|a| a.gcd(&modulo) == 1).collect();
+                Self::from_elements(&format!("Z_{}^*", modulo), "*", &elements, |a, b| {
+                    a * b % modulo
+                })
+            }
+            GroupType::Dihedral => Self::dihedral(modulo),
+        }
+    }
+
+    /// D_n with elements r^k s^f for rotations r and a reflection s, None for n = 0
+    pub fn dihedral(n: u64) -> Option<Self> {
+        let elements: Vec<DihedralElement> = (0..2)
+            .flat_map(|reflection| {
+                (0..n).map(move |rotation| DihedralElement::new(n, rotation, reflection == 1))
+            })
+            .collect();
+        Self::from_elements(&format!("D_{}", n), "*", &elements, |a, b| a.compose(b))
+    }
+
+    /// G x H with componentwise operation and labels "(a, b)"
+    pub fn direct_product(&self, other: &FiniteGroup) -> Self {
+        let n = other.order();
+        let index = |a: usize, b: usize| a * n + b;
+        let labels = (0..self.order() * n)
+            .map(|i| format!("({}, {})", self.labels[i / n], other.labels[i % n]))
+            .collect();
+        let table = (0..self.order() * n)
+            .map(|i| {
+                (0..self.order() * n)
+                    .map(|j| index(self.op(i / n, j / n), other.op(i % n, j % n)))
+                    .collect()
+            })
+            .collect();
+        let symbol = if self.symbol == other.symbol {
+            self.symbol.clone()
+        } else {
+            String::from("*")
+        };
+        Self {
+            name: format!("{} x {}", self.name, other.name),
+            symbol,
+            labels,
+            table,
+            identity: index(self.identity, other.identity),
+        }
+    }
+
+    pub fn order(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// the element with this label, whitespace is ignored
+    pub fn find(&self, label: &str) -> Option<usize> {
+        let strip = |text: &str| text.split_whitespace().collect::<String>();
+        let label = strip(label);
+        self.labels.iter().position(|l| strip(l) == label)
+    }
+
+    pub fn op(&self, a: usize, b: usize) -> usize {
+        self.table[a][b]
+    }
+
+    pub fn inverse(&self, a: usize) -> Option<usize> {
+        (0..self.order()).find(|&b| self.table[a][b] == self.identity)
+    }
+
+    /// true if every element has an inverse
+    pub fn is_group(&self) -> bool {
+        (0..self.order()).all(|a| self.inverse(a).is_some())
+    }
+
+    /// the smallest k with a^k = identity, None if the powers never return to it, as for
+    /// the non-units of (Z_n, *)
+    pub fn element_order(&self, a: usize) -> Option<usize> {
+        let mut power = a;
+        for order in 1..=self.order() {
+            if power == self.identity {
+                return Some(order);
+            }
+            power = self.op(power, a);
+        }
+        None
+    }
+
+    /// (order, number of elements with that order), ascending, elements without an order are left out
+    pub fn order_statistics(&self) -> Vec<(usize, usize)> {
+        let mut statistics: Vec<(usize, usize)> = Vec::new();
+        let mut orders: Vec<usize> = (0..self.order())
+            .filter_map(|a| self.element_order(a))
+            .collect();
+        orders.sort_unstable();
+        for order in orders {
+            match statistics.last_mut() {
+                Some((last, count)) if *last == order => *count += 1,
+                _ => statistics.push((order, 1)),
+            }
+        }
+        statistics
+    }
+
+    /// a small generating set, picking elements of large order first
+    pub fn generating_set(&self) -> Vec<usize> {
+        let mut candidates: Vec<usize> = (0..self.order()).collect();
+        candidates.sort_by_key(|a| std::cmp::Reverse(self.element_order(*a)));
+        let mut generators = Vec::new();
+        let mut generated = self.generate(&generators);
+        for a in candidates {
+            if generated.len() == self.order() {
+                break;
+            }
+            if generated.binary_search(&a).is_err() {
+                generators.push(a);
+                generated = self.generate(&generators);
+            }
+        }
+        generators
+    }
+
+    /// the smallest subgroup containing all generators, sorted
+    pub fn generate(&self, generators: &[usize]) -> Vec<usize> {
+        let mut elements = vec![self.identity];
+        let mut seen: HashSet<usize> = elements.iter().cloned().collect();
+        let mut i = 0;
+        while i < elements.len() {
+            for g in generators {
+                let next = self.op(elements[i], *g);
+                if seen.insert(next) {
+                    elements.push(next);
+                }
+            }
+            i += 1;
+        }
+        elements.sort_unstable();
+        elements
+    }
+
+    /// every subgroup, found as joins of cyclic subgroups, ascending by order
+    pub fn subgroups(&self) -> Vec<Subgroup> {
+        let mut subgroups: Vec<Subgroup> = Vec::new();
+        let mut known: HashSet<Vec<usize>> = HashSet::new();
+        for g in 0..self.order() {
+            let elements = self.generate(&[g]);
+            if known.insert(elements.clone()) {
+                subgroups.push(Subgroup {
+                    elements,
+                    generators: vec![g],
+                });
+            }
+        }
+        let cyclic = subgroups.clone();
+        let mut i = 0;
+        while i < subgroups.len() {
+            for c in &cyclic {
+                let g = c.generators[0];
+                if subgroups[i].elements.binary_search(&g).is_ok() {
+                    continue;
+                }
+                let mut generators = subgroups[i].generators.clone();
+                generators.push(g);
+                let elements = self.generate(&generators);
+                if known.insert(elements.clone()) {
+                    subgroups.push(Subgroup {
+                        elements,
+                        generators,
+                    });
+                }
+            }
+            i += 1;
+        }
+        // cyclic subgroups reached through a join still deserve a single generator
+        for subgroup in subgroups.iter_mut() {
+            if let Some(g) = subgroup
+                .elements
+                .iter()
+                .find(|g| self.element_order(**g) == Some(subgroup.order()))
+            {
+                subgroup.generators = vec![*g];
+            }
+        }
+        subgroups.sort_by(|a, b| {
+            a.order()
+                .cmp(&b.order())
+                .then_with(|| a.elements.cmp(&b.elements))
+        });
+        subgroups
+    }
+
+    /// the distinct cosets g subgroup, each sorted, ordered by their smallest element
+    pub fn left_cosets(&self, subgroup: &[usize]) -> Vec<Vec<usize>> {
+        self.cosets(subgroup, |g, h| self.op(g, h))
+    }
+
+    /// the distinct cosets subgroup g, each sorted, ordered by their smallest element
+    pub fn right_cosets(&self, subgroup: &[usize]) -> Vec<Vec<usize>> {
+        self.cosets(subgroup, |g, h| self.op(h, g))
+    }
+
+    fn cosets(&self, subgroup: &[usize], op: impl Fn(usize, usize) -> usize) -> Vec<Vec<usize>> {
+        let mut cosets: Vec<Vec<usize>> = Vec::new();
+        let mut covered = vec![false; self.order()];
+        for g in 0..self.order() {
+            if covered[g] {
+                continue;
+            }
+            let mut coset: Vec<usize> = subgroup.iter().map(|h| op(g, *h)).collect();
+            coset.sort_unstable();
+            coset.iter().for_each(|e| covered[*e] = true);
+            cosets.push(coset);
+        }
+        cosets
+    }
+
+    /// gH = Hg for every g
+    pub fn is_normal(&self, subgroup: &[usize]) -> bool {
+        let mut left = self.left_cosets(subgroup);
+        let mut right = self.right_cosets(subgroup);
+        left.sort();
+        right.sort();
+        left == right
+    }
+
+    /// G/H with every coset labelled by its smallest element as representative, like 1+H or aH,
+    /// None if H is not normal
+    pub fn quotient(&self, subgroup: &[usize], subgroup_name: &str) -> Option<Self> {
+        if !self.is_normal(subgroup) {
+            return None;
+        }
+        let cosets = self.left_cosets(subgroup);
+        let mut coset_of = vec![0; self.order()];
+        for (i, coset) in cosets.iter().enumerate() {
+            coset.iter().for_each(|e| coset_of[*e] = i);
+        }
+        let table = cosets
+            .iter()
+            .map(|a| {
+                cosets
+                    .iter()
+                    .map(|b| coset_of[self.op(a[0], b[0])])
+                    .collect()
+            })
+            .collect();
+        let labels = cosets
+            .iter()
+            .map(|coset| {
+                let representative = &self.labels[coset[0]];
+                if self.symbol == "+" {
+                    format!("{}+{}", representative, subgroup_name)
+                } else {
+                    format!("{}{}", representative, subgroup_name)
+                }
+            })
+            .collect();
+        Self::from_table(
+            &format!("{}/{}", self.name, subgroup_name),
+            &self.symbol,
+            labels,
+            table,
+        )
+    }
+
+    /// "{a, b, c}" from element indices
+    pub fn label_set(&self, elements: &[usize]) -> String {
+        let labels: Vec<&str> = elements.iter().map(|e| self.labels[*e].as_str()).collect();
+        format!("{{{}}}", labels.join(", "))
+    }
+
+    /// "<a, b>" from element indices
+    pub fn label_generators(&self, generators: &[usize]) -> String {
+        let labels: Vec<&str> = generators
+            .iter()
+            .map(|e| self.labels[*e].as_str())
+            .collect();
+        format!("<{}>", labels.join(", "))
+    }
+}
+
+/// r^rotation s^reflection in D_n, acting on the vertices 0..n as x -> rotation + (-1)^reflection x
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DihedralElement {
+    pub n: u64,
+    pub rotation: u64,
+    pub reflection: bool,
+}
+
+impl DihedralElement {
+    pub fn new(n: u64, rotation: u64, reflection: bool) -> Self {
+        Self {
+            n,
+            rotation: rotation % n,
+            reflection,
+        }
+    }
+
+    pub fn apply(&self, vertex: u64) -> u64 {
+        if self.reflection {
+            (self.rotation + self.n - vertex % self.n) % self.n
+        } else {
+            (self.rotation + vertex) % self.n
+        }
+    }
+
+    pub fn inverse(&self) -> Self {
+        if self.reflection {
+            *self
+        } else {
+            Self::new(self.n, self.n - self.rotation, false)
+        }
+    }
+
+    /// self after other, as maps of the vertices
+    pub fn compose(&self, other: &DihedralElement) -> Self {
+        let rotation = if self.reflection {
+            self.rotation + self.n - other.rotation
+        } else {
+            self.rotation + other.rotation
+        };
+        Self::new(self.n, rotation, self.reflection != other.reflection)
+    }
+}
+
+/// e, r, r2, ..., s, rs, r2s, ...
+impl Display for DihedralElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.rotation, self.reflection) {
+            (0, false) => write!(f, "e"),
+            (0, true) => write!(f, "s"),
+            (1, reflection) => write!(f, "r{}", if reflection { "s" } else { "" }),
+            (k, reflection) => write!(f, "r{}{}", k, if reflection { "s" } else { "" }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn element_orders_end_for_monoids() {
+        let monoid = FiniteGroup::z_n(6, GroupType::Multiplicative).unwrap();
+        assert!(!monoid.is_group());
+        let orders: Vec<Option<usize>> = (0..6).map(|a| monoid.element_order(a)).collect();
+        assert_eq!(orders, vec![None, Some(1), None, None, None, Some(2)]);
+        assert_eq!(monoid.order_statistics(), vec![(1, 1), (2, 1)]);
+        let group = FiniteGroup::z_n(12, GroupType::Additive).unwrap();
+        assert_eq!(group.element_order(8), Some(3));
+        assert_eq!(
+            group.order_statistics(),
+            vec![(1, 1), (2, 1), (3, 2), (4, 2), (6, 2), (12, 4)]
+        );
+    }
+}
@@ -0,0 +1,452 @@
+use crate::group_theory::number_theory::{
+    carmichael_lambda, divisor_count, divisor_sigma, divisors, is_fully_factored, mobius,
+    parse_big, totient, wheel_factorization,
+};
+use iced::canvas::{Canvas, Cursor, Frame, Geometry, Path, Program, Stroke};
+use iced::{
+    button, text_input, Align, Button, Color, Column, Element, Length, Point, Radio, Rectangle,
+    Row, Size, Text, TextInput,
+};
+use num_bigint::BigInt;
+use num_traits::{One, Signed, ToPrimitive};
+
+/// the plot shows at most this many consecutive numbers
+pub const PLOT_RANGE_LIMIT: u64 = 300;
+/// every plotted number is factored by trial division, so they stay below this
+pub const PLOT_VALUE_LIMIT: u64 = 1_000_000_000;
+/// larger divisor lists are only counted
+const DIVISOR_LIST_LIMIT: u64 = 1_000;
+const PLOT_HEIGHT: u16 = 200;
+/// room around the plot so that the points at the edges are drawn whole
+const PLOT_MARGIN: f32 = 8.0;
+const POINT_RADIUS: f32 = 3.0;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum NumberFunction {
+    #[default]
+    Totient,
+    Carmichael,
+    Mobius,
+    Sigma,
+    Tau,
+}
+
+impl From<NumberFunction> for String {
+    fn from(function: NumberFunction) -> Self {
+        String::from(match function {
+            NumberFunction::Totient => "phi(n)",
+            NumberFunction::Carmichael => "lambda(n)",
+            NumberFunction::Mobius => "mu(n)",
+            NumberFunction::Sigma => "sigma_k(n)",
+            NumberFunction::Tau => "tau(n)",
+        })
+    }
+}
+
+impl NumberFunction {
+    pub const ALL: [NumberFunction; 5] = [
+        NumberFunction::Totient,
+        NumberFunction::Carmichael,
+        NumberFunction::Mobius,
+        NumberFunction::Sigma,
+        NumberFunction::Tau,
+    ];
+
+    /// the value for a number with the given full factorization
+    pub fn evaluate(self, factors: &[BigInt], k: u32) -> BigInt {
+        match self {
+            NumberFunction::Totient => totient(factors),
+            NumberFunction::Carmichael => carmichael_lambda(factors),
+            NumberFunction::Mobius => BigInt::from(mobius(factors)),
+            NumberFunction::Sigma => divisor_sigma(factors, k),
+            NumberFunction::Tau => divisor_count(factors),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FunctionValues {
+    pub n: BigInt,
+    pub k: u32,
+    pub factors: Vec<BigInt>,
+    pub phi: BigInt,
+    pub lambda: BigInt,
+    pub mu: i8,
+    pub sigma: BigInt,
+    pub tau: BigInt,
+    /// empty above DIVISOR_LIST_LIMIT divisors
+    pub divisors: Vec<BigInt>,
+}
+
+impl FunctionValues {
+    /// None if n cannot be factored by trial division
+    pub fn new(n: BigInt, k: u32) -> Option<Self> {
+        let factors = wheel_factorization(&n);
+        if !is_fully_factored(&factors) {
+            return None;
+        }
+        let tau = divisor_count(&factors);
+        let divisors = if tau <= BigInt::from(DIVISOR_LIST_LIMIT) {
+            divisors(&factors)
+        } else {
+            Vec::new()
+        };
+        Some(Self {
+            k,
+            phi: totient(&factors),
+            lambda: carmichael_lambda(&factors),
+            mu: mobius(&factors),
+            sigma: divisor_sigma(&factors, k),
+            tau,
+            divisors,
+            factors,
+            n,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum IcedFunctionMessage {
+    EditN(String),
+    EditK(String),
+    Calculate,
+    EditRange(String),
+    FunctionSelected(NumberFunction),
+    Plot,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IcedFunctions {
+    n_text: String,
+    n_input: text_input::State,
+    k_text: String,
+    k_input: text_input::State,
+    calculate_button: button::State,
+    range_text: String,
+    range_input: text_input::State,
+    plot_button: button::State,
+
+    function: NumberFunction,
+    values: Option<FunctionValues>,
+    /// (n, f(n)) for every n in the plotted range
+    points: Vec<(u64, BigInt)>,
+
+    error_message: Option<String>,
+}
+
+impl IcedFunctions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, message: IcedFunctionMessage) {
+        match message {
+            IcedFunctionMessage::EditN(content) => {
+                self.n_text = content;
+            }
+            IcedFunctionMessage::EditK(content) => {
+                self.k_text = content;
+            }
+            IcedFunctionMessage::EditRange(content) => {
+                self.range_text = content;
+            }
+            IcedFunctionMessage::Calculate => {
+                self.error_message = None;
+                self.values = None;
+                let k = match self.k() {
+                    Some(k) => k,
+                    None => return,
+                };
+                match parse_big(&self.n_text) {
+                    Some(n) if n.is_positive() => match FunctionValues::new(n, k) {
+                        Some(values) => self.values = Some(values),
+                        None => {
+                            self.error_message =
+                                Some(String::from("n could not be factored by trial division"))
+                        }
+                    },
+                    _ => self.error_message = Some(String::from("n must be a positive number")),
+                }
+            }
+            IcedFunctionMessage::FunctionSelected(function) => {
+                self.function = function;
+                if !self.points.is_empty() {
+                    self.plot();
+                }
+            }
+            IcedFunctionMessage::Plot => {
+                self.plot();
+            }
+        }
+    }
+
+    /// k for sigma_k, 1 if left empty
+    fn k(&mut self) -> Option<u32> {
+        if self.k_text.trim().is_empty() {
+            return Some(1);
+        }
+        match self.k_text.trim().parse::<u32>() {
+            Ok(k) if k <= 10 => Some(k),
+            _ => {
+                self.error_message = Some(String::from("k must be a number from 0 to 10"));
+                None
+            }
+        }
+    }
+
+    fn plot(&mut self) {
+        self.error_message = None;
+        self.points.clear();
+        let k = match self.k() {
+            Some(k) => k,
+            None => return,
+        };
+        let bounds: Vec<Option<u64>> = self
+            .range_text
+            .split("..")
+            .map(|bound| bound.trim().parse::<u64>().ok())
+            .collect();
+        let (start, end) = match bounds[..] {
+            [Some(start), Some(end)] if start >= 1 && start <= end && end <= PLOT_VALUE_LIMIT => {
+                (start, end)
+            }
+            _ => {
+                self.error_message = Some(format!(
+                    "the range looks like 1..100 with numbers from 1 to {}",
+                    PLOT_VALUE_LIMIT
+                ));
+                return;
+            }
+        };
+        if end - start >= PLOT_RANGE_LIMIT {
+            self.error_message = Some(format!(
+                "at most {} numbers can be plotted",
+                PLOT_RANGE_LIMIT
+            ));
+            return;
+        }
+        let function = self.function;
+        self.points = (start..=end)
+            .map(|n| {
+                let factors = wheel_factorization(&BigInt::from(n));
+                (n, function.evaluate(&factors, k))
+            })
+            .collect();
+    }
+
+    pub fn view(&mut self) -> Element<'_, IcedFunctionMessage> {
+        let selected = self.function;
+        let input = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new("n: "))
+            .push(
+                TextInput::new(
+                    &mut self.n_input,
+                    "n",
+                    &self.n_text,
+                    IcedFunctionMessage::EditN,
+                )
+                .on_submit(IcedFunctionMessage::Calculate),
+            )
+            .push(Text::new("k: "))
+            .push(
+                TextInput::new(
+                    &mut self.k_input,
+                    "1",
+                    &self.k_text,
+                    IcedFunctionMessage::EditK,
+                )
+                .width(Length::Units(60))
+                .on_submit(IcedFunctionMessage::Calculate),
+            )
+            .push(
+                Button::new(&mut self.calculate_button, Text::new("Calculate"))
+                    .on_press(IcedFunctionMessage::Calculate),
+            );
+
+        let plot_input = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new("Range: "))
+            .push(
+                TextInput::new(
+                    &mut self.range_input,
+                    "1..100",
+                    &self.range_text,
+                    IcedFunctionMessage::EditRange,
+                )
+                .on_submit(IcedFunctionMessage::Plot),
+            )
+            .push(
+                Button::new(&mut self.plot_button, Text::new("Plot"))
+                    .on_press(IcedFunctionMessage::Plot),
+            );
+        let function_row = NumberFunction::ALL.iter().cloned().fold(
+            Row::new().spacing(10).push(Text::new("Function: ")),
+            |row, function| {
+                row.push(Radio::new(
+                    function,
+                    function,
+                    Some(selected),
+                    IcedFunctionMessage::FunctionSelected,
+                ))
+            },
+        );
+
+        let mut content = Column::new().spacing(10).push(input);
+        if let Some(values) = &self.values {
+            content = content.push(Self::values_view(values));
+        }
+        content = content.push(plot_input).push(function_row);
+        if !self.points.is_empty() {
+            content = content.push(Self::plot_view(&self.points, self.function));
+        }
+        if let Some(message) = &self.error_message {
+            content = content.push(Text::new(message).color([0.921, 0.039, 0.039]));
+        }
+        content.into()
+    }
+
+    fn values_view<'a>(values: &FunctionValues) -> Column<'a, IcedFunctionMessage> {
+        let factors: Vec<String> = values
+            .factors
+            .iter()
+            .filter(|f| !f.is_one())
+            .map(|f| f.to_string())
+            .collect();
+        let divisors = if values.divisors.is_empty() {
+            format!("Divisors: more than {}", DIVISOR_LIST_LIMIT)
+        } else {
+            let divisors: Vec<String> = values.divisors.iter().map(|d| d.to_string()).collect();
+            format!("Divisors: {}", divisors.join(", "))
+        };
+        // Z_n^* is cyclic iff some element reaches the group order, lambda is the largest order
+        let cyclic = if values.lambda == values.phi {
+            format!(
+                "Z_{}^* is cyclic: lambda(n) = phi(n) = {}, which happens exactly for n = 1, 2, 4, p^k, 2p^k",
+                values.n, values.phi
+            )
+        } else {
+            format!(
+                "Z_{}^* is not cyclic: every element order divides lambda(n) = {} < phi(n) = {}",
+                values.n, values.lambda, values.phi
+            )
+        };
+        Column::new()
+            .spacing(5)
+            .push(Text::new(format!(
+                "n = {} = {}",
+                values.n,
+                if factors.is_empty() {
+                    String::from("1")
+                } else {
+                    factors.join(" * ")
+                }
+            )))
+            .push(Text::new(format!("phi(n) = {}", values.phi)))
+            .push(Text::new(format!("lambda(n) = {}", values.lambda)))
+            .push(Text::new(format!("mu(n) = {}", values.mu)))
+            .push(Text::new(format!(
+                "sigma_{}(n) = {}",
+                values.k, values.sigma
+            )))
+            .push(Text::new(format!("tau(n) = {}", values.tau)))
+            .push(Text::new(divisors))
+            .push(Text::new(cyclic))
+    }
+
+    /// a point plot of f(n) joined by lines, scaled to the width of the screen
+    fn plot_view<'a>(
+        points: &[(u64, BigInt)],
+        function: NumberFunction,
+    ) -> Column<'a, IcedFunctionMessage> {
+        let values: Vec<f64> = points
+            .iter()
+            .map(|(_, value)| value.to_f64().unwrap_or(f64::MAX))
+            .collect();
+        let max = values.iter().cloned().fold(0.0, f64::max);
+        let min = values.iter().cloned().fold(0.0, f64::min);
+        Column::new()
+            .spacing(5)
+            .push(Text::new(format!(
+                "{} for n = {}..{}, max {}{}",
+                String::from(function),
+                points[0].0,
+                points[points.len() - 1].0,
+                max,
+                if min < 0.0 {
+                    format!(", min {}", min)
+                } else {
+                    String::new()
+                }
+            )))
+            .push(
+                Canvas::new(FunctionPlot { values, min, max })
+                    .width(Length::Fill)
+                    .height(Length::Units(PLOT_HEIGHT)),
+            )
+    }
+}
+
+/// f(n) over consecutive n, the value axis always contains 0
+struct FunctionPlot {
+    values: Vec<f64>,
+    min: f64,
+    max: f64,
+}
+
+impl FunctionPlot {
+    /// canvas position of the i-th value inside a frame of the given size
+    fn position(&self, size: Size, i: usize, value: f64) -> Point {
+        let width = size.width - 2.0 * PLOT_MARGIN;
+        let height = size.height - 2.0 * PLOT_MARGIN;
+        let x = if self.values.len() > 1 {
+            i as f32 / (self.values.len() - 1) as f32
+        } else {
+            0.5
+        };
+        let y = ((value - self.min) / (self.max - self.min).max(1.0)) as f32;
+        Point::new(PLOT_MARGIN + x * width, PLOT_MARGIN + (1.0 - y) * height)
+    }
+}
+
+impl<M> Program<M> for FunctionPlot {
+    fn draw(&self, bounds: Rectangle, _cursor: Cursor) -> Vec<Geometry> {
+        let size = bounds.size();
+        let mut frame = Frame::new(size);
+        frame.fill_rectangle(Point::ORIGIN, size, Color::from_rgb(0.95, 0.95, 0.95));
+
+        let last = self.values.len().saturating_sub(1);
+        frame.stroke(
+            &Path::line(self.position(size, 0, 0.0), self.position(size, last, 0.0)),
+            Stroke::default().with_color(Color::from_rgb(0.6, 0.6, 0.6)),
+        );
+        let line = Path::new(|builder| {
+            for (i, value) in self.values.iter().enumerate() {
+                let point = self.position(size, i, *value);
+                if i == 0 {
+                    builder.move_to(point);
+                } else {
+                    builder.line_to(point);
+                }
+            }
+        });
+        frame.stroke(
+            &line,
+            Stroke::default().with_color(Color::from_rgb(0.7, 0.75, 0.85)),
+        );
+        // points shrink once they would overlap
+        let radius = ((size.width - 2.0 * PLOT_MARGIN) / (2 * self.values.len()) as f32)
+            .clamp(1.0, POINT_RADIUS);
+        for (i, value) in self.values.iter().enumerate() {
+            let color = if *value >= 0.0 {
+                Color::from_rgb(0.2, 0.4, 0.8)
+            } else {
+                Color::from_rgb(0.8, 0.2, 0.2)
+            };
+            frame.fill(&Path::circle(self.position(size, i, *value), radius), color);
+        }
+        vec![frame.into_geometry()]
+    }
+}
@@ -0,0 +1,527 @@
+use crate::group_theory::finite_group::{FiniteGroup, GroupType};
+use crate::group_theory::permutation::{generate, Permutation};
+use iced::{button, text_input, Align, Button, Column, Command, Element, Row, Text, TextInput};
+use std::collections::HashSet;
+
+/// groups above this are not built, 6! = |S_6|
+pub const GROUP_SIZE_LIMIT: usize = 720;
+/// the isomorphism search backtracks over generator images, so it stays with small groups
+pub const ISOMORPHISM_SEARCH_LIMIT: usize = 128;
+/// maps above this only show kernel and image
+const MAP_LIST_LIMIT: usize = 64;
+
+/// parses direct products of Z_n, Z_n^*, D_n, S_n and permutation groups <p1; p2; ...>,
+/// e.g. "Z_2 x Z_4" or "<(1 2); (1 2 3)>"
+pub fn parse_group(text: &str) -> Result<FiniteGroup, String> {
+    let mut factors = text.split(['x', '×']).map(parse_factor);
+    let first = match factors.next() {
+        Some(first) => first?,
+        None => return Err(String::from("enter a group like Z_15^* or Z_2 x Z_4")),
+    };
+    factors.try_fold(first, |product, factor| {
+        let factor = factor?;
+        if product.order() * factor.order() > GROUP_SIZE_LIMIT {
+            return Err(format!(
+                "groups are limited to {} elements",
+                GROUP_SIZE_LIMIT
+            ));
+        }
+        Ok(product.direct_product(&factor))
+    })
+}
+
+fn parse_factor(text: &str) -> Result<FiniteGroup, String> {
+    let text = text.trim();
+    let too_large = || format!("groups are limited to {} elements", GROUP_SIZE_LIMIT);
+    if let Some(generators) = text.strip_prefix('<').and_then(|t| t.strip_suffix('>')) {
+        let generators = generators
+            .split(';')
+            .map(Permutation::parse)
+            .collect::<Result<Vec<Permutation>, String>>()?;
+        let names: Vec<String> = generators.iter().map(|p| p.to_string()).collect();
+        let elements = generate(&generators)
+            .filter(|elements| elements.len() <= GROUP_SIZE_LIMIT)
+            .ok_or_else(too_large)?;
+        return FiniteGroup::from_elements(
+            &format!("<{}>", names.join(", ")),
+            "o",
+            &elements,
+            |a, b| a.compose(b),
+        )
+        .ok_or_else(|| String::from("the permutations do not form a group"));
+    }
+
+    let compact: String = text.split_whitespace().collect();
+    let mut chars = compact.chars();
+    let kind = chars.next();
+    let rest = chars.as_str();
+    let rest = rest.strip_prefix('_').unwrap_or(rest);
+    let (number, star) = match rest.strip_suffix("^*").or_else(|| rest.strip_suffix('*')) {
+        Some(number) => (number, true),
+        None => (rest, false),
+    };
+    let n: u64 = match number.parse() {
+        Ok(n) if n > 0 => n,
+        _ => {
+            return Err(format!(
+                "{} is not Z_n, Z_n^*, D_n, S_n or <permutations>",
+                text
+            ))
+        }
+    };
+    let group = match (kind, star) {
+        (Some('Z'), false) if n as usize <= GROUP_SIZE_LIMIT => {
+            FiniteGroup::z_n(n, GroupType::Additive)
+        }
+        (Some('Z'), true) if n as usize <= GROUP_SIZE_LIMIT => {
+            FiniteGroup::z_n(n, GroupType::MultiplicativeStar)
+        }
+        (Some('D'), false) if 2 * n as usize <= GROUP_SIZE_LIMIT => FiniteGroup::dihedral(n),
+        (Some('S'), false) if factorial_fits(n) => {
+            let cycle: Vec<String> = (1..=n).map(|point| point.to_string()).collect();
+            let generators = [
+                Permutation::parse(if n > 1 { "(1 2)" } else { "()" })?,
+                Permutation::parse(&format!("({})", cycle.join(" ")))?,
+            ];
+            generate(&generators).and_then(|elements| {
+                FiniteGroup::from_elements(&format!("S_{}", n), "o", &elements, |a, b| a.compose(b))
+            })
+        }
+        (Some('Z'), _) | (Some('D'), false) | (Some('S'), false) => return Err(too_large()),
+        _ => {
+            return Err(format!(
+                "{} is not Z_n, Z_n^*, D_n, S_n or <permutations>",
+                text
+            ))
+        }
+    };
+    group.ok_or_else(|| format!("{} could not be built", text))
+}
+
+/// n! <= GROUP_SIZE_LIMIT without overflowing on the way
+fn factorial_fits(n: u64) -> bool {
+    (1..=n as usize)
+        .try_fold(1usize, |factorial, k| factorial.checked_mul(k))
+        .is_some_and(|factorial| factorial <= GROUP_SIZE_LIMIT)
+}
+
+/// extends generator images along the subgroup they generate, unreached elements stay None,
+/// Err if an element would get two images. f(x g) = f(x) f(g) on every edge of the Cayley graph
+/// makes f(a b) = f(a) f(b) for all a, b, so a consistent map is always a homomorphism
+fn extend_partial(
+    domain: &FiniteGroup,
+    codomain: &FiniteGroup,
+    images: &[(usize, usize)],
+) -> Result<Vec<Option<usize>>, String> {
+    let mut map = vec![None; domain.order()];
+    map[domain.identity] = Some(codomain.identity);
+    let mut reached = vec![domain.identity];
+    let mut i = 0;
+    while i < reached.len() {
+        let x = reached[i];
+        let x_image = map[x].unwrap();
+        for (g, g_image) in images {
+            let next = domain.op(x, *g);
+            let next_image = codomain.op(x_image, *g_image);
+            match map[next] {
+                None => {
+                    map[next] = Some(next_image);
+                    reached.push(next);
+                }
+                Some(image) if image != next_image => {
+                    return Err(format!(
+                        "not well defined: {} would map to both {} and {}",
+                        domain.labels[next], codomain.labels[image], codomain.labels[next_image]
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+        i += 1;
+    }
+    Ok(map)
+}
+
+/// the map on all of domain defined by the generator images
+pub fn extend_map(
+    domain: &FiniteGroup,
+    codomain: &FiniteGroup,
+    images: &[(usize, usize)],
+) -> Result<Vec<usize>, String> {
+    let map = extend_partial(domain, codomain, images)?;
+    let reached = map.iter().filter(|image| image.is_some()).count();
+    if reached < domain.order() {
+        return Err(format!(
+            "the generators only generate a subgroup of order {} in {}",
+            reached, domain.name
+        ));
+    }
+    Ok(map.into_iter().map(Option::unwrap).collect())
+}
+
+pub fn kernel(codomain: &FiniteGroup, map: &[usize]) -> Vec<usize> {
+    (0..map.len())
+        .filter(|a| map[*a] == codomain.identity)
+        .collect()
+}
+
+pub fn image(map: &[usize]) -> Vec<usize> {
+    let mut image = map.to_vec();
+    image.sort_unstable();
+    image.dedup();
+    image
+}
+
+/// generator images of an isomorphism g -> h, None if the groups are not isomorphic
+pub fn find_isomorphism(g: &FiniteGroup, h: &FiniteGroup) -> Option<Vec<(usize, usize)>> {
+    if g.order() != h.order() || g.order_statistics() != h.order_statistics() {
+        return None;
+    }
+    let generators = g.generating_set();
+    let h_orders: Vec<Option<usize>> = (0..h.order()).map(|a| h.element_order(a)).collect();
+    let mut images = Vec::with_capacity(generators.len());
+    if search_images(g, h, &generators, &h_orders, &mut images) {
+        Some(images)
+    } else {
+        None
+    }
+}
+
+/// assigns images of equal order one generator at a time, keeping the partial map injective
+fn search_images(
+    g: &FiniteGroup,
+    h: &FiniteGroup,
+    generators: &[usize],
+    h_orders: &[Option<usize>],
+    images: &mut Vec<(usize, usize)>,
+) -> bool {
+    let generator = match generators.get(images.len()) {
+        Some(generator) => *generator,
+        None => return true,
+    };
+    let order = g.element_order(generator);
+    for candidate in (0..h.order()).filter(|c| h_orders[*c] == order) {
+        images.push((generator, candidate));
+        let injective = match extend_partial(g, h, images) {
+            Ok(map) => {
+                let reached: Vec<usize> = map.into_iter().flatten().collect();
+                let distinct: HashSet<&usize> = reached.iter().collect();
+                distinct.len() == reached.len()
+            }
+            Err(_) => false,
+        };
+        if injective && search_images(g, h, generators, h_orders, images) {
+            return true;
+        }
+        images.pop();
+    }
+    false
+}
+
+/// the groups are handed back, so the result is read against the groups it was found for
+pub async fn search_isomorphism(g: FiniteGroup, h: FiniteGroup) -> Box<SearchResult> {
+    let images = find_isomorphism(&g, &h);
+    Box::new((g, h, images))
+}
+
+pub type SearchResult = (FiniteGroup, FiniteGroup, Option<Vec<(usize, usize)>>);
+
+/// the map defined by generator images with everything derived from it
+#[derive(Debug, Clone)]
+pub struct MapReport {
+    pub map: Vec<usize>,
+    pub kernel: Vec<usize>,
+    pub image: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub enum IcedHomomorphismMessage {
+    EditDomain(String),
+    EditCodomain(String),
+    EditMap(String),
+    Check,
+    Search,
+    Searched(Box<SearchResult>),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IcedHomomorphisms {
+    domain_text: String,
+    domain_input: text_input::State,
+    codomain_text: String,
+    codomain_input: text_input::State,
+    map_text: String,
+    map_input: text_input::State,
+    check_button: button::State,
+    search_button: button::State,
+
+    domain: Option<FiniteGroup>,
+    codomain: Option<FiniteGroup>,
+    report: Option<MapReport>,
+    searching: bool,
+    search_message: Option<String>,
+
+    error_message: Option<String>,
+}
+
+impl IcedHomomorphisms {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, message: IcedHomomorphismMessage) -> Command<IcedHomomorphismMessage> {
+        match message {
+            IcedHomomorphismMessage::EditDomain(content) => {
+                self.domain_text = content;
+            }
+            IcedHomomorphismMessage::EditCodomain(content) => {
+                self.codomain_text = content;
+            }
+            IcedHomomorphismMessage::EditMap(content) => {
+                self.map_text = content;
+            }
+            IcedHomomorphismMessage::Check => {
+                if !self.searching && self.parse_groups() {
+                    self.check();
+                }
+            }
+            IcedHomomorphismMessage::Search => {
+                if self.searching || !self.parse_groups() {
+                    return Command::none();
+                }
+                if let (Some(g), Some(h)) = (&self.domain, &self.codomain) {
+                    if g.order() > ISOMORPHISM_SEARCH_LIMIT {
+                        self.error_message = Some(format!(
+                            "the isomorphism search is limited to {} elements",
+                            ISOMORPHISM_SEARCH_LIMIT
+                        ));
+                        return Command::none();
+                    }
+                    self.searching = true;
+                    return Command::perform(
+                        search_isomorphism(g.clone(), h.clone()),
+                        IcedHomomorphismMessage::Searched,
+                    );
+                }
+            }
+            IcedHomomorphismMessage::Searched(result) => {
+                let (g, h, images) = *result;
+                self.searching = false;
+                self.domain = Some(g);
+                self.codomain = Some(h);
+                if let (Some(g), Some(h)) = (&self.domain, &self.codomain) {
+                    match images {
+                        Some(images) => {
+                            let pairs: Vec<String> = images
+                                .iter()
+                                .map(|(a, b)| format!("{} -> {}", g.labels[*a], h.labels[*b]))
+                                .collect();
+                            self.search_message =
+                                Some(format!("{} is isomorphic to {}", g.name, h.name));
+                            self.map_text = pairs.join("; ");
+                            self.check();
+                        }
+                        None => {
+                            self.search_message =
+                                Some(format!("{} is not isomorphic to {}", g.name, h.name));
+                        }
+                    }
+                }
+            }
+        }
+        Command::none()
+    }
+
+    /// parses both groups, false and an error if one of them fails
+    fn parse_groups(&mut self) -> bool {
+        self.error_message = None;
+        self.search_message = None;
+        self.report = None;
+        let groups = parse_group(&self.domain_text)
+            .map_err(|message| format!("G: {}", message))
+            .and_then(|g| {
+                parse_group(&self.codomain_text)
+                    .map(|h| (g, h))
+                    .map_err(|message| format!("H: {}", message))
+            });
+        match groups {
+            Ok((g, h)) => {
+                self.domain = Some(g);
+                self.codomain = Some(h);
+                true
+            }
+            Err(message) => {
+                self.domain = None;
+                self.codomain = None;
+                self.error_message = Some(message);
+                false
+            }
+        }
+    }
+
+    /// reads "a -> b; c -> d" and builds the report for the current groups
+    fn check(&mut self) {
+        let (g, h) = match (&self.domain, &self.codomain) {
+            (Some(g), Some(h)) => (g, h),
+            _ => return,
+        };
+        let images: Result<Vec<(usize, usize)>, String> = self
+            .map_text
+            .split(';')
+            .filter(|pair| !pair.trim().is_empty())
+            .map(|pair| {
+                let (a, b) = pair
+                    .split_once("->")
+                    .ok_or_else(|| String::from("map generators like 2 -> (1, 0); 14 -> (0, 1)"))?;
+                let a = g
+                    .find(a)
+                    .ok_or_else(|| format!("{} is not an element of {}", a.trim(), g.name))?;
+                let b = h
+                    .find(b)
+                    .ok_or_else(|| format!("{} is not an element of {}", b.trim(), h.name))?;
+                Ok((a, b))
+            })
+            .collect();
+        match images.and_then(|images| extend_map(g, h, &images)) {
+            Ok(map) => {
+                self.report = Some(MapReport {
+                    kernel: kernel(h, &map),
+                    image: image(&map),
+                    map,
+                });
+            }
+            Err(message) => self.error_message = Some(message),
+        }
+    }
+
+    pub fn view(&mut self) -> Element<'_, IcedHomomorphismMessage> {
+        let mut check_button = Button::new(&mut self.check_button, Text::new("Check Map"));
+        let mut search_button =
+            Button::new(&mut self.search_button, Text::new("Search Isomorphism"));
+        if !self.searching {
+            check_button = check_button.on_press(IcedHomomorphismMessage::Check);
+            search_button = search_button.on_press(IcedHomomorphismMessage::Search);
+        }
+
+        let input = Column::new()
+            .spacing(5)
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .align_items(Align::Center)
+                    .push(Text::new("G: "))
+                    .push(TextInput::new(
+                        &mut self.domain_input,
+                        "Z_15^*, Z_2 x Z_4, D_4, S_3, <(1 2); (1 2 3)>",
+                        &self.domain_text,
+                        IcedHomomorphismMessage::EditDomain,
+                    )),
+            )
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .align_items(Align::Center)
+                    .push(Text::new("H: "))
+                    .push(TextInput::new(
+                        &mut self.codomain_input,
+                        "Z_2 x Z_4",
+                        &self.codomain_text,
+                        IcedHomomorphismMessage::EditCodomain,
+                    )),
+            )
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .align_items(Align::Center)
+                    .push(Text::new("f: "))
+                    .push(
+                        TextInput::new(
+                            &mut self.map_input,
+                            "generator -> image; ..., e.g. 2 -> (0, 1); 14 -> (1, 0)",
+                            &self.map_text,
+                            IcedHomomorphismMessage::EditMap,
+                        )
+                        .on_submit(IcedHomomorphismMessage::Check),
+                    ),
+            )
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .push(check_button)
+                    .push(search_button),
+            );
+
+        let mut content = Column::new().spacing(10).push(input);
+        for group in [&self.domain, &self.codomain].iter().copied().flatten() {
+            let statistics: Vec<String> = group
+                .order_statistics()
+                .iter()
+                .map(|(order, count)| format!("{} of order {}", count, order))
+                .collect();
+            content = content.push(Text::new(format!(
+                "{}: order {}, {}",
+                group.name,
+                group.order(),
+                statistics.join(", ")
+            )));
+        }
+        if self.searching {
+            content = content.push(Text::new("searching..."));
+        }
+        if let Some(message) = &self.search_message {
+            content = content.push(Text::new(message));
+        }
+        if let (Some(report), Some(g), Some(h)) = (&self.report, &self.domain, &self.codomain) {
+            content = content.push(Self::report_view(report, g, h));
+        }
+        if let Some(message) = &self.error_message {
+            content = content.push(Text::new(message).color([0.921, 0.039, 0.039]));
+        }
+        content.into()
+    }
+
+    fn report_view<'a>(
+        report: &MapReport,
+        g: &FiniteGroup,
+        h: &FiniteGroup,
+    ) -> Column<'a, IcedHomomorphismMessage> {
+        let homomorphism = format!(
+            "f(x {} g) = f(x) {} f(g) holds on every generator edge, so f is a homomorphism",
+            g.symbol, h.symbol
+        );
+        let injective = report.kernel.len() == 1;
+        let surjective = report.image.len() == h.order();
+        let verdict = match (injective, surjective) {
+            (true, true) => format!(
+                "f is an isomorphism, {} is isomorphic to {}",
+                g.name, h.name
+            ),
+            (true, false) => String::from("f is injective, but not surjective"),
+            (false, true) => String::from("f is surjective, but not injective"),
+            (false, false) => String::from("f is neither injective nor surjective"),
+        };
+
+        let mut content = Column::new()
+            .spacing(5)
+            .push(Text::new(homomorphism))
+            .push(Text::new(format!(
+                "ker f = {}",
+                g.label_set(&report.kernel)
+            )))
+            .push(Text::new(format!("im f = {}", h.label_set(&report.image))))
+            .push(Text::new(format!(
+                "|G| / |ker f| = {} / {} = {} = |im f|",
+                g.order(),
+                report.kernel.len(),
+                g.order() / report.kernel.len(),
+            )))
+            .push(Text::new(verdict));
+        if g.order() <= MAP_LIST_LIMIT {
+            let pairs: Vec<String> = (0..g.order())
+                .map(|a| format!("{} -> {}", g.labels[a], h.labels[report.map[a]]))
+                .collect();
+            content = content.push(Text::new(pairs.join(", ")));
+        }
+        content
+    }
+}
@@ -0,0 +1,570 @@
+use crate::group_theory::finite_group::GroupType;
+use glucose::DMatrix;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+/// groups with more elements than this are analysed, but not listed element by element
+pub const ENUMERATION_LIMIT: u64 = 10_000;
+
+/// trial division gives up on divisors above this, the rest is left as one (possibly composite) factor
+pub const TRIAL_DIVISION_LIMIT: u64 = 1_000_000;
+
+const MILLER_RABIN_BASES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+pub fn parse_big(text: &str) -> Option<BigInt> {
+    text.trim().parse::<BigInt>().ok()
+}
+
+/// input: 0 < a < b, same layout as glucose: columns a, b, k, s, t
+pub fn extended_euclidean_as_dmatrix(a: &BigInt, b: &BigInt) -> DMatrix<BigInt> {
+    assert!(a.is_positive() && a < b);
+    let (mut a, mut b) = (a.clone(), b.clone());
+    let mut abs = vec![(a.clone(), b.clone())];
+    let mut ks = Vec::new();
+    while !(&b % &a).is_zero() {
+        let k = &b / &a;
+        let a_tmp = a.clone();
+        a = &b - &a * &k;
+        b = a_tmp;
+        ks.push(k);
+        abs.push((a.clone(), b.clone()));
+    }
+
+    let mut s = BigInt::one();
+    let mut t = BigInt::zero();
+    let mut sts = vec![(s.clone(), t.clone())];
+    for k in ks.iter().rev() {
+        let s_tmp = s.clone();
+        s = t - k * &s;
+        t = s_tmp;
+        sts.push((s.clone(), t.clone()));
+    }
+    sts.reverse();
+    ks.push(-BigInt::one());
+
+    let a_vec = abs.iter().map(|(a, _)| a.clone()).collect();
+    let b_vec = abs.into_iter().map(|(_, b)| b).collect();
+    let s_vec = sts.iter().map(|(s, _)| s.clone()).collect();
+    let t_vec = sts.into_iter().map(|(_, t)| t).collect();
+    DMatrix::new(vec![a_vec, b_vec, ks, s_vec, t_vec])
+}
+
+/// returns the inverse of num mod modulo if gcd(num, modulo) = 1
+pub fn mod_inverse(num: &BigInt, modulo: &BigInt) -> Option<BigInt> {
+    let extended = num.mod_floor(modulo).extended_gcd(modulo);
+    if extended.gcd.is_one() {
+        Some(extended.x.mod_floor(modulo))
+    } else {
+        None
+    }
+}
+
+/// base^exp mod modulo, negative exponents go through the modular inverse
+pub fn mod_pow(base: &BigInt, exp: &BigInt, modulo: &BigInt) -> Option<BigInt> {
+    if exp.is_negative() {
+        mod_inverse(base, modulo).map(|inverse| inverse.modpow(&-exp, modulo))
+    } else {
+        Some(base.mod_floor(modulo).modpow(exp, modulo))
+    }
+}
+
+/// determinant of a square matrix over Z_n by cofactor expansion along the first row,
+/// only meant for the small matrices of ciphers and examples
+pub fn determinant_mod(matrix: &[Vec<BigInt>], modulo: &BigInt) -> BigInt {
+    match matrix.len() {
+        0 => BigInt::one() % modulo,
+        1 => matrix[0][0].mod_floor(modulo),
+        size => (0..size)
+            .map(|j| {
+                let cofactor = determinant_mod(&minor(matrix, 0, j), modulo);
+                let term = &matrix[0][j] * cofactor;
+                if j % 2 == 0 {
+                    term
+                } else {
+                    -term
+                }
+            })
+            .sum::<BigInt>()
+            .mod_floor(modulo),
+    }
+}
+
+/// the matrix without the given row and column
+fn minor(matrix: &[Vec<BigInt>], row: usize, column: usize) -> Vec<Vec<BigInt>> {
+    matrix
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != row)
+        .map(|(_, entries)| {
+            entries
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != column)
+                .map(|(_, entry)| entry.clone())
+                .collect()
+        })
+        .collect()
+}
+
+/// det^-1 * adj(M) over Z_n, None if the determinant is no unit mod n
+pub fn matrix_inverse_mod(matrix: &[Vec<BigInt>], modulo: &BigInt) -> Option<Vec<Vec<BigInt>>> {
+    let size = matrix.len();
+    let det_inverse = mod_inverse(&determinant_mod(matrix, modulo), modulo)?;
+    if size == 1 {
+        return Some(vec![vec![det_inverse]]);
+    }
+    // the adjugate is the transposed cofactor matrix
+    let inverse = (0..size)
+        .map(|i| {
+            (0..size)
+                .map(|j| {
+                    let cofactor = determinant_mod(&minor(matrix, j, i), modulo);
+                    let cofactor = if (i + j) % 2 == 0 {
+                        cofactor
+                    } else {
+                        -cofactor
+                    };
+                    (cofactor * &det_inverse).mod_floor(modulo)
+                })
+                .collect()
+        })
+        .collect();
+    Some(inverse)
+}
+
+pub fn matrix_vector_mod(
+    matrix: &[Vec<BigInt>],
+    vector: &[BigInt],
+    modulo: &BigInt,
+) -> Vec<BigInt> {
+    matrix
+        .iter()
+        .map(|row| {
+            row.iter()
+                .zip(vector)
+                .map(|(a, b)| a * b)
+                .sum::<BigInt>()
+                .mod_floor(modulo)
+        })
+        .collect()
+}
+
+pub fn mul_mod_u64(a: u64, b: u64, modulo: u64) -> u64 {
+    ((a as u128 * b as u128) % modulo as u128) as u64
+}
+
+pub fn pow_mod_u64(mut base: u64, mut exp: u64, modulo: u64) -> u64 {
+    let mut result = 1 % modulo;
+    base %= modulo;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod_u64(result, base, modulo);
+        }
+        base = mul_mod_u64(base, base, modulo);
+        exp >>= 1;
+    }
+    result
+}
+
+/// square root of num mod an odd prime by Tonelli-Shanks, None if num is a non-residue
+pub fn sqrt_mod_prime_u64(num: u64, prime: u64) -> Option<u64> {
+    let num = num % prime;
+    if num == 0 || prime == 2 {
+        return Some(num);
+    }
+    if pow_mod_u64(num, (prime - 1) / 2, prime) != 1 {
+        return None;
+    }
+    let s = (prime - 1).trailing_zeros();
+    let q = (prime - 1) >> s;
+    let mut z = 2;
+    while pow_mod_u64(z, (prime - 1) / 2, prime) != prime - 1 {
+        z += 1;
+    }
+    let mut m = s;
+    let mut c = pow_mod_u64(z, q, prime);
+    let mut t = pow_mod_u64(num, q, prime);
+    let mut r = pow_mod_u64(num, q.div_ceil(2), prime);
+    while t != 1 {
+        let mut i = 0;
+        let mut t_power = t;
+        while t_power != 1 {
+            t_power = mul_mod_u64(t_power, t_power, prime);
+            i += 1;
+        }
+        let b = pow_mod_u64(c, 1 << (m - i - 1), prime);
+        m = i;
+        c = mul_mod_u64(b, b, prime);
+        t = mul_mod_u64(t, c, prime);
+        r = mul_mod_u64(r, b, prime);
+    }
+    Some(r)
+}
+
+/// the x with x = residue mod modulo for every pair, the moduli have to be pairwise coprime
+pub fn crt(congruences: &[(BigInt, BigInt)]) -> Option<BigInt> {
+    let product: BigInt = congruences.iter().map(|(_, modulo)| modulo).product();
+    congruences
+        .iter()
+        .try_fold(BigInt::zero(), |x, (residue, modulo)| {
+            let rest = &product / modulo;
+            let inverse = mod_inverse(&rest, modulo)?;
+            Some(x + residue * rest * inverse)
+        })
+        .map(|x| x.mod_floor(&product))
+}
+
+/// sieve of eratosthenes
+pub fn primes_up_to(bound: u64) -> Vec<u64> {
+    let bound = bound as usize;
+    let mut is_composite = vec![false; bound + 1];
+    let mut primes = Vec::new();
+    for i in 2..=bound {
+        if !is_composite[i] {
+            primes.push(i as u64);
+            let mut multiple = i * i;
+            while multiple <= bound {
+                is_composite[multiple] = true;
+                multiple += i;
+            }
+        }
+    }
+    primes
+}
+
+/// Some((root, k)) with root^k = num for the largest such k > 1
+pub fn perfect_power(num: &BigInt) -> Option<(BigInt, u32)> {
+    let bits = num.bits() as u32;
+    (2..=bits.max(2)).rev().find_map(|k| {
+        let root = num.nth_root(k);
+        if root > BigInt::one() && &root.pow(k) == num {
+            Some((root, k))
+        } else {
+            None
+        }
+    })
+}
+
+/// one Miller-Rabin round with num - 1 = d * 2^s, true if num is a probable prime to this base
+pub fn miller_rabin_round(num: &BigInt, base: &BigInt, d: &BigInt, s: u64) -> bool {
+    let num_minus_one: BigInt = num - 1u32;
+    let mut x = base.modpow(d, num);
+    if x.is_one() || x == num_minus_one {
+        return true;
+    }
+    for _ in 1..s {
+        x = x.modpow(&BigInt::from(2), num);
+        if x == num_minus_one {
+            return true;
+        }
+    }
+    false
+}
+
+/// splits num - 1 into d * 2^s with d odd
+pub fn split_power_of_two(num_minus_one: &BigInt) -> (BigInt, u64) {
+    let s = num_minus_one.trailing_zeros().unwrap_or(0);
+    (num_minus_one >> s, s)
+}
+
+/// Miller-Rabin with the first 13 primes as bases,
+/// deterministic below 3.3 * 10^24 and a probable prime test above
+pub fn is_prime(num: &BigInt) -> bool {
+    if num < &BigInt::from(2) {
+        return false;
+    }
+    for p in MILLER_RABIN_BASES.iter() {
+        if num == &BigInt::from(*p) {
+            return true;
+        }
+        if (num % *p).is_zero() {
+            return false;
+        }
+    }
+    let (d, s) = split_power_of_two(&(num - 1));
+    MILLER_RABIN_BASES
+        .iter()
+        .all(|base| miller_rabin_round(num, &BigInt::from(*base), &d, s))
+}
+
+/// the smallest prime >= num
+pub fn next_prime(num: &BigInt) -> BigInt {
+    let mut candidate = num.clone().max(BigInt::from(2));
+    while !is_prime(&candidate) {
+        candidate += 1u32;
+    }
+    candidate
+}
+
+/// a small xorshift generator, deterministic for a seed and not meant for real keys
+#[derive(Debug, Clone)]
+pub struct XorShift(pub u64);
+
+impl XorShift {
+    /// seeded from the clock
+    pub fn from_time() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos() as u64);
+        Self(nanos | 1)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// a number with exactly the given number of bits
+    pub fn bits(&mut self, bits: u64) -> BigInt {
+        let mut num = BigInt::zero();
+        for _ in 0..bits.div_ceil(64) {
+            num = (num << 64) + self.next_u64();
+        }
+        let num = num % (BigInt::one() << bits);
+        num | (BigInt::one() << (bits - 1))
+    }
+
+    /// a random prime with exactly the given number of bits, at least 2
+    pub fn prime(&mut self, bits: u64) -> BigInt {
+        loop {
+            let prime = next_prime(&self.bits(bits.max(2)));
+            if prime.bits() == bits.max(2) {
+                return prime;
+            }
+        }
+    }
+}
+
+/// trial division with a 2, 3, 5 wheel up to TRIAL_DIVISION_LIMIT,
+/// the last factor may be composite if it has no divisor below the limit
+pub fn wheel_factorization(num: &BigInt) -> Vec<BigInt> {
+    let mut num = num.abs();
+    let mut primes = Vec::new();
+    let wheel: [u64; 11] = [1, 2, 2, 4, 2, 4, 2, 4, 6, 2, 6];
+    let (mut f, mut w) = (2u64, 0);
+    let mut bound = num.sqrt();
+
+    while BigInt::from(f) <= bound && f <= TRIAL_DIVISION_LIMIT {
+        if (&num % f).is_zero() {
+            primes.push(BigInt::from(f));
+            num /= f;
+            bound = num.sqrt();
+        } else {
+            f += wheel[w];
+            w = if w == 10 { 3 } else { w + 1 };
+        }
+    }
+    primes.push(num);
+    primes
+}
+
+/// true if every factor returned by a factorization is prime
+pub fn is_fully_factored(factors: &[BigInt]) -> bool {
+    factors
+        .iter()
+        .all(|factor| factor.is_one() || is_prime(factor))
+}
+
+/// collapses a sorted factorization into (prime, exponent) pairs
+pub fn prime_powers(factors: &[BigInt]) -> Vec<(BigInt, u32)> {
+    let mut powers: Vec<(BigInt, u32)> = Vec::new();
+    for factor in factors.iter().filter(|factor| !factor.is_one()) {
+        match powers.last_mut() {
+            Some((prime, exp)) if prime == factor => *exp += 1,
+            _ => powers.push((factor.clone(), 1)),
+        }
+    }
+    powers
+}
+
+/// a generator of Z_(prime^exp)^* for an odd prime, None if prime - 1 cannot be factored
+pub fn primitive_root(prime: &BigInt, exp: u32) -> Option<BigInt> {
+    let factors = wheel_factorization(&(prime - 1u32));
+    if !is_fully_factored(&factors) {
+        return None;
+    }
+    Some(primitive_root_with(prime, exp, &factors))
+}
+
+/// a generator of Z_(prime^exp)^* for an odd prime, given the prime factors of prime - 1
+pub fn primitive_root_with(prime: &BigInt, exp: u32, factors: &[BigInt]) -> BigInt {
+    let prime_minus_one: BigInt = prime - 1u32;
+    let mut root = BigInt::from(2);
+    while !prime_powers(factors)
+        .iter()
+        .all(|(q, _)| !root.modpow(&(&prime_minus_one / q), prime).is_one())
+    {
+        root += 1u32;
+    }
+    // a root mod p stays one mod every p^k unless it is a root of unity mod p^2
+    let square = prime * prime;
+    if exp > 1 && root.modpow(&prime_minus_one, &square).is_one() {
+        root += prime;
+    }
+    root
+}
+
+/// euler's phi from a full prime factorization
+pub fn totient(factors: &[BigInt]) -> BigInt {
+    prime_powers(factors)
+        .iter()
+        .fold(BigInt::one(), |phi, (prime, exp)| {
+            phi * prime.pow(exp - 1) * (prime - 1)
+        })
+}
+
+/// carmichael's lambda, the exponent of Z_n^*, from a full prime factorization
+pub fn carmichael_lambda(factors: &[BigInt]) -> BigInt {
+    prime_powers(factors)
+        .iter()
+        .fold(BigInt::one(), |lambda, (prime, exp)| {
+            let part = if prime == &BigInt::from(2) && *exp >= 3 {
+                BigInt::from(2).pow(exp - 2)
+            } else {
+                prime.pow(exp - 1) * (prime - 1)
+            };
+            lambda.lcm(&part)
+        })
+}
+
+/// 0 if a square divides n, (-1)^(number of primes) otherwise
+pub fn mobius(factors: &[BigInt]) -> i8 {
+    let powers = prime_powers(factors);
+    if powers.iter().any(|(_, exp)| *exp > 1) {
+        0
+    } else if powers.len().is_even() {
+        1
+    } else {
+        -1
+    }
+}
+
+/// the sum of d^k over all divisors d, sigma_0 counts the divisors
+pub fn divisor_sigma(factors: &[BigInt], k: u32) -> BigInt {
+    prime_powers(factors)
+        .iter()
+        .fold(BigInt::one(), |sigma, (prime, exp)| {
+            let base = prime.pow(k);
+            let mut power = BigInt::one();
+            let mut sum = BigInt::zero();
+            for _ in 0..=*exp {
+                sum += &power;
+                power *= &base;
+            }
+            sigma * sum
+        })
+}
+
+/// the number of divisors
+pub fn divisor_count(factors: &[BigInt]) -> BigInt {
+    prime_powers(factors)
+        .iter()
+        .fold(BigInt::one(), |tau, (_, exp)| tau * (exp + 1))
+}
+
+pub fn group_size(modulo: &BigInt, modulo_factors: &[BigInt], kind: GroupType) -> BigInt {
+    match kind {
+        GroupType::Additive => modulo.clone(),
+        GroupType::Multiplicative => modulo.clone(),
+        GroupType::MultiplicativeStar => totient(modulo_factors),
+        GroupType::Dihedral => modulo * 2u32,
+    }
+}
+
+pub fn group(modulo: &BigInt, kind: GroupType) -> Vec<BigInt> {
+    let elements = residues(modulo);
+    match kind {
+        GroupType::Additive => elements.collect(),
+        GroupType::Multiplicative => elements.collect(),
+        GroupType::MultiplicativeStar => elements.filter(|e| e.gcd(modulo).is_one()).collect(),
+        // D_n has no residues, its elements come from FiniteGroup::dihedral
+        GroupType::Dihedral => Vec::new(),
+    }
+}
+
+/// 0..modulo, only meant for moduli below ENUMERATION_LIMIT
+pub fn residues(modulo: &BigInt) -> impl Iterator<Item = BigInt> {
+    let end = modulo.to_u64().unwrap_or(0);
+    (0..end).map(BigInt::from)
+}
+
+/// all divisors of the number with the given prime factorization, ascending
+pub fn divisors(factors: &[BigInt]) -> Vec<BigInt> {
+    let mut divisors = vec![BigInt::one()];
+    for (prime, exp) in prime_powers(factors) {
+        let mut next = Vec::with_capacity(divisors.len() * (exp as usize + 1));
+        for divisor in &divisors {
+            let mut power = BigInt::one();
+            for _ in 0..=exp {
+                next.push(divisor * &power);
+                power *= &prime;
+            }
+        }
+        divisors = next;
+    }
+    divisors.sort();
+    divisors
+}
+
+pub fn possible_orders(group_size_factors: &[BigInt]) -> Vec<BigInt> {
+    divisors(group_size_factors)
+}
+
+pub fn additive_order(modulo: &BigInt, num: &BigInt) -> BigInt {
+    modulo / num.gcd(modulo)
+}
+
+/// order of num in Z_n^*, found by dividing primes out of phi(n); None if num is not a unit
+pub fn multiplicative_order(
+    modulo: &BigInt,
+    num: &BigInt,
+    phi: &BigInt,
+    phi_factors: &[BigInt],
+) -> Option<BigInt> {
+    if !num.gcd(modulo).is_one() {
+        return None;
+    }
+    let mut order = phi.clone();
+    for (prime, _) in prime_powers(phi_factors) {
+        while (&order % &prime).is_zero() && num.modpow(&(&order / &prime), modulo).is_one() {
+            order /= &prime;
+        }
+    }
+    Some(order)
+}
+
+// returns (element, order), elements without an order (non-units of Z_n under *) are left out
+pub fn orders(
+    modulo: &BigInt,
+    group: &[BigInt],
+    kind: GroupType,
+    phi: &BigInt,
+    phi_factors: &[BigInt],
+) -> Vec<(BigInt, BigInt)> {
+    match kind {
+        GroupType::Additive => group
+            .iter()
+            .map(|num| (num.clone(), additive_order(modulo, num)))
+            .collect(),
+        GroupType::Multiplicative | GroupType::MultiplicativeStar => group
+            .iter()
+            .filter_map(|num| {
+                multiplicative_order(modulo, num, phi, phi_factors)
+                    .map(|order| (num.clone(), order))
+            })
+            .collect(),
+        GroupType::Dihedral => Vec::new(),
+    }
+}
+
+/// elements whose order equals the group size
+pub fn producers(group_size: &BigInt, orders: &[(BigInt, BigInt)]) -> Vec<BigInt> {
+    orders
+        .iter()
+        .filter(|(_, order)| order == group_size)
+        .map(|(num, _)| num.clone())
+        .collect()
+}
@@ -0,0 +1,476 @@
+use crate::group_theory::cayley::{
+    table_limit_notice, IcedCayleyMessage, IcedCayleyTable, CAYLEY_TABLE_LIMIT,
+};
+use crate::group_theory::finite_group::FiniteGroup;
+use crate::group_theory::subgroups::{IcedSubgroupLattice, IcedSubgroupMessage};
+use iced::{button, text_input, Align, Button, Column, Element, Row, Text, TextInput};
+use num_integer::Integer;
+use std::collections::HashSet;
+use std::fmt;
+
+/// largest point a permutation may move
+pub const PERMUTATION_DEGREE_LIMIT: usize = 32;
+/// generated groups above this are not enumerated, 8! = |S_8|
+pub const GENERATED_GROUP_LIMIT: usize = 40_320;
+/// generated groups above this only show their order
+const ELEMENT_LIST_LIMIT: usize = 120;
+
+/// a permutation of {1, ..., n}, stored 0 based as the image of every point
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Permutation {
+    images: Vec<usize>,
+}
+
+impl Permutation {
+    pub fn identity(degree: usize) -> Self {
+        Self {
+            images: (0..degree).collect(),
+        }
+    }
+
+    /// None if images is not a bijection of {0, ..., n - 1}
+    pub fn from_images(images: Vec<usize>) -> Option<Self> {
+        let mut seen = vec![false; images.len()];
+        for image in &images {
+            if *seen.get(*image)? {
+                return None;
+            }
+            seen[*image] = true;
+        }
+        Some(Self { images })
+    }
+
+    /// parses cycle notation "(1 3 2)(4 5)" or two-line notation "1 2 3 / 2 3 1",
+    /// "()" and "id" are the identity
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(String::from("empty permutation, use () for the identity"));
+        }
+        if text == "id" || text == "e" {
+            return Ok(Self::identity(1));
+        }
+        if let Some((top, bottom)) = text.split_once('/') {
+            return Self::parse_two_line(top, bottom);
+        }
+        Self::parse_cycles(text)
+    }
+
+    fn parse_two_line(top: &str, bottom: &str) -> Result<Self, String> {
+        let top = parse_points(top)?;
+        let bottom = parse_points(bottom)?;
+        if top.len() != bottom.len() {
+            return Err(String::from("both lines need the same number of points"));
+        }
+        let mut images = vec![usize::MAX; top.len()];
+        for (from, to) in top.iter().zip(bottom.iter()) {
+            match images.get_mut(*from) {
+                Some(image) if *image == usize::MAX => *image = *to,
+                _ => return Err(String::from("the top line has to list 1, ..., n once each")),
+            }
+        }
+        Self::from_images(images)
+            .ok_or_else(|| String::from("the bottom line has to list 1, ..., n once each"))
+    }
+
+    fn parse_cycles(text: &str) -> Result<Self, String> {
+        let mut pieces: Vec<&str> = text.split(')').map(str::trim).collect();
+        if pieces.pop() != Some("") {
+            return Err(String::from("cycles look like (1 3 2)(4 5)"));
+        }
+        let mut cycles = Vec::new();
+        for piece in pieces {
+            let points = match piece.strip_prefix('(') {
+                Some(points) => parse_points(points)?,
+                None => return Err(String::from("cycles look like (1 3 2)(4 5)")),
+            };
+            let distinct: HashSet<&usize> = points.iter().collect();
+            if distinct.len() != points.len() {
+                return Err(String::from("a cycle may not repeat a point"));
+            }
+            cycles.push(points);
+        }
+        let degree = cycles.iter().flatten().max().map_or(1, |max| max + 1);
+        // cycles are applied right to left like any other product
+        Ok(cycles
+            .iter()
+            .fold(Self::identity(degree), |product, cycle| {
+                let mut images: Vec<usize> = (0..degree).collect();
+                for (i, point) in cycle.iter().enumerate() {
+                    images[*point] = cycle[(i + 1) % cycle.len()];
+                }
+                product.compose(&Self { images })
+            }))
+    }
+
+    pub fn degree(&self) -> usize {
+        self.images.len()
+    }
+
+    /// the same permutation on {1, ..., degree}, fixing the new points
+    pub fn extend(&self, degree: usize) -> Self {
+        let mut images = self.images.clone();
+        images.extend(self.degree()..degree);
+        Self { images }
+    }
+
+    pub fn image(&self, point: usize) -> usize {
+        self.images.get(point).cloned().unwrap_or(point)
+    }
+
+    /// (self o other)(x) = self(other(x)), other is applied first
+    pub fn compose(&self, other: &Permutation) -> Self {
+        let degree = self.degree().max(other.degree());
+        Self {
+            images: (0..degree).map(|x| self.image(other.image(x))).collect(),
+        }
+    }
+
+    pub fn inverse(&self) -> Self {
+        let mut images = vec![0; self.degree()];
+        for (point, image) in self.images.iter().enumerate() {
+            images[*image] = point;
+        }
+        Self { images }
+    }
+
+    /// every cycle including fixed points, each starting at its smallest point
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let mut visited = vec![false; self.degree()];
+        let mut cycles = Vec::new();
+        for start in 0..self.degree() {
+            if visited[start] {
+                continue;
+            }
+            let mut cycle = vec![start];
+            visited[start] = true;
+            let mut point = self.images[start];
+            while point != start {
+                visited[point] = true;
+                cycle.push(point);
+                point = self.images[point];
+            }
+            cycles.push(cycle);
+        }
+        cycles
+    }
+
+    /// cycle lengths in descending order, fixed points included
+    pub fn cycle_type(&self) -> Vec<usize> {
+        let mut lengths: Vec<usize> = self.cycles().iter().map(Vec::len).collect();
+        lengths.sort_unstable_by(|a, b| b.cmp(a));
+        lengths
+    }
+
+    /// the lcm of the cycle lengths
+    pub fn order(&self) -> u64 {
+        self.cycles()
+            .iter()
+            .fold(1, |order, cycle| order.lcm(&(cycle.len() as u64)))
+    }
+
+    /// 1 for even, -1 for odd permutations, (-1)^(n - number of cycles)
+    pub fn sign(&self) -> i8 {
+        if (self.degree() - self.cycles().len()).is_even() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.images
+            .iter()
+            .enumerate()
+            .all(|(point, image)| point == *image)
+    }
+}
+
+/// cycle notation with 1 based points, fixed points left out
+impl fmt::Display for Permutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_identity() {
+            return write!(f, "()");
+        }
+        for cycle in self.cycles().iter().filter(|cycle| cycle.len() > 1) {
+            let points: Vec<String> = cycle.iter().map(|point| (point + 1).to_string()).collect();
+            write!(f, "({})", points.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// 1 based points separated by spaces or commas, returned 0 based
+fn parse_points(text: &str) -> Result<Vec<usize>, String> {
+    text.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|point| !point.is_empty())
+        .map(|point| match point.parse::<usize>() {
+            Ok(point) if (1..=PERMUTATION_DEGREE_LIMIT).contains(&point) => Ok(point - 1),
+            _ => Err(format!(
+                "{} is not a point between 1 and {}",
+                point, PERMUTATION_DEGREE_LIMIT
+            )),
+        })
+        .collect()
+}
+
+/// the closure of the generators under composition, None above GENERATED_GROUP_LIMIT
+pub fn generate(generators: &[Permutation]) -> Option<Vec<Permutation>> {
+    let degree = generators
+        .iter()
+        .map(Permutation::degree)
+        .max()
+        .unwrap_or(1);
+    let generators: Vec<Permutation> = generators.iter().map(|g| g.extend(degree)).collect();
+    let mut elements = vec![Permutation::identity(degree)];
+    let mut seen: HashSet<Permutation> = elements.iter().cloned().collect();
+    let mut i = 0;
+    while i < elements.len() {
+        for g in &generators {
+            let next = elements[i].compose(g);
+            if seen.insert(next.clone()) {
+                if elements.len() == GENERATED_GROUP_LIMIT {
+                    return None;
+                }
+                elements.push(next);
+            }
+        }
+        i += 1;
+    }
+    Some(elements)
+}
+
+#[derive(Debug, Clone)]
+pub enum IcedPermutationMessage {
+    EditValue(String),
+    Analyse,
+    Compose,
+    Generate,
+    CayleyMessage(IcedCayleyMessage),
+    SubgroupMessage(IcedSubgroupMessage),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IcedPermutations {
+    edit_text: String,
+    input_text: text_input::State,
+    analyse_button: button::State,
+    compose_button: button::State,
+    generate_button: button::State,
+
+    permutations: Vec<Permutation>,
+    composition: Option<Permutation>,
+    generated: Option<Vec<Permutation>>,
+    cayley: Option<IcedCayleyTable>,
+    lattice: Option<IcedSubgroupLattice>,
+
+    error_message: Option<String>,
+}
+
+impl IcedPermutations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, message: IcedPermutationMessage) {
+        match message {
+            IcedPermutationMessage::EditValue(content) => {
+                self.edit_text = content;
+            }
+            IcedPermutationMessage::Analyse => {
+                self.parse();
+            }
+            IcedPermutationMessage::Compose => {
+                if self.parse() {
+                    let degree = self.degree();
+                    self.composition = Some(
+                        self.permutations
+                            .iter()
+                            .fold(Permutation::identity(degree), |product, p| {
+                                product.compose(p)
+                            }),
+                    );
+                }
+            }
+            IcedPermutationMessage::Generate => {
+                if !self.parse() {
+                    return;
+                }
+                match generate(&self.permutations) {
+                    Some(elements) => {
+                        if elements.len() <= CAYLEY_TABLE_LIMIT {
+                            let generators: Vec<String> =
+                                self.permutations.iter().map(|p| p.to_string()).collect();
+                            let group = FiniteGroup::from_elements(
+                                &format!("<{}>", generators.join(", ")),
+                                "o",
+                                &elements,
+                                |a, b| a.compose(b),
+                            );
+                            self.cayley = group.clone().map(IcedCayleyTable::new);
+                            self.lattice = group.map(IcedSubgroupLattice::new);
+                        }
+                        self.generated = Some(elements);
+                    }
+                    None => {
+                        self.error_message = Some(format!(
+                            "the generated group has more than {} elements",
+                            GENERATED_GROUP_LIMIT
+                        ))
+                    }
+                }
+            }
+            IcedPermutationMessage::CayleyMessage(message) => {
+                if let Some(cayley) = &mut self.cayley {
+                    cayley.update(message)
+                }
+            }
+            IcedPermutationMessage::SubgroupMessage(message) => {
+                if let Some(lattice) = &mut self.lattice {
+                    lattice.update(message)
+                }
+            }
+        }
+    }
+
+    /// parses the ';' separated input into self.permutations, false and an error if that fails
+    fn parse(&mut self) -> bool {
+        self.error_message = None;
+        self.composition = None;
+        self.generated = None;
+        self.cayley = None;
+        self.lattice = None;
+        let parsed: Result<Vec<Permutation>, String> = self
+            .edit_text
+            .split(';')
+            .enumerate()
+            .map(|(i, text)| {
+                Permutation::parse(text)
+                    .map_err(|message| format!("permutation {}: {}", i + 1, message))
+            })
+            .collect();
+        match parsed {
+            Ok(permutations) => {
+                let degree = permutations
+                    .iter()
+                    .map(Permutation::degree)
+                    .max()
+                    .unwrap_or(1);
+                self.permutations = permutations.iter().map(|p| p.extend(degree)).collect();
+                true
+            }
+            Err(message) => {
+                self.permutations.clear();
+                self.error_message = Some(message);
+                false
+            }
+        }
+    }
+
+    fn degree(&self) -> usize {
+        self.permutations.first().map_or(1, Permutation::degree)
+    }
+
+    pub fn view(&mut self) -> Element<'_, IcedPermutationMessage> {
+        let degree = self.degree();
+        let input = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(
+                TextInput::new(
+                    &mut self.input_text,
+                    "(1 3 2)(4 5); 1 2 3 / 2 3 1; ...",
+                    &self.edit_text,
+                    IcedPermutationMessage::EditValue,
+                )
+                .padding(10)
+                .on_submit(IcedPermutationMessage::Analyse),
+            )
+            .push(
+                Button::new(&mut self.analyse_button, Text::new("Analyse"))
+                    .on_press(IcedPermutationMessage::Analyse),
+            )
+            .push(
+                Button::new(&mut self.compose_button, Text::new("Compose"))
+                    .on_press(IcedPermutationMessage::Compose),
+            )
+            .push(
+                Button::new(&mut self.generate_button, Text::new("Generate"))
+                    .on_press(IcedPermutationMessage::Generate),
+            );
+
+        let permutations = self
+            .permutations
+            .iter()
+            .enumerate()
+            .fold(Column::new().spacing(10), |col, (i, p)| {
+                col.push(Self::permutation_view(&format!("p{}", i + 1), p))
+            });
+
+        let mut content = Column::new()
+            .spacing(10)
+            .push(input)
+            .push(Text::new(format!(
+                "Permutations of {{1, ..., {}}}, products are applied right to left: (p o q)(x) = p(q(x))",
+                degree
+            )))
+            .push(permutations);
+
+        if let Some(composition) = &self.composition {
+            let names: Vec<String> = (1..=self.permutations.len())
+                .map(|i| format!("p{}", i))
+                .collect();
+            content = content.push(Self::permutation_view(&names.join(" o "), composition));
+        }
+        if let Some(elements) = &self.generated {
+            content = content.push(Text::new(format!(
+                "The generated subgroup of S_{} has order {}",
+                degree,
+                elements.len()
+            )));
+            if elements.len() <= ELEMENT_LIST_LIMIT {
+                let labels: Vec<String> = elements.iter().map(|p| p.to_string()).collect();
+                content = content.push(Text::new(format!("{{{}}}", labels.join(", "))));
+            }
+            if elements.len() > CAYLEY_TABLE_LIMIT {
+                content = content.push(table_limit_notice(elements.len()));
+            }
+        }
+        if let Some(cayley) = &mut self.cayley {
+            content = content.push(cayley.view().map(IcedPermutationMessage::CayleyMessage));
+        }
+        if let Some(lattice) = &mut self.lattice {
+            content = content.push(lattice.view().map(IcedPermutationMessage::SubgroupMessage));
+        }
+        if let Some(message) = &self.error_message {
+            content = content.push(Text::new(message).color([0.921, 0.039, 0.039]));
+        }
+        content.into()
+    }
+
+    fn permutation_view<'a>(name: &str, p: &Permutation) -> Column<'a, IcedPermutationMessage> {
+        // one small column per point keeps both lines aligned
+        let two_line = (0..p.degree()).fold(Row::new().spacing(8), |row, point| {
+            row.push(
+                Column::new()
+                    .align_items(Align::Center)
+                    .push(Text::new((point + 1).to_string()))
+                    .push(Text::new((p.image(point) + 1).to_string())),
+            )
+        });
+        let cycle_type: Vec<String> = p.cycle_type().iter().map(|l| l.to_string()).collect();
+        let parity = if p.sign() == 1 { "even" } else { "odd" };
+        Column::new()
+            .spacing(5)
+            .push(Text::new(format!("{} = {}", name, p)))
+            .push(two_line)
+            .push(Text::new(format!(
+                "inverse {}, order {}, sign {} ({}), cycle type [{}]",
+                p.inverse(),
+                p.order(),
+                p.sign(),
+                parity,
+                cycle_type.join(", ")
+            )))
+    }
+}
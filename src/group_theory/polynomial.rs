@@ -0,0 +1,1458 @@
+use crate::group_theory::number_theory::{
+    divisors, is_fully_factored, mul_mod_u64, parse_big, pow_mod_u64, prime_powers, primes_up_to,
+    wheel_factorization, XorShift,
+};
+use crate::group_theory::primality::is_prime_u64;
+use iced::{button, text_input, Align, Button, Column, Element, Row, Text, TextInput};
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use std::fmt::{Debug, Display};
+
+/// polynomials are only parsed up to this degree
+pub const POLYNOMIAL_DEGREE_LIMIT: usize = 256;
+/// the rational root test gives up if the leading and constant coefficient have more divisors
+const RATIONAL_ROOT_LIMIT: usize = 10_000;
+/// Cantor-Zassenhaus gives up after this many unlucky random polynomials
+const SPLIT_ATTEMPT_LIMIT: usize = 1_000;
+
+/// what formatting and trimming need to know about a coefficient
+pub trait Coefficient: Clone + PartialEq + Display + Debug {
+    fn is_zero(&self) -> bool;
+    fn is_one(&self) -> bool;
+    fn is_negative(&self) -> bool {
+        false
+    }
+    /// the coefficient without its sign
+    fn magnitude(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl Coefficient for u64 {
+    fn is_zero(&self) -> bool {
+        *self == 0
+    }
+
+    fn is_one(&self) -> bool {
+        *self == 1
+    }
+}
+
+/// the field the coefficients live in, polynomials over it are Vec<Element> with the lowest power first
+pub trait CoefficientField {
+    type Element: Coefficient;
+
+    fn zero(&self) -> Self::Element;
+    fn one(&self) -> Self::Element;
+    fn add(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+    fn neg(&self, a: &Self::Element) -> Self::Element;
+    fn mul(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+    /// a must not be zero
+    fn inverse(&self, a: &Self::Element) -> Self::Element;
+    /// numer / denom, None if denom is zero in the field
+    fn fraction(&self, numer: &BigInt, denom: &BigInt) -> Option<Self::Element>;
+    /// "Z_7" or "Q"
+    fn name(&self) -> String;
+
+    fn sub(&self, a: &Self::Element, b: &Self::Element) -> Self::Element {
+        self.add(a, &self.neg(b))
+    }
+
+    fn integer(&self, num: &BigInt) -> Self::Element {
+        self.fraction(num, &BigInt::one())
+            .unwrap_or_else(|| self.zero())
+    }
+}
+
+/// the integers mod a prime
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Zp(pub u64);
+
+impl CoefficientField for Zp {
+    type Element = u64;
+
+    fn zero(&self) -> u64 {
+        0
+    }
+
+    fn one(&self) -> u64 {
+        1 % self.0
+    }
+
+    fn add(&self, a: &u64, b: &u64) -> u64 {
+        ((*a as u128 + *b as u128) % self.0 as u128) as u64
+    }
+
+    fn neg(&self, a: &u64) -> u64 {
+        (self.0 - a % self.0) % self.0
+    }
+
+    fn mul(&self, a: &u64, b: &u64) -> u64 {
+        mul_mod_u64(*a, *b, self.0)
+    }
+
+    fn inverse(&self, a: &u64) -> u64 {
+        pow_mod_u64(*a, self.0 - 2, self.0)
+    }
+
+    fn fraction(&self, numer: &BigInt, denom: &BigInt) -> Option<u64> {
+        let p = BigInt::from(self.0);
+        let numer = numer.mod_floor(&p).to_u64()?;
+        let denom = denom.mod_floor(&p).to_u64()?;
+        if denom == 0 {
+            None
+        } else {
+            Some(self.mul(&numer, &self.inverse(&denom)))
+        }
+    }
+
+    fn name(&self) -> String {
+        format!("Z_{}", self.0)
+    }
+}
+
+/// a fraction in lowest terms with a positive denominator
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numer: BigInt,
+    denom: BigInt,
+}
+
+impl Rational {
+    /// denom must not be zero
+    pub fn new(numer: BigInt, denom: BigInt) -> Self {
+        let gcd = numer.gcd(&denom);
+        let sign = if denom.is_negative() {
+            -BigInt::one()
+        } else {
+            BigInt::one()
+        };
+        Self {
+            numer: numer / &gcd * &sign,
+            denom: denom / gcd * sign,
+        }
+    }
+
+    pub fn integer(num: BigInt) -> Self {
+        Self {
+            numer: num,
+            denom: BigInt::one(),
+        }
+    }
+
+    pub fn numer(&self) -> &BigInt {
+        &self.numer
+    }
+
+    pub fn denom(&self) -> &BigInt {
+        &self.denom
+    }
+}
+
+/// "3" or "-3/2"
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.denom.is_one() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+impl Coefficient for Rational {
+    fn is_zero(&self) -> bool {
+        self.numer.is_zero()
+    }
+
+    fn is_one(&self) -> bool {
+        self.numer.is_one() && self.denom.is_one()
+    }
+
+    fn is_negative(&self) -> bool {
+        self.numer.is_negative()
+    }
+
+    /// fractions are put in parentheses so that (3/2)x is not read as 3/(2x)
+    fn magnitude(&self) -> String {
+        let magnitude = Rational::new(self.numer.abs(), self.denom.clone());
+        if magnitude.denom.is_one() {
+            magnitude.to_string()
+        } else {
+            format!("({})", magnitude)
+        }
+    }
+}
+
+/// the rational numbers
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Rationals;
+
+impl CoefficientField for Rationals {
+    type Element = Rational;
+
+    fn zero(&self) -> Rational {
+        Rational::integer(BigInt::zero())
+    }
+
+    fn one(&self) -> Rational {
+        Rational::integer(BigInt::one())
+    }
+
+    fn add(&self, a: &Rational, b: &Rational) -> Rational {
+        Rational::new(
+            &a.numer * &b.denom + &b.numer * &a.denom,
+            &a.denom * &b.denom,
+        )
+    }
+
+    fn neg(&self, a: &Rational) -> Rational {
+        Rational::new(-&a.numer, a.denom.clone())
+    }
+
+    fn mul(&self, a: &Rational, b: &Rational) -> Rational {
+        Rational::new(&a.numer * &b.numer, &a.denom * &b.denom)
+    }
+
+    fn inverse(&self, a: &Rational) -> Rational {
+        Rational::new(a.denom.clone(), a.numer.clone())
+    }
+
+    fn fraction(&self, numer: &BigInt, denom: &BigInt) -> Option<Rational> {
+        if denom.is_zero() {
+            None
+        } else {
+            Some(Rational::new(numer.clone(), denom.clone()))
+        }
+    }
+
+    fn name(&self) -> String {
+        String::from("Q")
+    }
+}
+
+/// removes leading zero coefficients, the zero polynomial is empty
+pub fn trim<E: Coefficient>(mut poly: Vec<E>) -> Vec<E> {
+    while poly.last().is_some_and(Coefficient::is_zero) {
+        poly.pop();
+    }
+    poly
+}
+
+/// the degree, None for the zero polynomial
+pub fn degree<E>(poly: &[E]) -> Option<usize> {
+    poly.len().checked_sub(1)
+}
+
+pub fn poly_add<F: CoefficientField>(
+    a: &[F::Element],
+    b: &[F::Element],
+    field: &F,
+) -> Vec<F::Element> {
+    let zero = field.zero();
+    let length = a.len().max(b.len());
+    trim(
+        (0..length)
+            .map(|i| field.add(a.get(i).unwrap_or(&zero), b.get(i).unwrap_or(&zero)))
+            .collect(),
+    )
+}
+
+pub fn poly_sub<F: CoefficientField>(
+    a: &[F::Element],
+    b: &[F::Element],
+    field: &F,
+) -> Vec<F::Element> {
+    let negated: Vec<F::Element> = b.iter().map(|c| field.neg(c)).collect();
+    poly_add(a, &negated, field)
+}
+
+pub fn poly_mul<F: CoefficientField>(
+    a: &[F::Element],
+    b: &[F::Element],
+    field: &F,
+) -> Vec<F::Element> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut product = vec![field.zero(); a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            product[i + j] = field.add(&product[i + j], &field.mul(x, y));
+        }
+    }
+    trim(product)
+}
+
+/// c * x^shift * poly
+fn poly_scale<F: CoefficientField>(
+    poly: &[F::Element],
+    c: &F::Element,
+    shift: usize,
+    field: &F,
+) -> Vec<F::Element> {
+    let mut scaled = vec![field.zero(); shift];
+    scaled.extend(poly.iter().map(|x| field.mul(x, c)));
+    trim(scaled)
+}
+
+/// (quotient, remainder) of a / b, b must not be zero
+pub fn poly_div_rem<F: CoefficientField>(
+    a: &[F::Element],
+    b: &[F::Element],
+    field: &F,
+) -> (Vec<F::Element>, Vec<F::Element>) {
+    let b = trim(b.to_vec());
+    let mut remainder = trim(a.to_vec());
+    if remainder.len() < b.len() {
+        return (Vec::new(), remainder);
+    }
+    let lead_inverse = field.inverse(b.last().unwrap());
+    let mut quotient = vec![field.zero(); remainder.len() - b.len() + 1];
+    while remainder.len() >= b.len() {
+        let shift = remainder.len() - b.len();
+        let factor = field.mul(remainder.last().unwrap(), &lead_inverse);
+        for (i, c) in b.iter().enumerate() {
+            remainder[shift + i] = field.sub(&remainder[shift + i], &field.mul(c, &factor));
+        }
+        quotient[shift] = factor;
+        remainder = trim(remainder);
+    }
+    (trim(quotient), remainder)
+}
+
+/// poly divided by its leading coefficient
+pub fn make_monic<F: CoefficientField>(poly: &[F::Element], field: &F) -> Vec<F::Element> {
+    match poly.last() {
+        Some(lead) => poly_scale(poly, &field.inverse(lead), 0, field),
+        None => Vec::new(),
+    }
+}
+
+/// the monic gcd of a and b
+pub fn poly_gcd<F: CoefficientField>(
+    a: &[F::Element],
+    b: &[F::Element],
+    field: &F,
+) -> Vec<F::Element> {
+    let (mut a, mut b) = (trim(a.to_vec()), trim(b.to_vec()));
+    while !b.is_empty() {
+        let (_, remainder) = poly_div_rem(&a, &b, field);
+        a = std::mem::replace(&mut b, remainder);
+    }
+    make_monic(&a, field)
+}
+
+/// base^exp mod modulus by square and multiply
+pub fn poly_pow_mod<F: CoefficientField>(
+    base: &[F::Element],
+    exp: &BigInt,
+    modulus: &[F::Element],
+    field: &F,
+) -> Vec<F::Element> {
+    let mut result = poly_div_rem(&[field.one()], modulus, field).1;
+    for i in (0..exp.bits()).rev() {
+        result = poly_div_rem(&poly_mul(&result, &result, field), modulus, field).1;
+        if exp.bit(i) {
+            result = poly_div_rem(&poly_mul(&result, base, field), modulus, field).1;
+        }
+    }
+    result
+}
+
+pub fn derivative<F: CoefficientField>(poly: &[F::Element], field: &F) -> Vec<F::Element> {
+    trim(
+        poly.iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, c)| field.mul(c, &field.integer(&BigInt::from(i))))
+            .collect(),
+    )
+}
+
+/// f(x) by Horner's rule
+pub fn evaluate<F: CoefficientField>(poly: &[F::Element], x: &F::Element, field: &F) -> F::Element {
+    poly.iter()
+        .rev()
+        .fold(field.zero(), |value, c| field.add(&field.mul(&value, x), c))
+}
+
+/// "3x^2 - x + 1", highest power first
+pub fn format_poly<E: Coefficient>(poly: &[E], variable: &str) -> String {
+    let mut text = String::new();
+    for (i, c) in poly.iter().enumerate().rev().filter(|(_, c)| !c.is_zero()) {
+        let sign = match (text.is_empty(), c.is_negative()) {
+            (true, true) => "-",
+            (true, false) => "",
+            (false, true) => " - ",
+            (false, false) => " + ",
+        };
+        let coefficient = if c.is_one() || (c.is_negative() && c.magnitude() == "1") {
+            if i == 0 {
+                String::from("1")
+            } else {
+                String::new()
+            }
+        } else {
+            c.magnitude()
+        };
+        let power = match i {
+            0 => String::new(),
+            1 => variable.to_string(),
+            _ => format!("{}^{}", variable, i),
+        };
+        text.push_str(&format!("{}{}{}", sign, coefficient, power));
+    }
+    if text.is_empty() {
+        String::from("0")
+    } else {
+        text
+    }
+}
+
+/// parses "3x^2 - x + 1", "2*x^3", "(3/2)x - 1/4" or "(x + 1)^2 (x - 1)" into the given field
+pub fn parse_poly<F: CoefficientField>(text: &str, field: &F) -> Result<Vec<F::Element>, String> {
+    let mut parser = PolyParser {
+        chars: text.chars().filter(|c| !c.is_whitespace()).collect(),
+        position: 0,
+        field,
+    };
+    if parser.chars.is_empty() {
+        return Err(String::from("the polynomial is empty"));
+    }
+    let poly = parser.sum()?;
+    match parser.peek() {
+        None => Ok(poly),
+        Some(')') => Err(String::from("a ) has no matching (")),
+        Some(c) => Err(parser.unexpected(c)),
+    }
+}
+
+/// recursive descent over sums of products of powers, a product may leave out the *
+/// before x or (, and / divides by a constant
+struct PolyParser<'a, F> {
+    chars: Vec<char>,
+    position: usize,
+    field: &'a F,
+}
+
+impl<'a, F: CoefficientField> PolyParser<'a, F> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).cloned()
+    }
+
+    fn unexpected(&self, c: char) -> String {
+        format!("unexpected {} at position {}", c, self.position + 1)
+    }
+
+    fn check_degree(degree: usize) -> Result<(), String> {
+        if degree > POLYNOMIAL_DEGREE_LIMIT {
+            Err(format!(
+                "degrees above {} are not supported",
+                POLYNOMIAL_DEGREE_LIMIT
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn sum(&mut self) -> Result<Vec<F::Element>, String> {
+        let mut sum = Vec::new();
+        let mut negative = false;
+        if let Some(sign @ ('+' | '-')) = self.peek() {
+            negative = sign == '-';
+            self.position += 1;
+        }
+        loop {
+            let product = self.product()?;
+            sum = if negative {
+                poly_sub(&sum, &product, self.field)
+            } else {
+                poly_add(&sum, &product, self.field)
+            };
+            match self.peek() {
+                Some(sign @ ('+' | '-')) => {
+                    negative = sign == '-';
+                    self.position += 1;
+                }
+                _ => return Ok(sum),
+            }
+        }
+    }
+
+    fn product(&mut self) -> Result<Vec<F::Element>, String> {
+        let mut product = self.power()?;
+        loop {
+            match self.peek() {
+                Some('*') => {
+                    self.position += 1;
+                    let factor = self.power()?;
+                    product = poly_mul(&product, &factor, self.field);
+                }
+                Some('x') | Some('(') => {
+                    let factor = self.power()?;
+                    product = poly_mul(&product, &factor, self.field);
+                }
+                Some('/') => {
+                    self.position += 1;
+                    let divisor = self.power()?;
+                    if divisor.is_empty() {
+                        return Err(format!("division by zero in {}", self.field.name()));
+                    }
+                    if divisor.len() > 1 {
+                        return Err(String::from("only division by a constant is supported"));
+                    }
+                    let inverse = self.field.inverse(&divisor[0]);
+                    product = poly_scale(&product, &inverse, 0, self.field);
+                }
+                _ => return Ok(product),
+            }
+            Self::check_degree(product.len().saturating_sub(1))?;
+        }
+    }
+
+    fn power(&mut self) -> Result<Vec<F::Element>, String> {
+        let base = self.atom()?;
+        if self.peek() != Some('^') {
+            return Ok(base);
+        }
+        self.position += 1;
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.position += 1;
+        }
+        let exp: String = self.chars[start..self.position].iter().collect();
+        let exp = match exp.parse::<usize>() {
+            Ok(exp) if exp <= POLYNOMIAL_DEGREE_LIMIT => exp,
+            Ok(_) => {
+                return Err(format!(
+                    "exponents above {} are not supported",
+                    POLYNOMIAL_DEGREE_LIMIT
+                ))
+            }
+            Err(_) => return Err(String::from("^ must be followed by a natural number")),
+        };
+        Self::check_degree(base.len().saturating_sub(1) * exp)?;
+        Ok((0..exp).fold(vec![self.field.one()], |power, _| {
+            poly_mul(&power, &base, self.field)
+        }))
+    }
+
+    fn atom(&mut self) -> Result<Vec<F::Element>, String> {
+        match self.peek() {
+            Some('x') => {
+                self.position += 1;
+                Ok(vec![self.field.zero(), self.field.one()])
+            }
+            Some('(') => {
+                self.position += 1;
+                let inner = self.sum()?;
+                if self.peek() != Some(')') {
+                    return Err(String::from("a ( is not closed"));
+                }
+                self.position += 1;
+                Ok(inner)
+            }
+            Some(c) if c.is_ascii_digit() => {
+                let start = self.position;
+                while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                    self.position += 1;
+                }
+                let digits: String = self.chars[start..self.position].iter().collect();
+                let num = parse_big(&digits).unwrap_or_default();
+                Ok(trim(vec![self.field.integer(&num)]))
+            }
+            Some(c) => Err(self.unexpected(c)),
+            None => Err(String::from("the polynomial ends early")),
+        }
+    }
+}
+
+/// a / b with one line per subtracted multiple of b
+pub fn long_division<F: CoefficientField>(
+    a: &[F::Element],
+    b: &[F::Element],
+    field: &F,
+) -> (Vec<F::Element>, Vec<F::Element>, Vec<String>) {
+    let mut steps = Vec::new();
+    let mut quotient = Vec::new();
+    let mut remainder = trim(a.to_vec());
+    let lead_inverse = field.inverse(b.last().unwrap());
+    while remainder.len() >= b.len() {
+        let shift = remainder.len() - b.len();
+        let factor = field.mul(remainder.last().unwrap(), &lead_inverse);
+        let mut term = vec![field.zero(); shift];
+        term.push(factor.clone());
+        let subtracted = poly_scale(b, &factor, shift, field);
+        let next = poly_sub(&remainder, &subtracted, field);
+        steps.push(format!(
+            "({}) / ({}) -> {}, subtract {}, remainder {}",
+            format_poly(&remainder, "x"),
+            format_poly(b, "x"),
+            format_poly(&term, "x"),
+            format_poly(&subtracted, "x"),
+            format_poly(&next, "x")
+        ));
+        quotient = poly_add(&quotient, &term, field);
+        remainder = next;
+    }
+    (quotient, remainder, steps)
+}
+
+/// one row of the extended euclidean algorithm, s * a + t * b = r
+#[derive(Debug, Clone)]
+pub struct EuclidRow<E> {
+    pub r: Vec<E>,
+    /// the quotient that produced the next remainder, empty in the last row
+    pub q: Vec<E>,
+    pub s: Vec<E>,
+    pub t: Vec<E>,
+}
+
+/// the extended euclidean algorithm, the last row holds the monic gcd
+pub fn poly_extended_gcd<F: CoefficientField>(
+    a: &[F::Element],
+    b: &[F::Element],
+    field: &F,
+) -> Vec<EuclidRow<F::Element>> {
+    let mut rows = vec![
+        EuclidRow {
+            r: trim(a.to_vec()),
+            q: Vec::new(),
+            s: vec![field.one()],
+            t: Vec::new(),
+        },
+        EuclidRow {
+            r: trim(b.to_vec()),
+            q: Vec::new(),
+            s: Vec::new(),
+            t: vec![field.one()],
+        },
+    ];
+    while !rows[rows.len() - 1].r.is_empty() {
+        let (previous, last) = (&rows[rows.len() - 2], &rows[rows.len() - 1]);
+        let (q, r) = poly_div_rem(&previous.r, &last.r, field);
+        let s = poly_sub(&previous.s, &poly_mul(&q, &last.s, field), field);
+        let t = poly_sub(&previous.t, &poly_mul(&q, &last.t, field), field);
+        let index = rows.len() - 1;
+        rows[index].q = q;
+        rows.push(EuclidRow {
+            r,
+            q: Vec::new(),
+            s,
+            t,
+        });
+    }
+    // the zero remainder is dropped and the gcd scaled to be monic
+    rows.pop();
+    if let Some(last) = rows.last_mut() {
+        if let Some(lead) = last.r.last() {
+            let inverse = field.inverse(lead);
+            last.r = poly_scale(&last.r, &inverse, 0, field);
+            last.s = poly_scale(&last.s, &inverse, 0, field);
+            last.t = poly_scale(&last.t, &inverse, 0, field);
+            last.q = Vec::new();
+        }
+    }
+    rows
+}
+
+/// Rabin's test: f of degree n is irreducible over Z_p iff x^(p^n) = x mod f
+/// and gcd(x^(p^(n/r)) - x, f) = 1 for every prime r dividing n
+pub fn is_irreducible(poly: &[u64], prime: u64) -> bool {
+    let field = Zp(prime);
+    let poly = trim(poly.to_vec());
+    if poly.len() < 2 {
+        return false;
+    }
+    let degree = poly.len() - 1;
+    let x = poly_div_rem(&[0, 1], &poly, &field).1;
+    let p = BigInt::from(prime);
+    // frobenius[k] = x^(p^k) mod f
+    let mut frobenius = vec![x.clone()];
+    for k in 1..=degree {
+        let next = poly_pow_mod(&frobenius[k - 1], &p, &poly, &field);
+        frobenius.push(next);
+    }
+    if frobenius[degree] != x {
+        return false;
+    }
+    let degree_factors = wheel_factorization(&BigInt::from(degree));
+    prime_powers(&degree_factors).iter().all(|(r, _)| {
+        let k = degree / r.to_usize().unwrap_or(1);
+        poly_gcd(&poly_sub(&frobenius[k], &x, &field), &poly, &field).len() == 1
+    })
+}
+
+/// the digits of index in the given base as coefficients, lowest first
+pub fn poly_from_index(mut index: u64, base: u64, length: usize) -> Vec<u64> {
+    let mut poly = Vec::with_capacity(length);
+    for _ in 0..length {
+        poly.push(index % base);
+        index /= base;
+    }
+    trim(poly)
+}
+
+/// a polynomial split into a constant and monic factors with multiplicities
+#[derive(Debug, Clone)]
+pub struct Factorization<E> {
+    pub unit: E,
+    /// sorted by degree
+    pub factors: Vec<(Vec<E>, u32)>,
+    /// what could not be decided about the factors
+    pub note: Option<String>,
+}
+
+impl<E: Coefficient> Display for Factorization<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts: Vec<String> = Vec::new();
+        if !self.unit.is_one() || self.factors.is_empty() {
+            parts.push(self.unit.to_string());
+        }
+        for (factor, exp) in &self.factors {
+            let factor = format!("({})", format_poly(factor, "x"));
+            parts.push(if *exp == 1 {
+                factor
+            } else {
+                format!("{}^{}", factor, exp)
+            });
+        }
+        write!(f, "{}", parts.join(" * "))
+    }
+}
+
+/// f = prod g_i^i with squarefree, pairwise coprime g_i, f has to be monic
+fn squarefree_factorization(poly: &[u64], prime: u64) -> Vec<(Vec<u64>, u32)> {
+    let field = Zp(prime);
+    let mut factors = Vec::new();
+    let poly_derivative = derivative(poly, &field);
+    let mut rest = poly.to_vec();
+    if !poly_derivative.is_empty() {
+        let mut c = poly_gcd(poly, &poly_derivative, &field);
+        let mut w = poly_div_rem(poly, &c, &field).0;
+        let mut i = 1;
+        while w.len() > 1 {
+            let y = poly_gcd(&w, &c, &field);
+            let z = poly_div_rem(&w, &y, &field).0;
+            if z.len() > 1 {
+                factors.push((z, i));
+            }
+            i += 1;
+            c = poly_div_rem(&c, &y, &field).0;
+            w = y;
+        }
+        rest = c;
+    }
+    // what is left is a polynomial in x^p, and a^p = a for every a in Z_p
+    if rest.len() > 1 {
+        let root: Vec<u64> = rest.iter().step_by(prime as usize).cloned().collect();
+        for (factor, exp) in squarefree_factorization(&root, prime) {
+            factors.push((factor, exp * prime as u32));
+        }
+    }
+    factors
+}
+
+/// splits a squarefree monic f into products of all its irreducible factors of the same degree
+fn distinct_degree_factorization(poly: &[u64], prime: u64) -> Vec<(Vec<u64>, usize)> {
+    let field = Zp(prime);
+    let p = BigInt::from(prime);
+    let mut factors = Vec::new();
+    let mut rest = poly.to_vec();
+    let mut h = poly_div_rem(&[0, 1], &rest, &field).1;
+    let mut d = 1;
+    while rest.len() > 2 * d {
+        h = poly_pow_mod(&h, &p, &rest, &field);
+        let g = poly_gcd(&rest, &poly_sub(&h, &[0, 1], &field), &field);
+        if g.len() > 1 {
+            rest = poly_div_rem(&rest, &g, &field).0;
+            h = poly_div_rem(&h, &rest, &field).1;
+            factors.push((g, d));
+        }
+        d += 1;
+    }
+    if rest.len() > 1 {
+        let degree = rest.len() - 1;
+        factors.push((rest, degree));
+    }
+    factors
+}
+
+/// Cantor-Zassenhaus: splits a product of irreducible factors of degree d into the factors
+fn equal_degree_factorization(
+    poly: &[u64],
+    d: usize,
+    prime: u64,
+    random: &mut XorShift,
+) -> Result<Vec<Vec<u64>>, String> {
+    let field = Zp(prime);
+    let n = poly.len() - 1;
+    if n == d {
+        return Ok(vec![poly.to_vec()]);
+    }
+    let exp: BigInt = (BigInt::from(prime).pow(d as u32) - 1u32) / 2u32;
+    for _ in 0..SPLIT_ATTEMPT_LIMIT {
+        let a = trim(
+            (0..n)
+                .map(|_| random.next_u64() % prime)
+                .collect::<Vec<u64>>(),
+        );
+        if a.len() < 2 {
+            continue;
+        }
+        // over Z_2 the trace a + a^2 + ... + a^(2^(d-1)) takes the place of a^((p^d - 1) / 2)
+        let b = if prime == 2 {
+            let mut power = a.clone();
+            let mut trace = a;
+            for _ in 1..d {
+                power = poly_div_rem(&poly_mul(&power, &power, &field), poly, &field).1;
+                trace = poly_add(&trace, &power, &field);
+            }
+            trace
+        } else {
+            poly_sub(&poly_pow_mod(&a, &exp, poly, &field), &[1], &field)
+        };
+        let g = poly_gcd(poly, &b, &field);
+        if g.len() > 1 && g.len() < poly.len() {
+            let rest = poly_div_rem(poly, &g, &field).0;
+            let mut factors = equal_degree_factorization(&g, d, prime, random)?;
+            factors.extend(equal_degree_factorization(&rest, d, prime, random)?);
+            return Ok(factors);
+        }
+    }
+    Err(format!(
+        "{} could not be split into factors of degree {}",
+        format_poly(poly, "x"),
+        d
+    ))
+}
+
+/// the complete factorization over Z_p: squarefree, distinct degree and equal degree steps
+pub fn factor_mod_prime(poly: &[u64], prime: u64) -> Result<Factorization<u64>, String> {
+    let field = Zp(prime);
+    let poly = trim(poly.to_vec());
+    let unit = match poly.last() {
+        Some(lead) => *lead,
+        None => return Err(String::from("0 has no factorization")),
+    };
+    let monic = make_monic(&poly, &field);
+    // Cantor-Zassenhaus only needs the candidates to be spread out, not unpredictable
+    let mut random = XorShift(0x9e37_79b9_7f4a_7c15);
+    let mut factors = Vec::new();
+    for (squarefree, exp) in squarefree_factorization(&monic, prime) {
+        for (product, d) in distinct_degree_factorization(&squarefree, prime) {
+            for factor in equal_degree_factorization(&product, d, prime, &mut random)? {
+                factors.push((factor, exp));
+            }
+        }
+    }
+    factors.sort_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+    Ok(Factorization {
+        unit,
+        factors,
+        note: None,
+    })
+}
+
+/// the integer multiple of poly with coprime coefficients and a positive leading coefficient
+pub fn primitive_part(poly: &[Rational]) -> Vec<BigInt> {
+    let denominator = poly.iter().fold(BigInt::one(), |lcm, c| lcm.lcm(c.denom()));
+    let integers: Vec<BigInt> = poly
+        .iter()
+        .map(|c| c.numer() * (&denominator / c.denom()))
+        .collect();
+    let content = integers.iter().fold(BigInt::zero(), |gcd, c| gcd.gcd(c));
+    let sign = match integers.last() {
+        Some(lead) if lead.is_negative() => -BigInt::one(),
+        _ => BigInt::one(),
+    };
+    if content.is_zero() {
+        return integers;
+    }
+    integers.iter().map(|c| c / &content * &sign).collect()
+}
+
+/// every rational root p/q with p | a_0 and q | a_n, poly must not have 0 as a root
+pub fn rational_roots(poly: &[Rational]) -> Result<Vec<Rational>, String> {
+    let integers = primitive_part(poly);
+    let (constant, lead) = match (integers.first(), integers.last()) {
+        (Some(constant), Some(lead)) if integers.len() > 1 => (constant.abs(), lead.abs()),
+        _ => return Ok(Vec::new()),
+    };
+    let constant_factors = wheel_factorization(&constant);
+    let lead_factors = wheel_factorization(&lead);
+    if !is_fully_factored(&constant_factors) || !is_fully_factored(&lead_factors) {
+        return Err(String::from(
+            "the rational root test needs a_0 and a_n factored by trial division",
+        ));
+    }
+    let numerators = divisors(&constant_factors);
+    let denominators = divisors(&lead_factors);
+    if numerators.len() * denominators.len() > RATIONAL_ROOT_LIMIT {
+        return Err(format!(
+            "more than {} candidates for rational roots",
+            RATIONAL_ROOT_LIMIT
+        ));
+    }
+    let mut roots: Vec<Rational> = Vec::new();
+    for numer in &numerators {
+        for denom in &denominators {
+            for sign in [BigInt::one(), -BigInt::one()].iter() {
+                let candidate = Rational::new(numer * sign, denom.clone());
+                if !roots.contains(&candidate) && evaluate(poly, &candidate, &Rationals).is_zero() {
+                    roots.push(candidate);
+                }
+            }
+        }
+    }
+    Ok(roots)
+}
+
+/// f(x + shift)
+pub fn taylor_shift(poly: &[Rational], shift: &Rational) -> Vec<Rational> {
+    let field = Rationals;
+    let linear = vec![shift.clone(), field.one()];
+    poly.iter().rev().fold(Vec::new(), |value, c| {
+        poly_add(
+            &poly_mul(&value, &linear, &field),
+            std::slice::from_ref(c),
+            &field,
+        )
+    })
+}
+
+/// a prime p dividing every coefficient but the leading one, with p^2 not dividing the constant
+fn eisenstein_prime(integers: &[BigInt]) -> Option<BigInt> {
+    let (constant, lead) = (integers.first()?, integers.last()?);
+    let constant_factors = wheel_factorization(constant);
+    if !is_fully_factored(&constant_factors) {
+        return None;
+    }
+    prime_powers(&constant_factors)
+        .into_iter()
+        .find(|(prime, exp)| {
+            *exp == 1
+                && !(lead % prime).is_zero()
+                && integers[..integers.len() - 1]
+                    .iter()
+                    .all(|c| (c % prime).is_zero())
+        })
+        .map(|(prime, _)| prime)
+}
+
+/// Some(true) if irreducible over Q, Some(false) if not, None if none of the tests decided
+/// the rational root test, Eisenstein's criterion and reduction mod small primes are tried
+pub fn irreducible_over_q(poly: &[Rational]) -> Result<(Option<bool>, String), String> {
+    let degree = match degree(poly) {
+        Some(degree) if degree > 0 => degree,
+        _ => {
+            return Ok((
+                Some(false),
+                String::from("constants are units, not irreducible"),
+            ))
+        }
+    };
+    if degree == 1 {
+        return Ok((
+            Some(true),
+            String::from("linear polynomials are irreducible"),
+        ));
+    }
+    if poly[0].is_zero() {
+        return Ok((Some(false), String::from("x divides it")));
+    }
+    let roots = rational_roots(poly)?;
+    if let Some(root) = roots.first() {
+        return Ok((
+            Some(false),
+            format!("{} is a rational root, so x - {} divides it", root, root),
+        ));
+    }
+    if degree <= 3 {
+        return Ok((
+            Some(true),
+            String::from("degree 2 or 3 without rational roots"),
+        ));
+    }
+    let integers = primitive_part(poly);
+    let lead = integers[degree].clone();
+    // f(x) is irreducible iff f(x + a) is, shifting often makes Eisenstein applicable
+    for shift in [0, 1, -1, 2, -2].iter() {
+        let shifted = taylor_shift(poly, &Rational::integer(BigInt::from(*shift)));
+        if let Some(prime) = eisenstein_prime(&primitive_part(&shifted)) {
+            let polynomial = match shift {
+                0 => String::from("f(x)"),
+                _ => format!("f(x + {})", shift),
+            };
+            return Ok((
+                Some(true),
+                format!(
+                    "Eisenstein's criterion for {} with p = {}",
+                    polynomial, prime
+                ),
+            ));
+        }
+    }
+    for prime in primes_up_to(100) {
+        let p = BigInt::from(prime);
+        if (&lead % &p).is_zero() {
+            continue;
+        }
+        let reduced: Vec<u64> = integers
+            .iter()
+            .map(|c| c.mod_floor(&p).to_u64().unwrap_or(0))
+            .collect();
+        if is_irreducible(&reduced, prime) {
+            return Ok((
+                Some(true),
+                format!("irreducible mod {} with the same degree", prime),
+            ));
+        }
+    }
+    Ok((
+        None,
+        String::from("no rational roots, but no test could decide irreducibility"),
+    ))
+}
+
+/// splits off every rational root, the remaining factor has none
+pub fn factor_over_q(poly: &[Rational]) -> Result<Factorization<Rational>, String> {
+    let field = Rationals;
+    let unit = match poly.last() {
+        Some(lead) => lead.clone(),
+        None => return Err(String::from("0 has no factorization")),
+    };
+    let mut rest = make_monic(poly, &field);
+    let mut factors: Vec<(Vec<Rational>, u32)> = Vec::new();
+    let mut split_off = |rest: &mut Vec<Rational>, root: Rational| {
+        let linear = vec![field.neg(&root), field.one()];
+        let mut exp = 0;
+        while rest.len() > 1 && evaluate(rest, &root, &field).is_zero() {
+            *rest = poly_div_rem(rest, &linear, &field).0;
+            exp += 1;
+        }
+        factors.push((linear, exp));
+    };
+    if rest.len() > 1 && rest[0].is_zero() {
+        split_off(&mut rest, field.zero());
+    }
+    for root in rational_roots(&rest)? {
+        split_off(&mut rest, root);
+    }
+    let mut note = None;
+    if rest.len() > 1 {
+        let (irreducible, reason) = irreducible_over_q(&rest)?;
+        let rest_text = format_poly(&rest, "x");
+        note = Some(match irreducible {
+            Some(true) => format!("{} is irreducible: {}", rest_text, reason),
+            Some(false) => format!("{} is reducible: {}", rest_text, reason),
+            None => format!("{} may split further: {}", rest_text, reason),
+        });
+        factors.push((rest, 1));
+    }
+    factors.sort_by_key(|(a, _)| a.len());
+    Ok(Factorization {
+        unit,
+        factors,
+        note,
+    })
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PolynomialOperation {
+    Add,
+    Multiply,
+    Divide,
+    Euclid,
+    Irreducible,
+    Factor,
+}
+
+#[derive(Debug, Clone)]
+pub enum IcedPolynomialMessage {
+    EditField(String),
+    EditA(String),
+    EditB(String),
+    Calculate(PolynomialOperation),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IcedPolynomials {
+    field_text: String,
+    field_input: text_input::State,
+    a_text: String,
+    a_input: text_input::State,
+    b_text: String,
+    b_input: text_input::State,
+    add_button: button::State,
+    multiply_button: button::State,
+    divide_button: button::State,
+    euclid_button: button::State,
+    irreducible_button: button::State,
+    factor_button: button::State,
+
+    output: Vec<String>,
+    /// the extended euclidean algorithm with the columns r, q, s, t
+    euclid: Option<Vec<[String; 4]>>,
+    error_message: Option<String>,
+}
+
+impl IcedPolynomials {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, message: IcedPolynomialMessage) {
+        match message {
+            IcedPolynomialMessage::EditField(content) => self.field_text = content,
+            IcedPolynomialMessage::EditA(content) => self.a_text = content,
+            IcedPolynomialMessage::EditB(content) => self.b_text = content,
+            IcedPolynomialMessage::Calculate(operation) => {
+                self.output.clear();
+                self.euclid = None;
+                self.error_message = None;
+                let (a, b) = (&self.a_text, &self.b_text);
+                let result = if self.field_text.trim().is_empty() {
+                    Self::calculate(operation, &Rationals, a, b)
+                } else {
+                    match self.field_text.trim().parse::<u64>() {
+                        Ok(prime) if is_prime_u64(prime) => {
+                            Self::calculate(operation, &Zp(prime), a, b)
+                        }
+                        _ => Err(String::from("p must be a prime, or empty for Q")),
+                    }
+                };
+                match result {
+                    Ok((output, euclid)) => {
+                        self.output = output;
+                        self.euclid = euclid;
+                    }
+                    Err(message) => self.error_message = Some(message),
+                }
+            }
+        }
+    }
+
+    /// the output lines and, for the EEA, its table
+    #[allow(clippy::type_complexity)]
+    fn calculate<F: FactorField>(
+        operation: PolynomialOperation,
+        field: &F,
+        a_text: &str,
+        b_text: &str,
+    ) -> Result<(Vec<String>, Option<Vec<[String; 4]>>), String> {
+        let show = |poly: &[F::Element]| format_poly(poly, "x");
+        let a = parse_poly(a_text, field)?;
+        let b = if b_text.trim().is_empty() {
+            Vec::new()
+        } else {
+            parse_poly(b_text, field)?
+        };
+        let needs_b = matches!(
+            operation,
+            PolynomialOperation::Divide | PolynomialOperation::Euclid
+        );
+        if needs_b && b.is_empty() {
+            return Err(String::from("b must not be 0"));
+        }
+        let mut output = Vec::new();
+        let mut euclid = None;
+        match operation {
+            PolynomialOperation::Add => output.push(format!(
+                "({}) + ({}) = {}",
+                show(&a),
+                show(&b),
+                show(&poly_add(&a, &b, field))
+            )),
+            PolynomialOperation::Multiply => output.push(format!(
+                "({}) * ({}) = {}",
+                show(&a),
+                show(&b),
+                show(&poly_mul(&a, &b, field))
+            )),
+            PolynomialOperation::Divide => {
+                let (quotient, remainder, steps) = long_division(&a, &b, field);
+                output.extend(steps);
+                output.push(format!(
+                    "{} = ({}) * ({}) + {}  over {}",
+                    show(&a),
+                    show(&b),
+                    show(&quotient),
+                    show(&remainder),
+                    field.name()
+                ));
+            }
+            PolynomialOperation::Euclid => {
+                let rows = poly_extended_gcd(&a, &b, field);
+                if let Some(last) = rows.last() {
+                    output.push(format!(
+                        "gcd = {} = ({}) * a + ({}) * b  over {}",
+                        show(&last.r),
+                        show(&last.s),
+                        show(&last.t),
+                        field.name()
+                    ));
+                }
+                euclid = Some(
+                    rows.iter()
+                        .map(|row| [show(&row.r), show(&row.q), show(&row.s), show(&row.t)])
+                        .collect(),
+                );
+            }
+            PolynomialOperation::Irreducible => {
+                let (irreducible, reason) = field.irreducible(&a)?;
+                output.push(format!(
+                    "{} is {} over {}: {}",
+                    show(&a),
+                    match irreducible {
+                        Some(true) => "irreducible",
+                        Some(false) => "reducible",
+                        None => "possibly irreducible",
+                    },
+                    field.name(),
+                    reason
+                ));
+            }
+            PolynomialOperation::Factor => {
+                let factorization = field.factor(&a)?;
+                output.push(format!(
+                    "{} = {}  over {}",
+                    show(&a),
+                    factorization,
+                    field.name()
+                ));
+                output.extend(factorization.note);
+            }
+        }
+        Ok((output, euclid))
+    }
+
+    pub fn view(&mut self) -> Element<'_, IcedPolynomialMessage> {
+        let input = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new("p: "))
+            .push(TextInput::new(
+                &mut self.field_input,
+                "empty for Q",
+                &self.field_text,
+                IcedPolynomialMessage::EditField,
+            ))
+            .push(Text::new("a: "))
+            .push(TextInput::new(
+                &mut self.a_input,
+                "3x^2 + 2x + 1",
+                &self.a_text,
+                IcedPolynomialMessage::EditA,
+            ))
+            .push(Text::new("b: "))
+            .push(TextInput::new(
+                &mut self.b_input,
+                "x - 1",
+                &self.b_text,
+                IcedPolynomialMessage::EditB,
+            ));
+        let operations = Row::new()
+            .spacing(10)
+            .push(
+                Button::new(&mut self.add_button, Text::new("a + b"))
+                    .on_press(IcedPolynomialMessage::Calculate(PolynomialOperation::Add)),
+            )
+            .push(
+                Button::new(&mut self.multiply_button, Text::new("a * b")).on_press(
+                    IcedPolynomialMessage::Calculate(PolynomialOperation::Multiply),
+                ),
+            )
+            .push(
+                Button::new(&mut self.divide_button, Text::new("a / b")).on_press(
+                    IcedPolynomialMessage::Calculate(PolynomialOperation::Divide),
+                ),
+            )
+            .push(
+                Button::new(&mut self.euclid_button, Text::new("EEA")).on_press(
+                    IcedPolynomialMessage::Calculate(PolynomialOperation::Euclid),
+                ),
+            )
+            .push(
+                Button::new(&mut self.irreducible_button, Text::new("Irreducible?")).on_press(
+                    IcedPolynomialMessage::Calculate(PolynomialOperation::Irreducible),
+                ),
+            )
+            .push(
+                Button::new(&mut self.factor_button, Text::new("Factor")).on_press(
+                    IcedPolynomialMessage::Calculate(PolynomialOperation::Factor),
+                ),
+            );
+
+        let output = self
+            .output
+            .iter()
+            .fold(Column::new().spacing(5), |col, line| {
+                col.push(Text::new(line))
+            });
+
+        // laid out like the integer EEA screen, one column per quantity
+        let euclid = match &self.euclid {
+            None => Row::new(),
+            Some(rows) => (0..4).fold(
+                Row::new().spacing(20).align_items(Align::Center),
+                |row, i| {
+                    row.push(rows.iter().fold(
+                        Column::new().push(Text::new(EUCLID_INDICES[i])),
+                        |col, entries| col.push(Text::new(entries[i].clone())),
+                    ))
+                },
+            ),
+        };
+
+        let maybe_error = match &self.error_message {
+            None => Text::new(""),
+            Some(message) => Text::new(message).color([0.921, 0.039, 0.039]),
+        };
+
+        Column::new()
+            .spacing(10)
+            .push(input)
+            .push(operations)
+            .push(output)
+            .push(euclid)
+            .push(maybe_error)
+            .into()
+    }
+}
+
+const EUCLID_INDICES: [&str; 4] = ["r", "q", "s", "t"];
+
+/// the field specific parts of the screen, Z_p factors completely while Q only splits off roots
+pub trait FactorField: CoefficientField {
+    fn irreducible(&self, poly: &[Self::Element]) -> Result<(Option<bool>, String), String>;
+    fn factor(&self, poly: &[Self::Element]) -> Result<Factorization<Self::Element>, String>;
+}
+
+impl FactorField for Zp {
+    fn irreducible(&self, poly: &[u64]) -> Result<(Option<bool>, String), String> {
+        if is_irreducible(poly, self.0) {
+            return Ok((Some(true), String::from("Rabin's test")));
+        }
+        let factorization = factor_mod_prime(poly, self.0)?;
+        Ok((Some(false), format!("it splits as {}", factorization)))
+    }
+
+    fn factor(&self, poly: &[u64]) -> Result<Factorization<u64>, String> {
+        factor_mod_prime(poly, self.0)
+    }
+}
+
+impl FactorField for Rationals {
+    fn irreducible(&self, poly: &[Rational]) -> Result<(Option<bool>, String), String> {
+        irreducible_over_q(poly)
+    }
+
+    fn factor(&self, poly: &[Rational]) -> Result<Factorization<Rational>, String> {
+        factor_over_q(poly)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// unit times the factors with their multiplicities
+    fn expand(factorization: &Factorization<u64>, field: &Zp) -> Vec<u64> {
+        factorization
+            .factors
+            .iter()
+            .flat_map(|(factor, exp)| std::iter::repeat_n(factor, *exp as usize))
+            .fold(vec![factorization.unit], |product, factor| {
+                poly_mul(&product, factor, field)
+            })
+    }
+
+    #[test]
+    fn factor_mod_prime_recovers_the_product() {
+        for (prime, factors) in [
+            (2, vec!["x^2 + x + 1", "x^2 + x + 1", "x^3 + x + 1", "x"]),
+            (
+                3,
+                vec!["2", "x^2 + 1", "x + 1", "x + 1", "x + 1", "x^4 + x + 2"],
+            ),
+            (7, vec!["x^3 + 3", "x^3 + 5", "x + 4", "x + 6"]),
+            (101, vec!["x^2 + 2", "x^2 + 3", "x^2 + 5", "x + 7", "x + 7"]),
+        ] {
+            let field = Zp(prime);
+            let poly = factors.iter().fold(vec![1], |product, factor| {
+                poly_mul(&product, &parse_poly(factor, &field).unwrap(), &field)
+            });
+            let factorization = factor_mod_prime(&poly, prime).unwrap();
+            assert_eq!(expand(&factorization, &field), poly, "over Z_{}", prime);
+            for (factor, _) in &factorization.factors {
+                assert!(
+                    is_irreducible(factor, prime),
+                    "{:?} over Z_{}",
+                    factor,
+                    prime
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn equal_degree_factors_are_split_completely() {
+        // x^8 - x over Z_2 is the product of every irreducible of degree 1 and 3
+        let field = Zp(2);
+        let poly = parse_poly("x^8 + x", &field).unwrap();
+        let factorization = factor_mod_prime(&poly, 2).unwrap();
+        let degrees: Vec<usize> = factorization
+            .factors
+            .iter()
+            .map(|(factor, exp)| {
+                assert_eq!(*exp, 1);
+                factor.len() - 1
+            })
+            .collect();
+        assert_eq!(degrees, vec![1, 1, 3, 3]);
+    }
+
+    #[test]
+    fn parse_poly_multiplies_groups() {
+        let field = Zp(7);
+        assert_eq!(parse_poly("(x+1)(x-1)", &field).unwrap(), vec![6, 0, 1]);
+        assert_eq!(parse_poly("2(x+1)", &field).unwrap(), vec![2, 2]);
+        assert_eq!(
+            parse_poly("(x + 1)^2 (x - 1)", &field).unwrap(),
+            vec![6, 6, 1, 1]
+        );
+        assert_eq!(
+            parse_poly("-(x^2 - 3) * 2x", &field).unwrap(),
+            vec![0, 6, 0, 5]
+        );
+        assert_eq!(parse_poly("3x^2 - x + 1", &field).unwrap(), vec![1, 6, 3]);
+        let rational = |numer: i64, denom: i64| Rational::new(numer.into(), denom.into());
+        assert_eq!(
+            parse_poly("(3/2)x - 1/4", &Rationals).unwrap(),
+            vec![rational(-1, 4), rational(3, 2)]
+        );
+    }
+
+    #[test]
+    fn parse_poly_rejects_malformed_input() {
+        let field = Zp(7);
+        for text in [
+            "",
+            "(x+1",
+            "x+1)",
+            "x^",
+            "x^-1",
+            "x^300",
+            "(x^200)^2",
+            "x/(x+1)",
+            "1/7",
+            "x+",
+            "y",
+        ] {
+            assert!(parse_poly(text, &field).is_err(), "{}", text);
+        }
+    }
+}
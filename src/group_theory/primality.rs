@@ -0,0 +1,360 @@
+use crate::group_theory::number_theory::{
+    is_fully_factored, miller_rabin_round, mul_mod_u64, parse_big, pow_mod_u64, prime_powers,
+    split_power_of_two, wheel_factorization,
+};
+use iced::{button, text_input, Align, Button, Column, Element, Row, Text, TextInput};
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive, Zero};
+
+/// bases that make Miller-Rabin deterministic for every n < 2^64
+pub const DETERMINISTIC_64_BIT_BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BaseVerdict {
+    /// the base proves num composite
+    Witness,
+    /// the base passes although num is composite
+    Liar,
+    /// the base passes and compositeness is not known
+    Passed,
+    /// the base shares a factor with num
+    SharesFactor,
+}
+
+#[derive(Debug, Clone)]
+pub struct MillerRabinStep {
+    pub base: BigInt,
+    /// base^d, base^(2d), ..., base^(2^(s-1) d) mod num
+    pub sequence: Vec<BigInt>,
+    pub verdict: BaseVerdict,
+}
+
+#[derive(Debug, Clone)]
+pub struct FermatStep {
+    pub base: BigInt,
+    /// base^(num - 1) mod num
+    pub power: BigInt,
+    pub verdict: BaseVerdict,
+}
+
+#[derive(Debug, Clone)]
+pub struct PrimalityReport {
+    pub num: BigInt,
+    pub d: BigInt,
+    pub s: u64,
+    pub miller_rabin: Vec<MillerRabinStep>,
+    pub fermat: Vec<FermatStep>,
+    /// Some if num fits into 64 bits
+    pub deterministic: Option<bool>,
+    /// Some if num could be factored, true for composites passing Korselt's criterion
+    pub carmichael: Option<bool>,
+}
+
+/// deterministic Miller-Rabin for 64 bit input
+pub fn is_prime_u64(num: u64) -> bool {
+    if num < 2 {
+        return false;
+    }
+    for p in DETERMINISTIC_64_BIT_BASES.iter() {
+        if num == *p {
+            return true;
+        }
+        if num.is_multiple_of(*p) {
+            return false;
+        }
+    }
+    let s = (num - 1).trailing_zeros();
+    let d = (num - 1) >> s;
+    DETERMINISTIC_64_BIT_BASES.iter().all(|base| {
+        let mut x = pow_mod_u64(*base, d, num);
+        if x == 1 || x == num - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul_mod_u64(x, x, num);
+            if x == num - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// Korselt's criterion: num is squarefree, composite and p - 1 | num - 1 for every prime p | num
+pub fn is_carmichael(num: &BigInt, factors: &[BigInt]) -> bool {
+    let powers = prime_powers(factors);
+    let num_minus_one: BigInt = num - 1u32;
+    powers.len() > 1
+        && powers
+            .iter()
+            .all(|(prime, exp)| *exp == 1 && (&num_minus_one % (prime - BigInt::one())).is_zero())
+}
+
+/// runs Miller-Rabin and Fermat for every base, num has to be odd and > 3
+pub fn primality_report(num: &BigInt, bases: &[BigInt]) -> PrimalityReport {
+    let num_minus_one: BigInt = num - 1u32;
+    let (d, s) = split_power_of_two(&num_minus_one);
+
+    let deterministic = num.to_u64().map(is_prime_u64);
+    let factors = wheel_factorization(num);
+    let carmichael = if is_fully_factored(&factors) {
+        Some(is_carmichael(num, &factors))
+    } else {
+        None
+    };
+
+    let mut miller_rabin: Vec<MillerRabinStep> = bases
+        .iter()
+        .map(|base| {
+            let mut sequence = vec![base.modpow(&d, num)];
+            for _ in 1..s {
+                let next = sequence.last().unwrap().modpow(&BigInt::from(2), num);
+                sequence.push(next);
+            }
+            let verdict = if !base.gcd(num).is_one() {
+                BaseVerdict::SharesFactor
+            } else if miller_rabin_round(num, base, &d, s) {
+                BaseVerdict::Passed
+            } else {
+                BaseVerdict::Witness
+            };
+            MillerRabinStep {
+                base: base.clone(),
+                sequence,
+                verdict,
+            }
+        })
+        .collect();
+
+    let mut fermat: Vec<FermatStep> = bases
+        .iter()
+        .map(|base| {
+            let power = base.modpow(&num_minus_one, num);
+            let verdict = if !base.gcd(num).is_one() {
+                BaseVerdict::SharesFactor
+            } else if power.is_one() {
+                BaseVerdict::Passed
+            } else {
+                BaseVerdict::Witness
+            };
+            FermatStep {
+                base: base.clone(),
+                power,
+                verdict,
+            }
+        })
+        .collect();
+
+    // a single witness anywhere settles compositeness, which turns every pass into a lie
+    let composite = deterministic == Some(false)
+        || factors.len() > 1
+        || miller_rabin
+            .iter()
+            .any(|step| step.verdict != BaseVerdict::Passed)
+        || fermat
+            .iter()
+            .any(|step| step.verdict != BaseVerdict::Passed);
+    if composite {
+        miller_rabin
+            .iter_mut()
+            .filter(|step| step.verdict == BaseVerdict::Passed)
+            .for_each(|step| step.verdict = BaseVerdict::Liar);
+        fermat
+            .iter_mut()
+            .filter(|step| step.verdict == BaseVerdict::Passed)
+            .for_each(|step| step.verdict = BaseVerdict::Liar);
+    }
+
+    PrimalityReport {
+        num: num.clone(),
+        d,
+        s,
+        miller_rabin,
+        fermat,
+        deterministic,
+        carmichael,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum IcedPrimalityMessage {
+    EditBases(String),
+    Test,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IcedPrimality {
+    bases_text: String,
+    bases_input: text_input::State,
+    test_button: button::State,
+    report: Option<PrimalityReport>,
+    message: Option<String>,
+    error_message: Option<String>,
+}
+
+impl IcedPrimality {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, message: IcedPrimalityMessage, num: Option<BigInt>) {
+        match message {
+            IcedPrimalityMessage::EditBases(content) => {
+                self.bases_text = content;
+            }
+            IcedPrimalityMessage::Test => {
+                self.error_message = None;
+                self.message = None;
+                self.report = None;
+                let num = match num {
+                    Some(num) => num,
+                    None => {
+                        self.error_message = Some(String::from("enter a modulo to test first"));
+                        return;
+                    }
+                };
+                if num < BigInt::from(5) || num.is_even() {
+                    self.message = Some(match num.to_u64() {
+                        Some(2) | Some(3) => format!("{} is prime", num),
+                        _ => format!("{} is neither an odd number > 3 nor prime", num),
+                    });
+                    return;
+                }
+                let bases: Option<Vec<BigInt>> = if self.bases_text.trim().is_empty() {
+                    Some(
+                        DETERMINISTIC_64_BIT_BASES
+                            .iter()
+                            .map(|base| BigInt::from(*base))
+                            .filter(|base| base < &num)
+                            .collect(),
+                    )
+                } else {
+                    self.bases_text.split(',').map(parse_big).collect()
+                };
+                match bases {
+                    Some(bases)
+                        if bases
+                            .iter()
+                            .all(|base| base > &BigInt::one() && base < &num) =>
+                    {
+                        self.report = Some(primality_report(&num, &bases));
+                    }
+                    _ => {
+                        self.error_message =
+                            Some(String::from("bases must be numbers with 1 < base < n"))
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn view(&mut self) -> Element<'_, IcedPrimalityMessage> {
+        let input = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new("Bases: "))
+            .push(
+                TextInput::new(
+                    &mut self.bases_input,
+                    "2, 3, 5, ... (empty for the 64 bit bases)",
+                    &self.bases_text,
+                    IcedPrimalityMessage::EditBases,
+                )
+                .on_submit(IcedPrimalityMessage::Test),
+            )
+            .push(
+                Button::new(&mut self.test_button, Text::new("Test Primality"))
+                    .on_press(IcedPrimalityMessage::Test),
+            );
+
+        let mut content = Column::new().spacing(5).push(input);
+        if let Some(message) = &self.message {
+            content = content.push(Text::new(message));
+        }
+        if let Some(report) = &self.report {
+            content = content.push(Self::report_view(report));
+        }
+        if let Some(message) = &self.error_message {
+            content = content.push(Text::new(message).color([0.921, 0.039, 0.039]));
+        }
+        content.into()
+    }
+
+    fn report_view<'a>(report: &PrimalityReport) -> Column<'a, IcedPrimalityMessage> {
+        let deterministic = match report.deterministic {
+            Some(true) => String::from("Deterministic (64 bit): prime"),
+            Some(false) => String::from("Deterministic (64 bit): composite"),
+            None => String::from("Deterministic (64 bit): input too large"),
+        };
+        let carmichael = match report.carmichael {
+            Some(true) => "Carmichael number: yes, every coprime base passes Fermat",
+            Some(false) => "Carmichael number: no",
+            None => "Carmichael number: unknown, could not factor",
+        };
+
+        let miller_rabin = report.miller_rabin.iter().fold(
+            Column::new().push(Text::new(format!(
+                "Miller-Rabin: n - 1 = {} * 2^{}",
+                report.d, report.s
+            ))),
+            |col, step| {
+                let sequence = step
+                    .sequence
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                col.push(Text::new(format!(
+                    "a = {}: {} -> {}",
+                    step.base,
+                    sequence,
+                    verdict_text(step.verdict)
+                )))
+            },
+        );
+
+        let fermat = report.fermat.iter().fold(
+            Column::new().push(Text::new("Fermat: a^(n - 1) mod n")),
+            |col, step| {
+                col.push(Text::new(format!(
+                    "a = {}: {} -> {}",
+                    step.base,
+                    step.power,
+                    verdict_text(step.verdict)
+                )))
+            },
+        );
+
+        let summary = format!(
+            "{} Miller-Rabin liars, {} Fermat liars",
+            report
+                .miller_rabin
+                .iter()
+                .filter(|step| step.verdict == BaseVerdict::Liar)
+                .count(),
+            report
+                .fermat
+                .iter()
+                .filter(|step| step.verdict == BaseVerdict::Liar)
+                .count(),
+        );
+
+        Column::new()
+            .spacing(10)
+            .push(Text::new(deterministic))
+            .push(Text::new(carmichael))
+            .push(Text::new(summary))
+            .push(miller_rabin)
+            .push(fermat)
+    }
+}
+
+fn verdict_text(verdict: BaseVerdict) -> &'static str {
+    match verdict {
+        BaseVerdict::Witness => "witness",
+        BaseVerdict::Liar => "liar",
+        BaseVerdict::Passed => "probably prime",
+        BaseVerdict::SharesFactor => "shares a factor with n",
+    }
+}
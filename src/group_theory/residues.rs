@@ -0,0 +1,525 @@
+use crate::group_theory::finite_group::GroupType;
+use crate::group_theory::number_theory::{
+    crt, group, is_fully_factored, is_prime, mod_inverse, parse_big, prime_powers, residues,
+    split_power_of_two, wheel_factorization, ENUMERATION_LIMIT,
+};
+use iced::{button, text_input, Align, Button, Column, Element, Row, Text, TextInput};
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive, Zero};
+use std::collections::BTreeSet;
+
+/// at most this many square roots are combined through the chinese remainder theorem
+pub const ROOT_LIMIT: usize = 256;
+
+/// one line of a Jacobi symbol evaluation, sign * (a / n) with the rule that led there
+#[derive(Debug, Clone)]
+pub struct JacobiStep {
+    pub sign: i8,
+    pub a: BigInt,
+    pub n: BigInt,
+    pub reason: String,
+}
+
+/// the Jacobi symbol (a / n) for odd n > 0 with every reduction and reciprocity step
+pub fn jacobi(a: &BigInt, n: &BigInt) -> (i8, Vec<JacobiStep>) {
+    let mut sign = 1i8;
+    let mut a = a.mod_floor(n);
+    let mut n = n.clone();
+    let mut steps = vec![JacobiStep {
+        sign,
+        a: a.clone(),
+        n: n.clone(),
+        reason: String::from("reduce a mod n"),
+    }];
+    while !a.is_zero() && !a.is_one() {
+        let twos = a.trailing_zeros().unwrap_or(0);
+        if twos > 0 {
+            a >>= twos;
+            let n_mod_8 = (&n % 8u32).to_u32().unwrap_or(0);
+            let two = if n_mod_8 == 3 || n_mod_8 == 5 { -1 } else { 1 };
+            if twos % 2 == 1 {
+                sign *= two;
+            }
+            steps.push(JacobiStep {
+                sign,
+                a: a.clone(),
+                n: n.clone(),
+                reason: format!(
+                    "(2 / {}) = {} since {} = {} mod 8, taken {} times",
+                    n, two, n, n_mod_8, twos
+                ),
+            });
+            if a.is_one() {
+                break;
+            }
+        }
+        let flips = (&a % 4u32) == BigInt::from(3) && (&n % 4u32) == BigInt::from(3);
+        if flips {
+            sign = -sign;
+        }
+        std::mem::swap(&mut a, &mut n);
+        steps.push(JacobiStep {
+            sign,
+            a: a.clone(),
+            n: n.clone(),
+            reason: if flips {
+                String::from("reciprocity, both are 3 mod 4 so the sign flips")
+            } else {
+                String::from("reciprocity, one of them is 1 mod 4")
+            },
+        });
+        a = a.mod_floor(&n);
+        steps.push(JacobiStep {
+            sign,
+            a: a.clone(),
+            n: n.clone(),
+            reason: String::from("reduce a mod n"),
+        });
+    }
+    let symbol = if n.is_one() || a.is_one() { sign } else { 0 };
+    (symbol, steps)
+}
+
+/// Tonelli-Shanks for an odd prime with its intermediate values, None for non-residues
+pub fn tonelli_shanks(num: &BigInt, prime: &BigInt) -> Option<(BigInt, Vec<String>)> {
+    let num = num.mod_floor(prime);
+    let prime_minus_one: BigInt = prime - 1u32;
+    let half = &prime_minus_one / 2u32;
+    if num.is_zero() {
+        return Some((num, vec![String::from("a = 0, so x = 0")]));
+    }
+    if !num.modpow(&half, prime).is_one() {
+        return None;
+    }
+    let (q, s) = split_power_of_two(&prime_minus_one);
+    let mut z = BigInt::from(2);
+    while num_is_residue(&z, prime, &half) {
+        z += 1u32;
+    }
+    let mut steps = vec![format!(
+        "p - 1 = {} * 2^{}, z = {} is a non-residue",
+        q, s, z
+    )];
+    let mut m = s;
+    let mut c = z.modpow(&q, prime);
+    let mut t = num.modpow(&q, prime);
+    let mut r = num.modpow(&((&q + 1u32) / 2u32), prime);
+    steps.push(format!("M = {}, c = {}, t = {}, R = {}", m, c, t, r));
+    while !t.is_one() {
+        let mut i = 0;
+        let mut t_power = t.clone();
+        while !t_power.is_one() {
+            t_power = &t_power * &t_power % prime;
+            i += 1;
+        }
+        let b = c.modpow(&(BigInt::one() << (m - i - 1)), prime);
+        m = i;
+        c = &b * &b % prime;
+        t = t * &c % prime;
+        r = r * &b % prime;
+        steps.push(format!(
+            "t has order 2^{}, b = {}: M = {}, c = {}, t = {}, R = {}",
+            i, b, m, c, t, r
+        ));
+    }
+    Some((r, steps))
+}
+
+fn num_is_residue(num: &BigInt, prime: &BigInt, half: &BigInt) -> bool {
+    num.modpow(half, prime).is_one()
+}
+
+/// every x mod p^k with x^2 = a, lifting a root mod p with Hensel's lemma where possible
+fn roots_mod_prime_power(
+    a: &BigInt,
+    prime: &BigInt,
+    exp: u32,
+    steps: &mut Vec<String>,
+) -> Result<Vec<BigInt>, String> {
+    let prime_power = prime.pow(exp);
+    let a = a.mod_floor(&prime_power);
+    let two = BigInt::from(2);
+    let mut roots: Vec<BigInt> = if prime != &two && !(&a % prime).is_zero() {
+        let (root, ts_steps) = match tonelli_shanks(&a, prime) {
+            Some(result) => result,
+            None => {
+                steps.push(format!("{} is no square mod {}", a, prime));
+                return Ok(Vec::new());
+            }
+        };
+        steps.push(format!("Tonelli-Shanks mod {}:", prime));
+        steps.extend(ts_steps.into_iter().map(|step| format!("    {}", step)));
+        let mut x = root;
+        let mut modulo = prime.clone();
+        for _ in 1..exp {
+            modulo *= prime;
+            let inverse = mod_inverse(&(&x * 2u32), &modulo).unwrap_or_else(BigInt::one);
+            x = (&x - (&x * &x - &a) * inverse).mod_floor(&modulo);
+            steps.push(format!("Hensel lift: x = {} mod {}", x, modulo));
+        }
+        vec![x.clone(), (&prime_power - &x).mod_floor(&prime_power)]
+    } else if prime == &two && a.is_odd() {
+        // odd squares mod 2^k are 1 mod 8, their roots come in fours
+        let a_mod_8 = (&a % 8u32).to_u32().unwrap_or(0);
+        match exp {
+            1 => vec![BigInt::one()],
+            2 if a_mod_8 % 4 == 1 => vec![BigInt::one(), BigInt::from(3)],
+            _ if exp >= 3 && a_mod_8 == 1 => {
+                let mut x = BigInt::one();
+                for j in 3..exp {
+                    if !((&x * &x - &a) % (BigInt::one() << (j + 1))).is_zero() {
+                        x += BigInt::one() << (j - 1);
+                    }
+                }
+                steps.push(format!("lifted x = {} mod {}", x, prime_power));
+                let half = BigInt::one() << (exp - 1);
+                vec![
+                    x.clone(),
+                    (&prime_power - &x).mod_floor(&prime_power),
+                    (&x + &half).mod_floor(&prime_power),
+                    (&half - &x).mod_floor(&prime_power),
+                ]
+            }
+            _ => Vec::new(),
+        }
+    } else if prime_power <= BigInt::from(ENUMERATION_LIMIT) {
+        steps.push(format!(
+            "{} shares the factor {} with the modulus, searching all residues mod {}",
+            a, prime, prime_power
+        ));
+        residues(&prime_power)
+            .filter(|x| (x * x - &a).mod_floor(&prime_power).is_zero())
+            .collect()
+    } else {
+        return Err(format!(
+            "{} shares the factor {} with the modulus and {} is too large to search",
+            a, prime, prime_power
+        ));
+    };
+    roots.sort();
+    roots.dedup();
+    let list: Vec<String> = roots.iter().map(|x| x.to_string()).collect();
+    steps.push(format!(
+        "roots mod {}: {{{}}}",
+        prime_power,
+        list.join(", ")
+    ));
+    Ok(roots)
+}
+
+/// every x mod n with x^2 = a, up to ROOT_LIMIT, combined from the prime powers of n
+pub fn sqrt_mod(a: &BigInt, n: &BigInt) -> Result<(Vec<BigInt>, Vec<String>), String> {
+    let factors = wheel_factorization(n);
+    if !is_fully_factored(&factors) {
+        return Err(String::from("n could not be factored by trial division"));
+    }
+    let mut steps = Vec::new();
+    let mut per_prime: Vec<(Vec<BigInt>, BigInt)> = Vec::new();
+    for (prime, exp) in prime_powers(&factors) {
+        let roots = roots_mod_prime_power(a, &prime, exp, &mut steps)?;
+        if roots.is_empty() {
+            steps.push(format!("no root mod {}, so none mod {}", prime.pow(exp), n));
+            return Ok((Vec::new(), steps));
+        }
+        per_prime.push((roots, prime.pow(exp)));
+    }
+    // every choice of one root per prime power gives a root mod n
+    let mut combinations: Vec<Vec<(BigInt, BigInt)>> = vec![Vec::new()];
+    for (roots, modulo) in &per_prime {
+        combinations = combinations
+            .into_iter()
+            .flat_map(|combination| {
+                roots.iter().map(move |root| {
+                    let mut next = combination.clone();
+                    next.push((root.clone(), modulo.clone()));
+                    next
+                })
+            })
+            .take(ROOT_LIMIT)
+            .collect();
+    }
+    if per_prime.len() > 1 {
+        steps.push(format!(
+            "CRT combines one root per prime power into {} roots mod {}",
+            combinations.len(),
+            n
+        ));
+    }
+    let mut roots: Vec<BigInt> = combinations
+        .iter()
+        .filter_map(|combination| crt(combination))
+        .collect();
+    roots.sort();
+    roots.dedup();
+    Ok((roots, steps))
+}
+
+#[derive(Debug, Clone)]
+pub enum IcedResidueMessage {
+    EditA(String),
+    EditN(String),
+    Residues,
+    Symbol,
+    SquareRoot,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IcedResidues {
+    a_text: String,
+    a_input: text_input::State,
+    n_text: String,
+    n_input: text_input::State,
+    residues_button: button::State,
+    symbol_button: button::State,
+    root_button: button::State,
+
+    output: Vec<String>,
+    error_message: Option<String>,
+}
+
+impl IcedResidues {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, message: IcedResidueMessage) {
+        match message {
+            IcedResidueMessage::EditA(content) => {
+                self.a_text = content;
+            }
+            IcedResidueMessage::EditN(content) => {
+                self.n_text = content;
+            }
+            IcedResidueMessage::Residues => {
+                self.reset();
+                if let Some(n) = self.modulus() {
+                    self.residues(&n);
+                }
+            }
+            IcedResidueMessage::Symbol => {
+                self.reset();
+                if let (Some(n), Some(a)) = (self.modulus(), self.value()) {
+                    self.symbol(&a, &n);
+                }
+            }
+            IcedResidueMessage::SquareRoot => {
+                self.reset();
+                if let (Some(n), Some(a)) = (self.modulus(), self.value()) {
+                    match sqrt_mod(&a, &n) {
+                        Ok((roots, steps)) => {
+                            self.output = steps;
+                            let roots: Vec<String> = roots.iter().map(|x| x.to_string()).collect();
+                            self.output.push(if roots.is_empty() {
+                                format!("x^2 = {} mod {} has no solution", a, n)
+                            } else {
+                                format!("x^2 = {} mod {}: x in {{{}}}", a, n, roots.join(", "))
+                            });
+                        }
+                        Err(message) => self.error_message = Some(message),
+                    }
+                }
+            }
+        }
+    }
+
+    fn reset(&mut self) {
+        self.output.clear();
+        self.error_message = None;
+    }
+
+    fn modulus(&mut self) -> Option<BigInt> {
+        match parse_big(&self.n_text) {
+            Some(n) if n > BigInt::one() => Some(n),
+            _ => {
+                self.error_message = Some(String::from("n must be a number > 1"));
+                None
+            }
+        }
+    }
+
+    fn value(&mut self) -> Option<BigInt> {
+        let a = parse_big(&self.a_text);
+        if a.is_none() {
+            self.error_message = Some(String::from("a must be a number"));
+        }
+        a
+    }
+
+    /// squares of the units, they form a subgroup of Z_n^*
+    fn residues(&mut self, n: &BigInt) {
+        if n > &BigInt::from(ENUMERATION_LIMIT) {
+            self.error_message = Some(format!(
+                "residues are only listed for n <= {}",
+                ENUMERATION_LIMIT
+            ));
+            return;
+        }
+        let units = group(n, GroupType::MultiplicativeStar);
+        let squares: BTreeSet<BigInt> = units.iter().map(|x| x * x % n).collect();
+        let non_residues: Vec<String> = units
+            .iter()
+            .filter(|x| !squares.contains(x))
+            .map(|x| x.to_string())
+            .collect();
+        let squares: Vec<String> = squares.iter().map(|x| x.to_string()).collect();
+        self.output.push(format!(
+            "{} quadratic residues in Z_{}^* (order {}): {}",
+            squares.len(),
+            n,
+            units.len(),
+            squares.join(", ")
+        ));
+        self.output.push(format!(
+            "{} non-residues: {}",
+            non_residues.len(),
+            non_residues.join(", ")
+        ));
+    }
+
+    fn symbol(&mut self, a: &BigInt, n: &BigInt) {
+        if n.is_even() {
+            self.error_message = Some(String::from("the Jacobi symbol needs an odd n"));
+            return;
+        }
+        let prime = is_prime(n);
+        let (symbol, steps) = jacobi(a, n);
+        let name = if prime { "Legendre" } else { "Jacobi" };
+        self.output
+            .push(format!("{} symbol ({} / {}):", name, a, n));
+        for step in steps {
+            self.output.push(format!(
+                "= {}({} / {})    {}",
+                if step.sign < 0 { "-" } else { "" },
+                step.a,
+                step.n,
+                step.reason
+            ));
+        }
+        self.output.push(format!("({} / {}) = {}", a, n, symbol));
+        if prime {
+            let euler = a.modpow(&((n - 1u32) / 2u32), n);
+            self.output.push(format!(
+                "Euler's criterion: {}^(({} - 1) / 2) = {} mod {}",
+                a.mod_floor(n),
+                n,
+                euler,
+                n
+            ));
+        } else if symbol == 1 {
+            self.output.push(String::from(
+                "for composite n, (a / n) = 1 does not make a a square, try the square roots",
+            ));
+        }
+    }
+
+    pub fn view(&mut self) -> Element<'_, IcedResidueMessage> {
+        let input = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new("a: "))
+            .push(TextInput::new(
+                &mut self.a_input,
+                "a",
+                &self.a_text,
+                IcedResidueMessage::EditA,
+            ))
+            .push(Text::new("n: "))
+            .push(TextInput::new(
+                &mut self.n_input,
+                "n",
+                &self.n_text,
+                IcedResidueMessage::EditN,
+            ))
+            .push(
+                Button::new(&mut self.residues_button, Text::new("Residues"))
+                    .on_press(IcedResidueMessage::Residues),
+            )
+            .push(
+                Button::new(&mut self.symbol_button, Text::new("Symbol (a / n)"))
+                    .on_press(IcedResidueMessage::Symbol),
+            )
+            .push(
+                Button::new(&mut self.root_button, Text::new("Square Roots"))
+                    .on_press(IcedResidueMessage::SquareRoot),
+            );
+        let mut content = self
+            .output
+            .iter()
+            .fold(Column::new().spacing(10).push(input), |col, line| {
+                col.push(Text::new(line))
+            });
+        if let Some(message) = &self.error_message {
+            content = content.push(Text::new(message).color([0.921, 0.039, 0.039]));
+        }
+        content.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(num: u64) -> BigInt {
+        BigInt::from(num)
+    }
+
+    fn brute_force_roots(a: u64, n: u64) -> Vec<BigInt> {
+        (0..n).filter(|x| x * x % n == a % n).map(big).collect()
+    }
+
+    #[test]
+    fn sqrt_mod_finds_every_root() {
+        // odd primes, their powers with and without p | a, powers of two and composites
+        for n in [
+            2, 4, 8, 16, 32, 7, 13, 17, 41, 9, 27, 25, 49, 15, 24, 45, 72, 105, 200,
+        ] {
+            for a in 0..n {
+                let (roots, _) = sqrt_mod(&big(a), &big(n)).unwrap();
+                assert_eq!(roots, brute_force_roots(a, n), "x^2 = {} mod {}", a, n);
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt_mod_round_trips_for_large_moduli() {
+        // 10^9 + 9 = 1 mod 8 makes Tonelli-Shanks loop, the second modulus needs Hensel lifting
+        for n in [big(1_000_000_009), big(1_000_000_009) * big(13 * 13 * 13)] {
+            for x in [2u64, 12_345, 999_999_999] {
+                let a = big(x) * big(x) % &n;
+                let (roots, _) = sqrt_mod(&a, &n).unwrap();
+                assert!(roots.contains(&(big(x) % &n)));
+                assert!(roots.iter().all(|r| (r * r - &a).mod_floor(&n).is_zero()));
+            }
+        }
+    }
+
+    #[test]
+    fn tonelli_shanks_finds_roots_exactly_for_residues() {
+        let prime = big(1_000_000_009);
+        for a in 1..50 {
+            let root = tonelli_shanks(&big(a), &prime).map(|(root, _)| root);
+            match jacobi(&big(a), &prime).0 {
+                1 => assert_eq!(root.unwrap().modpow(&big(2), &prime), big(a)),
+                _ => assert!(root.is_none()),
+            }
+        }
+    }
+
+    #[test]
+    fn jacobi_agrees_with_euler_for_primes() {
+        for p in [3u64, 5, 7, 11, 101, 1_009] {
+            for a in 0..p.min(200) {
+                let euler = big(a).modpow(&big((p - 1) / 2), &big(p));
+                let expected = if euler.is_zero() {
+                    0
+                } else if euler.is_one() {
+                    1
+                } else {
+                    -1
+                };
+                assert_eq!(jacobi(&big(a), &big(p)).0, expected, "({} / {})", a, p);
+            }
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use crate::group_theory::number_theory::{
+    crt, divisor_count, divisors, prime_powers, residues, totient, ENUMERATION_LIMIT,
+};
+use iced::{Column, Text};
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+
+/// rings with more ideals than this only get them counted
+pub const IDEAL_LIST_LIMIT: u64 = 100;
+/// idempotents come from the subsets of the prime powers, so their number is 2^k
+const IDEMPOTENT_PRIME_LIMIT: usize = 10;
+
+/// the ideal dZ_n for a divisor d of n
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ideal {
+    pub generator: BigInt,
+    pub order: BigInt,
+    /// dZ_n is prime, and in a finite ring then also maximal, iff d is prime
+    pub prime: bool,
+}
+
+/// the ring structure of Z_n from a full prime factorization of n
+#[derive(Debug, Clone)]
+pub struct RingReport {
+    pub modulo: BigInt,
+    pub unit_count: BigInt,
+    pub zero_divisor_count: BigInt,
+    pub nilpotent_count: BigInt,
+    pub idempotent_count: BigInt,
+    /// the product of the distinct primes, a is nilpotent iff rad(n) | a
+    pub radical: BigInt,
+    /// the element lists are empty above ENUMERATION_LIMIT
+    pub units: Vec<BigInt>,
+    pub zero_divisors: Vec<BigInt>,
+    pub nilpotents: Vec<BigInt>,
+    /// found through the chinese remainder theorem, empty with more than 10 distinct primes
+    pub idempotents: Vec<BigInt>,
+    /// empty above IDEAL_LIST_LIMIT
+    pub ideals: Vec<Ideal>,
+}
+
+impl RingReport {
+    pub fn new(modulo: &BigInt, prime_factors: &[BigInt]) -> Self {
+        let powers = prime_powers(prime_factors);
+        let radical: BigInt = powers.iter().map(|(prime, _)| prime).product();
+        let unit_count = totient(prime_factors);
+        let nilpotent_count = modulo / &radical;
+        // every element but 0 that is not a unit
+        let zero_divisor_count = if modulo.is_one() {
+            BigInt::zero()
+        } else {
+            modulo - &unit_count - 1u32
+        };
+
+        let (mut units, mut zero_divisors, mut nilpotents) = (Vec::new(), Vec::new(), Vec::new());
+        if modulo <= &BigInt::from(ENUMERATION_LIMIT) {
+            for a in residues(modulo) {
+                if a.gcd(modulo).is_one() {
+                    units.push(a.clone());
+                } else if !a.is_zero() {
+                    zero_divisors.push(a.clone());
+                }
+                if (&a % &radical).is_zero() {
+                    nilpotents.push(a);
+                }
+            }
+        }
+
+        // e = 1 mod the chosen prime powers and 0 mod the rest
+        let mut idempotents = Vec::new();
+        if powers.len() <= IDEMPOTENT_PRIME_LIMIT {
+            for subset in 0..1u32 << powers.len() {
+                let congruences: Vec<(BigInt, BigInt)> = powers
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (prime, exp))| {
+                        let residue = if subset >> i & 1 == 1 {
+                            BigInt::one()
+                        } else {
+                            BigInt::zero()
+                        };
+                        (residue, prime.pow(*exp))
+                    })
+                    .collect();
+                idempotents.extend(crt(&congruences).map(|e| e.mod_floor(modulo)));
+            }
+            idempotents.sort();
+            idempotents.dedup();
+        }
+
+        let ideals = if divisor_count(prime_factors) <= BigInt::from(IDEAL_LIST_LIMIT) {
+            let primes: Vec<&BigInt> = powers.iter().map(|(prime, _)| prime).collect();
+            divisors(prime_factors)
+                .into_iter()
+                .map(|d| Ideal {
+                    order: modulo / &d,
+                    prime: primes.contains(&&d),
+                    generator: d,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            modulo: modulo.clone(),
+            unit_count,
+            zero_divisor_count,
+            nilpotent_count,
+            idempotent_count: BigInt::from(2).pow(powers.len() as u32),
+            radical,
+            units,
+            zero_divisors,
+            nilpotents,
+            idempotents,
+            ideals,
+        }
+    }
+
+    /// Z_n is a field iff n is prime, and a finite integral domain is already a field
+    pub fn is_field(&self) -> bool {
+        self.radical == self.modulo && self.idempotent_count == BigInt::from(2)
+    }
+
+    pub fn view<'a, M: 'a>(&self) -> Column<'a, M> {
+        let n = &self.modulo;
+        let list = |elements: &[BigInt], count: &BigInt| -> String {
+            if elements.is_empty() && !count.is_zero() {
+                String::from("not listed")
+            } else {
+                let elements: Vec<String> = elements.iter().map(|e| e.to_string()).collect();
+                elements.join(", ")
+            }
+        };
+        let structure = if self.is_field() {
+            format!("Z_{} is a field and so an integral domain", n)
+        } else if n.is_one() {
+            String::from("Z_1 is the zero ring, neither a field nor an integral domain")
+        } else {
+            format!(
+                "Z_{} is neither a field nor an integral domain, it has zero divisors",
+                n
+            )
+        };
+        let ideal_count = if self.ideals.is_empty() {
+            format!("more than {}, not listed", IDEAL_LIST_LIMIT)
+        } else {
+            self.ideals.len().to_string()
+        };
+        let ideals = self.ideals.iter().fold(
+            Column::new().push(Text::new(format!(
+                "Ideals ({}), one dZ_{} for every divisor d:",
+                ideal_count, n
+            ))),
+            |col, ideal| {
+                let kind = if ideal.prime {
+                    ", prime and maximal"
+                } else {
+                    ""
+                };
+                col.push(Text::new(format!(
+                    "    ({}) of order {}, Z_{}/({}) = Z_{}{}",
+                    ideal.generator, ideal.order, n, ideal.generator, ideal.generator, kind
+                )))
+            },
+        );
+        Column::new()
+            .spacing(5)
+            .push(Text::new(structure))
+            .push(Text::new(format!(
+                "Units ({}): {}",
+                self.unit_count,
+                list(&self.units, &self.unit_count)
+            )))
+            .push(Text::new(format!(
+                "Zero divisors ({}): {}",
+                self.zero_divisor_count,
+                list(&self.zero_divisors, &self.zero_divisor_count)
+            )))
+            .push(Text::new(format!(
+                "Nilpotents ({}, the multiples of rad(n) = {}): {}",
+                self.nilpotent_count,
+                self.radical,
+                list(&self.nilpotents, &self.nilpotent_count)
+            )))
+            .push(Text::new(format!(
+                "Idempotents ({}, one per set of prime powers): {}",
+                self.idempotent_count,
+                list(&self.idempotents, &self.idempotent_count)
+            )))
+            .push(ideals)
+    }
+}
@@ -0,0 +1,693 @@
+use crate::group_theory::number_theory::{
+    extended_euclidean_as_dmatrix, is_prime, mod_inverse, parse_big, XorShift,
+};
+use crate::group_theory::EEA_INDICES;
+use glucose::DMatrix;
+use iced::{button, text_input, Align, Button, Column, Command, Element, Row, Text, TextInput};
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+/// primes are generated with at most this many bits
+pub const RSA_BIT_LIMIT: u64 = 1024;
+const DEFAULT_EXPONENT: u32 = 65_537;
+const DEFAULT_BITS: u64 = 64;
+/// generate gives up after this many primes that are not coprime to e or repeat p
+const PRIME_DRAW_LIMIT: usize = 1000;
+
+/// p, q, e and everything derived from them
+#[derive(Debug, Clone)]
+pub struct RsaKey {
+    pub p: BigInt,
+    pub q: BigInt,
+    pub n: BigInt,
+    pub phi: BigInt,
+    pub e: BigInt,
+    pub d: BigInt,
+    /// the EEA of e and phi(n), its first s is d up to a multiple of phi(n)
+    pub eea: DMatrix<BigInt>,
+}
+
+impl RsaKey {
+    pub fn new(p: BigInt, q: BigInt, e: BigInt) -> Result<Self, String> {
+        if !is_prime(&p) || !is_prime(&q) {
+            return Err(String::from("p and q must be primes"));
+        }
+        if p == q {
+            return Err(String::from(
+                "p and q must differ, n = p^2 is factored by a square root",
+            ));
+        }
+        let n = &p * &q;
+        let phi: BigInt = (&p - 1u32) * (&q - 1u32);
+        if e <= BigInt::one() || e >= phi {
+            return Err(format!("e must lie between 1 and phi(n) = {}", phi));
+        }
+        if !e.gcd(&phi).is_one() {
+            return Err(format!(
+                "gcd(e, phi(n)) = {}, e has no inverse",
+                e.gcd(&phi)
+            ));
+        }
+        let eea = extended_euclidean_as_dmatrix(&e, &phi);
+        let d = eea.data[3][0].mod_floor(&phi);
+        Ok(Self {
+            p,
+            q,
+            n,
+            phi,
+            e,
+            d,
+            eea,
+        })
+    }
+
+    /// two random primes with the given number of bits each
+    pub async fn generate(bits: u64, e: BigInt, seed: u64) -> Result<Self, String> {
+        // p - 1 is even, so an even e never becomes coprime to it
+        if e < BigInt::from(3) || e.is_even() {
+            return Err(String::from("e must be odd and at least 3"));
+        }
+        let mut random = XorShift(seed);
+        // e has to be coprime to p - 1 and q - 1, so primes failing that are drawn again
+        let mut primes = std::iter::repeat_with(|| random.prime(bits))
+            .take(PRIME_DRAW_LIMIT)
+            .filter(|prime| (prime - 1u32).gcd(&e).is_one());
+        let no_primes = || {
+            format!(
+                "no {} bit primes p with gcd(p - 1, e) = 1 after {} tries",
+                bits, PRIME_DRAW_LIMIT
+            )
+        };
+        let p = primes.next().ok_or_else(no_primes)?;
+        let q = primes.find(|q| *q != p).ok_or_else(no_primes)?;
+        Self::new(p, q, e)
+    }
+
+    pub fn encrypt(&self, m: &BigInt) -> BigInt {
+        m.modpow(&self.e, &self.n)
+    }
+
+    pub fn decrypt(&self, c: &BigInt) -> BigInt {
+        c.modpow(&self.d, &self.n)
+    }
+
+    pub fn sign(&self, m: &BigInt) -> BigInt {
+        m.modpow(&self.d, &self.n)
+    }
+
+    pub fn verify(&self, m: &BigInt, signature: &BigInt) -> bool {
+        &signature.modpow(&self.e, &self.n) == m
+    }
+
+    /// decryption mod p and mod q with the half size exponents d mod (p - 1) and d mod (q - 1),
+    /// recombined by Garner's formula
+    pub fn decrypt_crt(&self, c: &BigInt) -> (BigInt, Vec<String>) {
+        let (p, q) = (&self.p, &self.q);
+        let dp = self.d.mod_floor(&(p - 1u32));
+        let dq = self.d.mod_floor(&(q - 1u32));
+        let q_inverse = mod_inverse(q, p).unwrap_or_else(BigInt::zero);
+        let m_p = c.modpow(&dp, p);
+        let m_q = c.modpow(&dq, q);
+        let h = (&q_inverse * (&m_p - &m_q)).mod_floor(p);
+        let m = &m_q + &h * q;
+        let steps = vec![
+            format!("d_p = d mod (p - 1) = {}", dp),
+            format!("d_q = d mod (q - 1) = {}", dq),
+            format!("q^-1 mod p = {}", q_inverse),
+            format!("m_p = c^d_p mod p = {}", m_p),
+            format!("m_q = c^d_q mod q = {}", m_q),
+            format!("h = q^-1 (m_p - m_q) mod p = {}", h),
+            format!("m = m_q + h q = {}", m),
+        ];
+        (m, steps)
+    }
+}
+
+/// the bytes of a text as one big endian number
+pub fn encode_text(text: &str) -> BigInt {
+    BigInt::from_bytes_be(Sign::Plus, text.as_bytes())
+}
+
+/// the inverse of encode_text, None if the bytes are not utf-8
+pub fn decode_text(num: &BigInt) -> Option<String> {
+    let (_, bytes) = num.to_bytes_be();
+    String::from_utf8(bytes).ok()
+}
+
+/// with a small e and m^e < n no reduction happens, so the integer e-th root of c is m
+pub fn small_exponent_attack(c: &BigInt, e: &BigInt) -> Option<BigInt> {
+    let e = e.to_u32()?;
+    let root = c.nth_root(e);
+    if &root.pow(e) == c {
+        Some(root)
+    } else {
+        None
+    }
+}
+
+/// moduli sharing a prime are both factored by their gcd, Some((p, n / p, other / p))
+pub fn shared_factor_attack(n: &BigInt, other: &BigInt) -> Option<(BigInt, BigInt, BigInt)> {
+    let p = n.gcd(other);
+    if p.is_one() || &p == n || &p == other {
+        None
+    } else {
+        Some((p.clone(), n / &p, other / &p))
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone)]
+pub enum IcedRsaMessage {
+    EditP(String),
+    EditQ(String),
+    EditE(String),
+    EditBits(String),
+    Generate,
+    Generated(Result<RsaKey, String>),
+    Build,
+    EditMessage(String),
+    EditCipher(String),
+    EditSignature(String),
+    EditOtherModulus(String),
+    Encrypt,
+    Decrypt,
+    DecryptCrt,
+    Sign,
+    Verify,
+    SmallExponentAttack,
+    SharedFactorAttack,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct IcedRsa {
+    p_text: String,
+    p_input: text_input::State,
+    q_text: String,
+    q_input: text_input::State,
+    e_text: String,
+    e_input: text_input::State,
+    bits_text: String,
+    bits_input: text_input::State,
+    generate_button: button::State,
+    build_button: button::State,
+    generating: bool,
+
+    message_text: String,
+    message_input: text_input::State,
+    cipher_text: String,
+    cipher_input: text_input::State,
+    signature_text: String,
+    signature_input: text_input::State,
+    other_modulus_text: String,
+    other_modulus_input: text_input::State,
+    encrypt_button: button::State,
+    decrypt_button: button::State,
+    crt_button: button::State,
+    sign_button: button::State,
+    verify_button: button::State,
+    small_exponent_button: button::State,
+    shared_factor_button: button::State,
+
+    key: Option<RsaKey>,
+    output: Vec<String>,
+    error_message: Option<String>,
+}
+
+impl IcedRsa {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn update(&mut self, message: IcedRsaMessage) -> Command<IcedRsaMessage> {
+        match message {
+            IcedRsaMessage::EditP(content) => self.p_text = content,
+            IcedRsaMessage::EditQ(content) => self.q_text = content,
+            IcedRsaMessage::EditE(content) => self.e_text = content,
+            IcedRsaMessage::EditBits(content) => self.bits_text = content,
+            IcedRsaMessage::EditMessage(content) => self.message_text = content,
+            IcedRsaMessage::EditCipher(content) => self.cipher_text = content,
+            IcedRsaMessage::EditSignature(content) => self.signature_text = content,
+            IcedRsaMessage::EditOtherModulus(content) => self.other_modulus_text = content,
+            IcedRsaMessage::Generate => {
+                if self.generating {
+                    return Command::none();
+                }
+                self.error_message = None;
+                let bits = match self.bits_text.trim() {
+                    "" => Ok(DEFAULT_BITS),
+                    bits => bits.parse::<u64>(),
+                };
+                let bits = match bits {
+                    Ok(bits) if (8..=RSA_BIT_LIMIT).contains(&bits) => bits,
+                    _ => {
+                        self.error_message =
+                            Some(format!("the primes need from 8 to {} bits", RSA_BIT_LIMIT));
+                        return Command::none();
+                    }
+                };
+                let e = match self.exponent() {
+                    Some(e) => e,
+                    None => return Command::none(),
+                };
+                self.generating = true;
+                return Command::perform(
+                    RsaKey::generate(bits, e, XorShift::from_time().0),
+                    IcedRsaMessage::Generated,
+                );
+            }
+            IcedRsaMessage::Generated(key) => {
+                self.generating = false;
+                self.set_key(key);
+            }
+            IcedRsaMessage::Build => {
+                self.error_message = None;
+                let key = match (parse_big(&self.p_text), parse_big(&self.q_text)) {
+                    (Some(p), Some(q)) => match self.exponent() {
+                        Some(e) => RsaKey::new(p, q, e),
+                        None => return Command::none(),
+                    },
+                    _ => Err(String::from("p and q must be numbers")),
+                };
+                self.set_key(key);
+            }
+            message => {
+                self.error_message = None;
+                match &self.key {
+                    Some(key) => {
+                        let key = key.clone();
+                        if let Err(message) = self.calculate(&key, message) {
+                            self.error_message = Some(message);
+                        }
+                    }
+                    None => {
+                        self.error_message = Some(String::from("build or generate a key first"))
+                    }
+                }
+            }
+        }
+        Command::none()
+    }
+
+    /// e from its input, 65537 if left empty
+    fn exponent(&mut self) -> Option<BigInt> {
+        if self.e_text.trim().is_empty() {
+            return Some(BigInt::from(DEFAULT_EXPONENT));
+        }
+        let e = parse_big(&self.e_text).filter(|e| *e >= BigInt::from(3) && e.is_odd());
+        if e.is_none() {
+            self.error_message = Some(String::from(
+                "e must be an odd number of at least 3, phi(n) is even",
+            ));
+        }
+        e
+    }
+
+    fn set_key(&mut self, key: Result<RsaKey, String>) {
+        self.output.clear();
+        match key {
+            Ok(key) => {
+                self.p_text = key.p.to_string();
+                self.q_text = key.q.to_string();
+                self.e_text = key.e.to_string();
+                self.key = Some(key);
+            }
+            Err(message) => {
+                self.key = None;
+                self.error_message = Some(message);
+            }
+        }
+    }
+
+    /// a number is taken as is, anything else as text
+    fn message(&self, n: &BigInt) -> Result<BigInt, String> {
+        let m = parse_big(&self.message_text).unwrap_or_else(|| encode_text(&self.message_text));
+        if m.is_negative() || &m >= n {
+            Err(format!(
+                "the message must lie in 0..n, n has {} bits",
+                n.bits()
+            ))
+        } else {
+            Ok(m)
+        }
+    }
+
+    fn calculate(&mut self, key: &RsaKey, message: IcedRsaMessage) -> Result<(), String> {
+        let number = |text: &str, name: &str| {
+            parse_big(text).ok_or_else(|| format!("{} must be a number", name))
+        };
+        match message {
+            IcedRsaMessage::Encrypt => {
+                let m = self.message(&key.n)?;
+                let c = key.encrypt(&m);
+                self.output.push(format!(
+                    "c = m^e mod n = {}^{} mod {} = {}",
+                    m, key.e, key.n, c
+                ));
+                self.cipher_text = c.to_string();
+            }
+            IcedRsaMessage::Decrypt => {
+                let c = number(&self.cipher_text, "c")?;
+                let m = key.decrypt(&c);
+                self.output.push(format!(
+                    "m = c^d mod n = {}^{} mod {} = {}",
+                    c, key.d, key.n, m
+                ));
+                if let Some(text) = decode_text(&m) {
+                    self.output.push(format!("as text: {}", text));
+                }
+            }
+            IcedRsaMessage::DecryptCrt => {
+                let c = number(&self.cipher_text, "c")?;
+                let (m, steps) = key.decrypt_crt(&c);
+                self.output.push(format!("CRT decryption of c = {}:", c));
+                self.output
+                    .extend(steps.into_iter().map(|step| format!("    {}", step)));
+                self.output.push(format!(
+                    "c^d mod n = {}, the exponents mod p and q have half the bits",
+                    key.decrypt(&c)
+                ));
+                if let Some(text) = decode_text(&m) {
+                    self.output.push(format!("as text: {}", text));
+                }
+            }
+            IcedRsaMessage::Sign => {
+                let m = self.message(&key.n)?;
+                let signature = key.sign(&m);
+                self.output.push(format!(
+                    "s = m^d mod n = {}^{} mod {} = {}",
+                    m, key.d, key.n, signature
+                ));
+                self.signature_text = signature.to_string();
+            }
+            IcedRsaMessage::Verify => {
+                let m = self.message(&key.n)?;
+                let signature = number(&self.signature_text, "the signature")?;
+                let check = signature.modpow(&key.e, &key.n);
+                self.output.push(format!(
+                    "s^e mod n = {}, m = {}: the signature is {}",
+                    check,
+                    m,
+                    if key.verify(&m, &signature) {
+                        "valid"
+                    } else {
+                        "invalid"
+                    }
+                ));
+            }
+            IcedRsaMessage::SmallExponentAttack => {
+                let c = number(&self.cipher_text, "c")?;
+                self.output.push(format!(
+                    "if m^{} < n, c = m^{} without any reduction, so m is the integer {}-th root of c",
+                    key.e, key.e, key.e
+                ));
+                match small_exponent_attack(&c, &key.e) {
+                    Some(m) => {
+                        self.output
+                            .push(format!("{}-th root of {} = {}", key.e, c, m));
+                        if let Some(text) = decode_text(&m) {
+                            self.output.push(format!("as text: {}", text));
+                        }
+                    }
+                    None => self.output.push(format!(
+                        "c is no perfect {}-th power, the message was reduced mod n",
+                        key.e
+                    )),
+                }
+            }
+            IcedRsaMessage::SharedFactorAttack => {
+                // without a second modulus one is made that reuses p, as a weak generator would
+                let other = if self.other_modulus_text.trim().is_empty() {
+                    let mut random = XorShift::from_time();
+                    let other = &key.p * random.prime(key.q.bits());
+                    self.other_modulus_text = other.to_string();
+                    other
+                } else {
+                    number(&self.other_modulus_text, "the other modulus")?
+                };
+                self.output
+                    .push(format!("gcd({}, {}) = {}", key.n, other, key.n.gcd(&other)));
+                match shared_factor_attack(&key.n, &other) {
+                    Some((p, q, other_q)) => {
+                        self.output
+                            .push(format!("n = {} * {}, other = {} * {}", p, q, p, other_q));
+                        let cracked = RsaKey::new(p, q, key.e.clone())?;
+                        self.output.push(format!(
+                            "phi(n) = {}, so d = e^-1 mod phi(n) = {}",
+                            cracked.phi, cracked.d
+                        ));
+                    }
+                    None => self
+                        .output
+                        .push(String::from("the moduli share no factor, the attack fails")),
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub fn view(&mut self) -> Element<'_, IcedRsaMessage> {
+        let key_input = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new("p: "))
+            .push(TextInput::new(
+                &mut self.p_input,
+                "p",
+                &self.p_text,
+                IcedRsaMessage::EditP,
+            ))
+            .push(Text::new("q: "))
+            .push(TextInput::new(
+                &mut self.q_input,
+                "q",
+                &self.q_text,
+                IcedRsaMessage::EditQ,
+            ))
+            .push(Text::new("e: "))
+            .push(TextInput::new(
+                &mut self.e_input,
+                "65537",
+                &self.e_text,
+                IcedRsaMessage::EditE,
+            ))
+            .push(
+                Button::new(&mut self.build_button, Text::new("Build"))
+                    .on_press(IcedRsaMessage::Build),
+            );
+        let generate = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new("bits per prime: "))
+            .push(TextInput::new(
+                &mut self.bits_input,
+                "64",
+                &self.bits_text,
+                IcedRsaMessage::EditBits,
+            ))
+            .push(
+                Button::new(&mut self.generate_button, Text::new("Generate"))
+                    .on_press(IcedRsaMessage::Generate),
+            )
+            .push(Text::new(if self.generating {
+                "generating..."
+            } else {
+                ""
+            }));
+
+        let key = match &self.key {
+            Some(key) => {
+                let eea = key.eea.data.iter().enumerate().fold(
+                    Row::new().spacing(20).align_items(Align::Center),
+                    |row, (i, chunk)| {
+                        row.push(chunk.iter().fold(
+                            Column::new().push(Text::new(EEA_INDICES[i])),
+                            |col, item| col.push(Text::new(item.to_string())),
+                        ))
+                    },
+                );
+                Column::new()
+                    .spacing(5)
+                    .push(Text::new(format!("n = p q = {}", key.n)))
+                    .push(Text::new(format!("phi(n) = (p - 1)(q - 1) = {}", key.phi)))
+                    .push(Text::new(format!(
+                        "EEA of e = {} and phi(n), d = s mod phi(n) = {}",
+                        key.e, key.d
+                    )))
+                    .push(eea)
+            }
+            None => Column::new(),
+        };
+
+        let values = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(Text::new("m: "))
+            .push(TextInput::new(
+                &mut self.message_input,
+                "number or text",
+                &self.message_text,
+                IcedRsaMessage::EditMessage,
+            ))
+            .push(Text::new("c: "))
+            .push(TextInput::new(
+                &mut self.cipher_input,
+                "c",
+                &self.cipher_text,
+                IcedRsaMessage::EditCipher,
+            ))
+            .push(Text::new("s: "))
+            .push(TextInput::new(
+                &mut self.signature_input,
+                "s",
+                &self.signature_text,
+                IcedRsaMessage::EditSignature,
+            ));
+        let operations = Row::new()
+            .spacing(10)
+            .push(
+                Button::new(&mut self.encrypt_button, Text::new("Encrypt m"))
+                    .on_press(IcedRsaMessage::Encrypt),
+            )
+            .push(
+                Button::new(&mut self.decrypt_button, Text::new("Decrypt c"))
+                    .on_press(IcedRsaMessage::Decrypt),
+            )
+            .push(
+                Button::new(&mut self.crt_button, Text::new("Decrypt c (CRT)"))
+                    .on_press(IcedRsaMessage::DecryptCrt),
+            )
+            .push(
+                Button::new(&mut self.sign_button, Text::new("Sign m"))
+                    .on_press(IcedRsaMessage::Sign),
+            )
+            .push(
+                Button::new(&mut self.verify_button, Text::new("Verify s"))
+                    .on_press(IcedRsaMessage::Verify),
+            );
+        let attacks = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(
+                Button::new(
+                    &mut self.small_exponent_button,
+                    Text::new("Small e attack on c"),
+                )
+                .on_press(IcedRsaMessage::SmallExponentAttack),
+            )
+            .push(TextInput::new(
+                &mut self.other_modulus_input,
+                "other modulus, empty to make one",
+                &self.other_modulus_text,
+                IcedRsaMessage::EditOtherModulus,
+            ))
+            .push(
+                Button::new(
+                    &mut self.shared_factor_button,
+                    Text::new("Shared factor attack"),
+                )
+                .on_press(IcedRsaMessage::SharedFactorAttack),
+            );
+
+        let output = self
+            .output
+            .iter()
+            .fold(Column::new().spacing(5), |col, line| {
+                col.push(Text::new(line))
+            });
+
+        let maybe_error = match &self.error_message {
+            None => Text::new(""),
+            Some(message) => Text::new(message).color([0.921, 0.039, 0.039]),
+        };
+
+        Column::new()
+            .spacing(10)
+            .push(key_input)
+            .push(generate)
+            .push(key)
+            .push(values)
+            .push(operations)
+            .push(attacks)
+            .push(output)
+            .push(maybe_error)
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(num: u64) -> BigInt {
+        BigInt::from(num)
+    }
+
+    #[test]
+    fn invalid_keys_are_rejected() {
+        assert!(RsaKey::new(big(61), big(61), big(17)).is_err());
+        assert!(RsaKey::new(big(61), big(63), big(17)).is_err());
+        // phi = 60 * 52 = 3120 = 2^4 * 3 * 5 * 13
+        assert!(RsaKey::new(big(61), big(53), big(65)).is_err());
+        assert!(RsaKey::new(big(61), big(53), big(3120)).is_err());
+        assert!(RsaKey::new(big(61), big(53), big(1)).is_err());
+    }
+
+    #[test]
+    fn decryption_inverts_encryption() {
+        let key = RsaKey::new(big(61), big(53), big(17)).unwrap();
+        assert_eq!(
+            (key.n.clone(), key.phi.clone(), key.d.clone()),
+            (big(3233), big(3120), big(2753))
+        );
+        assert_eq!(key.encrypt(&big(65)), big(2790));
+        for m in [0, 1, 2, 65, 1000, 3232] {
+            let c = key.encrypt(&big(m));
+            assert_eq!(key.decrypt(&c), big(m));
+            assert_eq!(key.decrypt_crt(&c).0, big(m));
+        }
+    }
+
+    #[test]
+    fn signatures_verify_only_their_message() {
+        let key = RsaKey::new(big(1_000_003), big(1_000_033), big(65_537)).unwrap();
+        let m = big(123_456_789);
+        let signature = key.sign(&m);
+        assert!(key.verify(&m, &signature));
+        assert!(!key.verify(&(&m + 1u32), &signature));
+        assert!(!key.verify(&m, &(&signature + 1u32)));
+    }
+
+    #[test]
+    fn generated_keys_work() {
+        let key = async_std::task::block_on(RsaKey::generate(64, big(65_537), 7)).unwrap();
+        assert_ne!(key.p, key.q);
+        assert!((&key.e * &key.d).mod_floor(&key.phi).is_one());
+        let m = encode_text("hi");
+        assert_eq!(decode_text(&key.decrypt(&key.encrypt(&m))).unwrap(), "hi");
+        assert!(async_std::task::block_on(RsaKey::generate(64, big(4), 7)).is_err());
+    }
+
+    #[test]
+    fn small_exponent_attack_takes_the_cube_root() {
+        let key = async_std::task::block_on(RsaKey::generate(128, big(3), 11)).unwrap();
+        let m = encode_text("short");
+        let c = key.encrypt(&m);
+        assert_eq!(small_exponent_attack(&c, &key.e), Some(m));
+        assert_eq!(small_exponent_attack(&(c + 1u32), &key.e), None);
+    }
+
+    #[test]
+    fn shared_factor_attack_splits_both_moduli() {
+        let (p, q, r) = (big(1_000_003), big(1_000_033), big(1_000_037));
+        let n = &p * &q;
+        let other = &p * &r;
+        assert_eq!(
+            shared_factor_attack(&n, &other),
+            Some((p, q.clone(), r.clone()))
+        );
+        assert_eq!(shared_factor_attack(&n, &(&r * big(1_000_039))), None);
+        assert_eq!(shared_factor_attack(&n, &n), None);
+    }
+}
@@ -0,0 +1,538 @@
+use crate::group_theory::cayley::{IcedCayleyMessage, IcedCayleyTable};
+use crate::group_theory::finite_group::{FiniteGroup, Subgroup};
+use crate::utils::{canvas_label, element_color};
+use iced::canvas::{event, Canvas, Cursor, Event, Frame, Geometry, Path, Program, Stroke};
+use iced::{mouse, Color, Column, Element, Length, Point, Rectangle, Row, Size, Text, Vector};
+
+/// the widest a node gets, levels narrower than the canvas are centred
+const NODE_WIDTH: f32 = 140.0;
+const NODE_HEIGHT: f32 = 64.0;
+const NODE_SPACING: f32 = 10.0;
+/// the room for the edges between two levels
+const LEVEL_GAP: f32 = 40.0;
+/// an edge passing a level it skips takes this part of a node's slot
+const PASS_WIDTH: f32 = 0.25;
+const EDGE_WIDTH: f32 = 1.5;
+const EDGE_COLOR: Color = Color::from_rgb(0.6, 0.6, 0.6);
+const HIGHLIGHT: Color = Color::BLACK;
+/// subgroups are only enumerated up to this group order, the joins of cyclic subgroups grow
+/// too fast beyond it
+pub const SUBGROUP_LATTICE_LIMIT: usize = 64;
+
+#[derive(Debug, Clone)]
+pub enum IcedSubgroupMessage {
+    Select(usize),
+    QuotientMessage(IcedCayleyMessage),
+}
+
+/// the subgroup lattice of a finite group, drawn as one level of nodes per subgroup order
+#[derive(Debug, Clone)]
+pub struct IcedSubgroupLattice {
+    group: FiniteGroup,
+    subgroups: Vec<Subgroup>,
+    /// covers[i] are the maximal proper subgroups of subgroups[i], the edges of the Hasse diagram
+    covers: Vec<Vec<usize>>,
+    diagram: LatticeDiagram,
+    selected: Option<usize>,
+    /// G/H of the selected subgroup if it is normal, with its element orders
+    quotient: Option<IcedCayleyTable>,
+    quotient_orders: Vec<String>,
+}
+
+impl IcedSubgroupLattice {
+    pub fn new(group: FiniteGroup) -> Self {
+        let subgroups = if group.order() <= SUBGROUP_LATTICE_LIMIT {
+            group.subgroups()
+        } else {
+            Vec::new()
+        };
+        let covers = covers(&subgroups);
+        let order = group.order();
+        let diagram = LatticeDiagram {
+            layout: LatticeLayout::new(&subgroups, &covers),
+            labels: subgroups
+                .iter()
+                .enumerate()
+                .map(|(i, subgroup)| {
+                    (
+                        format!("H{}", i),
+                        format!("order {}", subgroup.order()),
+                        group.label_generators(&subgroup.generators),
+                    )
+                })
+                .collect(),
+            colors: subgroups
+                .iter()
+                .map(|subgroup| {
+                    element_color(divisor_index(order, subgroup.order()), divisor_count(order))
+                })
+                .collect(),
+            selected: None,
+        };
+        Self {
+            group,
+            subgroups,
+            covers,
+            diagram,
+            selected: None,
+            quotient: None,
+            quotient_orders: Vec::new(),
+        }
+    }
+
+    pub fn update(&mut self, message: IcedSubgroupMessage) {
+        match message {
+            IcedSubgroupMessage::Select(index) => {
+                self.selected = if self.selected == Some(index) {
+                    None
+                } else {
+                    Some(index)
+                };
+                self.diagram.selected = self.selected;
+                let quotient = self.selected.and_then(|index| {
+                    self.group
+                        .quotient(&self.subgroups[index].elements, &format!("H{}", index))
+                });
+                self.quotient_orders = match &quotient {
+                    Some(quotient) => (0..quotient.order())
+                        .map(|a| {
+                            format!(
+                                "{}: order {}",
+                                quotient.labels[a],
+                                quotient.element_order(a).unwrap_or_default()
+                            )
+                        })
+                        .collect(),
+                    None => Vec::new(),
+                };
+                self.quotient = quotient.map(IcedCayleyTable::new);
+            }
+            IcedSubgroupMessage::QuotientMessage(message) => {
+                if let Some(quotient) = &mut self.quotient {
+                    quotient.update(message)
+                }
+            }
+        }
+    }
+
+    pub fn view(&mut self) -> Element<'_, IcedSubgroupMessage> {
+        if self.group.order() > SUBGROUP_LATTICE_LIMIT {
+            return Text::new(format!(
+                "the group has {} elements, subgroup lattices are only built up to {}",
+                self.group.order(),
+                SUBGROUP_LATTICE_LIMIT
+            ))
+            .into();
+        }
+        let group = &self.group;
+        let subgroups = &self.subgroups;
+        let covers = &self.covers;
+        let selected = self.selected;
+        let order = group.order();
+        let height = self.diagram.height();
+        let lattice = Canvas::new(&mut self.diagram)
+            .width(Length::Fill)
+            .height(Length::Units(height as u16));
+
+        let mut content = Column::new()
+            .spacing(10)
+            .push(Text::new(format!(
+                "Subgroup lattice of {}: {} subgroups, click a subgroup for its elements and cosets",
+                group.name,
+                subgroups.len()
+            )))
+            .push(lattice);
+        if let Some(index) = selected {
+            let subgroup = &subgroups[index];
+            let name = format!("H{}", index);
+            let index_of = order / subgroup.order();
+            let lagrange = Row::new()
+                .spacing(30)
+                .push(Text::new(format!("|{}| = {}", name, subgroup.order())))
+                .push(Text::new(format!("[G : {}] = {}", name, index_of)))
+                .push(Text::new(format!(
+                    "|G| = [G : {}] * |{}| = {} * {} = {}",
+                    name,
+                    name,
+                    index_of,
+                    subgroup.order(),
+                    order
+                )));
+            let left = group.left_cosets(&subgroup.elements).iter().fold(
+                Column::new().push(Text::new(format!(
+                    "left cosets g {} {}",
+                    group.symbol, name
+                ))),
+                |col, coset| col.push(Text::new(group.label_set(coset))),
+            );
+            let right = group.right_cosets(&subgroup.elements).iter().fold(
+                Column::new().push(Text::new(format!(
+                    "right cosets {} {} g",
+                    name, group.symbol
+                ))),
+                |col, coset| col.push(Text::new(group.label_set(coset))),
+            );
+            let normal = if group.is_normal(&subgroup.elements) {
+                format!("{} is normal, left and right cosets agree", name)
+            } else {
+                format!("{} is not normal, so G/{} is not a group", name, name)
+            };
+            content = content.push(Text::new(format!(
+                "{} = {} = {}",
+                name,
+                group.label_generators(&subgroup.generators),
+                group.label_set(&subgroup.elements)
+            )));
+            if !covers[index].is_empty() {
+                let names: Vec<String> = covers[index].iter().map(|j| format!("H{}", j)).collect();
+                content = content.push(Text::new(format!(
+                    "maximal subgroups of {}: {}",
+                    name,
+                    names.join(", ")
+                )));
+            }
+            content = content
+                .push(lagrange)
+                .push(Row::new().spacing(30).push(left).push(right))
+                .push(Text::new(normal));
+            if let Some(quotient) = &mut self.quotient {
+                let orders = self
+                    .quotient_orders
+                    .iter()
+                    .fold(Column::new(), |col, order| col.push(Text::new(order)));
+                content = content
+                    .push(quotient.view().map(IcedSubgroupMessage::QuotientMessage))
+                    .push(orders);
+            }
+        }
+        content.into()
+    }
+}
+
+/// a slot in a level of the diagram, a subgroup or a cover edge passing a level it skips
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LatticeItem {
+    Node(usize),
+    Pass(usize),
+}
+
+/// where the Hasse diagram puts its nodes, one level per subgroup order with the largest
+/// on top, x positions are in node slots, an edge that skips levels bends through a pass
+/// on every level in between
+#[derive(Debug, Clone)]
+struct LatticeLayout {
+    /// (upper, lower) for every cover relation
+    edges: Vec<(usize, usize)>,
+    /// (level, x) of the centre of every subgroup
+    nodes: Vec<(usize, f32)>,
+    /// (level, x) of the passes of every edge, top to bottom
+    passes: Vec<Vec<(usize, f32)>>,
+    levels: usize,
+    /// the widest level in node slots
+    width: f32,
+}
+
+impl LatticeLayout {
+    fn new(subgroups: &[Subgroup], covers: &[Vec<usize>]) -> Self {
+        let mut orders: Vec<usize> = subgroups.iter().map(Subgroup::order).collect();
+        orders.dedup();
+        orders.reverse();
+        let level_of: Vec<usize> = subgroups
+            .iter()
+            .map(|subgroup| orders.iter().position(|o| *o == subgroup.order()).unwrap())
+            .collect();
+        let edges: Vec<(usize, usize)> = covers
+            .iter()
+            .enumerate()
+            .flat_map(|(upper, below)| below.iter().map(move |lower| (upper, *lower)))
+            .collect();
+
+        let mut rows: Vec<Vec<LatticeItem>> = vec![Vec::new(); orders.len()];
+        for (i, level) in level_of.iter().enumerate() {
+            rows[*level].push(LatticeItem::Node(i));
+        }
+        for (edge, (upper, lower)) in edges.iter().enumerate() {
+            for row in &mut rows[level_of[*upper] + 1..level_of[*lower]] {
+                row.push(LatticeItem::Pass(edge));
+            }
+        }
+        // the items one level up that an item hangs from
+        let parents = |level: usize, item: LatticeItem| -> Vec<LatticeItem> {
+            let parent = |edge: usize| {
+                let upper = edges[edge].0;
+                if level_of[upper] + 1 == level {
+                    LatticeItem::Node(upper)
+                } else {
+                    LatticeItem::Pass(edge)
+                }
+            };
+            match item {
+                LatticeItem::Node(i) => (0..edges.len())
+                    .filter(|edge| edges[*edge].1 == i)
+                    .map(parent)
+                    .collect(),
+                LatticeItem::Pass(edge) => vec![parent(edge)],
+            }
+        };
+        let item_width = |item: &LatticeItem| match item {
+            LatticeItem::Node(_) => 1.0,
+            LatticeItem::Pass(_) => PASS_WIDTH,
+        };
+        let row_width = |row: &[LatticeItem]| row.iter().map(item_width).sum::<f32>();
+        let width = rows.iter().map(|row| row_width(row)).fold(0.0, f32::max);
+
+        // every level is sorted by the mean position of what it hangs from to keep crossings down
+        let mut nodes = vec![(0, 0.0); subgroups.len()];
+        let mut passes = vec![Vec::new(); edges.len()];
+        let mut above: Vec<(LatticeItem, f32)> = Vec::new();
+        for (level, mut row) in rows.into_iter().enumerate() {
+            let x_of = |item: LatticeItem| {
+                above
+                    .iter()
+                    .find(|(other, _)| *other == item)
+                    .map_or(0.0, |(_, x)| *x)
+            };
+            let barycenter = |item: &LatticeItem| {
+                let xs: Vec<f32> = parents(level, *item).into_iter().map(x_of).collect();
+                xs.iter().sum::<f32>() / xs.len().max(1) as f32
+            };
+            row.sort_by(|a, b| barycenter(a).partial_cmp(&barycenter(b)).unwrap());
+            let mut x = (width - row_width(&row)) / 2.0;
+            above = row
+                .into_iter()
+                .map(|item| {
+                    let center = x + item_width(&item) / 2.0;
+                    x += item_width(&item);
+                    match item {
+                        LatticeItem::Node(i) => nodes[i] = (level, center),
+                        LatticeItem::Pass(edge) => passes[edge].push((level, center)),
+                    }
+                    (item, center)
+                })
+                .collect();
+        }
+
+        Self {
+            edges,
+            nodes,
+            passes,
+            levels: orders.len(),
+            width,
+        }
+    }
+}
+
+/// the Hasse diagram on a canvas, a click on a node selects its subgroup
+#[derive(Debug, Clone)]
+struct LatticeDiagram {
+    layout: LatticeLayout,
+    /// name, order and generators of every subgroup
+    labels: Vec<(String, String, String)>,
+    /// the index of the order among the divisors of |G| as a colour
+    colors: Vec<Color>,
+    selected: Option<usize>,
+}
+
+impl LatticeDiagram {
+    fn height(&self) -> f32 {
+        let levels = self.layout.levels as f32;
+        (levels * NODE_HEIGHT + (levels - 1.0) * LEVEL_GAP).max(0.0)
+    }
+
+    /// the width of a node slot and the x of the left edge of the widest level
+    fn slots(&self, size: Size) -> (f32, f32) {
+        let slot = (size.width / self.layout.width.max(1.0)).min(NODE_WIDTH + NODE_SPACING);
+        (slot, (size.width - slot * self.layout.width) / 2.0)
+    }
+
+    fn level_top(level: usize) -> f32 {
+        level as f32 * (NODE_HEIGHT + LEVEL_GAP)
+    }
+
+    fn node_bounds(&self, i: usize, size: Size) -> Rectangle {
+        let (slot, left) = self.slots(size);
+        let (level, x) = self.layout.nodes[i];
+        let width = (slot - NODE_SPACING).max(1.0);
+        Rectangle::new(
+            Point::new(left + x * slot - width / 2.0, Self::level_top(level)),
+            Size::new(width, NODE_HEIGHT),
+        )
+    }
+
+    fn node_at(&self, point: Point, size: Size) -> Option<usize> {
+        (0..self.layout.nodes.len()).find(|i| self.node_bounds(*i, size).contains(point))
+    }
+
+    fn touches(&self, edge: usize) -> bool {
+        let (upper, lower) = self.layout.edges[edge];
+        self.selected == Some(upper) || self.selected == Some(lower)
+    }
+
+    /// from the bottom of the upper node down every pass to the top of the lower node
+    fn edge_path(&self, edge: usize, size: Size) -> Path {
+        let (slot, left) = self.slots(size);
+        let (upper, lower) = self.layout.edges[edge];
+        let upper = self.node_bounds(upper, size);
+        let lower = self.node_bounds(lower, size);
+        Path::new(|builder| {
+            builder.move_to(Point::new(upper.center_x(), upper.y + upper.height));
+            for (level, x) in &self.layout.passes[edge] {
+                let x = left + x * slot;
+                builder.line_to(Point::new(x, Self::level_top(*level)));
+                builder.line_to(Point::new(x, Self::level_top(*level) + NODE_HEIGHT));
+            }
+            builder.line_to(Point::new(lower.center_x(), lower.y));
+        })
+    }
+}
+
+impl Program<IcedSubgroupMessage> for LatticeDiagram {
+    fn update(
+        &mut self,
+        event: Event,
+        bounds: Rectangle,
+        cursor: Cursor,
+    ) -> (event::Status, Option<IcedSubgroupMessage>) {
+        if let Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) = event {
+            let clicked = cursor
+                .position_in(&bounds)
+                .and_then(|point| self.node_at(point, bounds.size()));
+            if let Some(i) = clicked {
+                return (
+                    event::Status::Captured,
+                    Some(IcedSubgroupMessage::Select(i)),
+                );
+            }
+        }
+        (event::Status::Ignored, None)
+    }
+
+    fn draw(&self, bounds: Rectangle, _cursor: Cursor) -> Vec<Geometry> {
+        let size = bounds.size();
+        let mut frame = Frame::new(size);
+        // the edges of the selected subgroup go last so they stay on top
+        let mut edges: Vec<usize> = (0..self.layout.edges.len()).collect();
+        edges.sort_by_key(|edge| self.touches(*edge));
+        for edge in edges {
+            let (color, width) = if self.touches(edge) {
+                (HIGHLIGHT, 2.0 * EDGE_WIDTH)
+            } else {
+                (EDGE_COLOR, EDGE_WIDTH)
+            };
+            frame.stroke(
+                &self.edge_path(edge, size),
+                Stroke::default().with_color(color).with_width(width),
+            );
+        }
+        for (i, (name, order, generators)) in self.labels.iter().enumerate() {
+            let node = self.node_bounds(i, size);
+            frame.fill_rectangle(node.position(), node.size(), self.colors[i]);
+            if self.selected == Some(i) {
+                frame.stroke(
+                    &Path::rectangle(node.position(), node.size()),
+                    Stroke::default().with_color(HIGHLIGHT).with_width(2.0),
+                );
+            }
+            let length = ((node.width - 10.0) / 6.0).max(0.0) as usize;
+            let generators: String = if generators.chars().count() > length {
+                generators
+                    .chars()
+                    .take(length.saturating_sub(3))
+                    .chain("...".chars())
+                    .collect()
+            } else {
+                generators.clone()
+            };
+            let center = node.center();
+            frame.fill_text(canvas_label(
+                name.as_str(),
+                center - Vector::new(0.0, 18.0),
+                16.0,
+            ));
+            frame.fill_text(canvas_label(order.as_str(), center, 14.0));
+            frame.fill_text(canvas_label(
+                generators,
+                center + Vector::new(0.0, 18.0),
+                12.0,
+            ));
+        }
+        vec![frame.into_geometry()]
+    }
+
+    fn mouse_interaction(&self, bounds: Rectangle, cursor: Cursor) -> mouse::Interaction {
+        match cursor.position_in(&bounds) {
+            Some(point) if self.node_at(point, bounds.size()).is_some() => {
+                mouse::Interaction::Pointer
+            }
+            _ => mouse::Interaction::default(),
+        }
+    }
+}
+
+/// for every subgroup the subgroups directly below it in the inclusion order,
+/// expects the subgroups ascending by order
+fn covers(subgroups: &[Subgroup]) -> Vec<Vec<usize>> {
+    (0..subgroups.len())
+        .map(|i| {
+            let below: Vec<usize> = (0..i)
+                .filter(|j| {
+                    subgroups[*j].order() < subgroups[i].order()
+                        && subgroups[i].contains(&subgroups[*j])
+                })
+                .collect();
+            below
+                .iter()
+                .cloned()
+                .filter(|j| {
+                    !below
+                        .iter()
+                        .any(|k| k != j && subgroups[*k].contains(&subgroups[*j]))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn divisor_count(num: usize) -> usize {
+    (1..=num).filter(|d| num.is_multiple_of(*d)).count()
+}
+
+fn divisor_index(num: usize, divisor: usize) -> usize {
+    (1..divisor).filter(|d| num.is_multiple_of(*d)).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::group_theory::finite_group::GroupType;
+
+    #[test]
+    fn lattice_layout_of_z_12() {
+        let lattice = IcedSubgroupLattice::new(FiniteGroup::z_n(12, GroupType::Additive).unwrap());
+        let diagram = &lattice.diagram;
+        let layout = &diagram.layout;
+        // orders 12, 6, 4, 3, 2, 1, one subgroup each
+        assert_eq!(layout.levels, 6);
+        assert_eq!(layout.nodes.len(), 6);
+        assert_eq!(layout.edges.len(), 7);
+        for (edge, (upper, lower)) in layout.edges.iter().enumerate() {
+            let (upper_level, lower_level) = (layout.nodes[*upper].0, layout.nodes[*lower].0);
+            assert!(upper_level < lower_level);
+            assert_eq!(layout.passes[edge].len(), lower_level - upper_level - 1);
+        }
+        for size in [
+            Size::new(1000.0, diagram.height()),
+            Size::new(90.0, diagram.height()),
+        ] {
+            for i in 0..layout.nodes.len() {
+                let node = diagram.node_bounds(i, size);
+                assert!(node.x >= 0.0 && node.x + node.width <= size.width);
+                assert_eq!(diagram.node_at(node.center(), size), Some(i));
+            }
+        }
+        assert_eq!(
+            diagram.node_at(Point::new(0.0, 0.0), Size::new(1000.0, 500.0)),
+            None
+        );
+    }
+}
@@ -1,5 +1,6 @@
 #![windows_subsystem = "windows"]
 
+use crate::coding::{CodingMessage, IcedCoding};
 use crate::errors::LoadError;
 use crate::group_theory::{GroupTheoryMessage, IcedGroupTheory};
 use crate::matrix::{MatrixCalculationState, MatrixMessage};
@@ -9,6 +10,7 @@ use iced::{
     HorizontalAlignment, Length, Row, Scrollable, Settings, Text,
 };
 
+pub mod coding;
 pub mod errors;
 pub mod group_theory;
 pub mod matrix;
@@ -18,6 +20,7 @@ fn main() -> iced::Result {
     Pyruvate::run(Settings::default())
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug)]
 pub enum Pyruvate {
     Loading,
@@ -41,11 +44,13 @@ impl Default for State {
     }
 }
 
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
 pub enum SubState {
     None,
     Matrix(MatrixCalculationState),
     GroupTheory(IcedGroupTheory),
+    Coding(IcedCoding),
 }
 
 impl State {
@@ -59,6 +64,7 @@ pub enum Message {
     Loaded(Result<State, LoadError>),
     MatrixMessage(MatrixMessage),
     GroupTheoryMessage(GroupTheoryMessage),
+    CodingMessage(CodingMessage),
     SwitchState(SubState),
     None,
 }
@@ -102,14 +108,23 @@ impl Application for Pyruvate {
                     Message::SwitchState(new) => {
                         state.current = new;
                     }
-                    Message::MatrixMessage(sub_message) => match &mut state.current {
-                        SubState::Matrix(sub_state) => sub_state.update(sub_message),
-                        _ => {}
-                    },
-                    Message::GroupTheoryMessage(sub_message) => match &mut state.current {
-                        SubState::GroupTheory(sub_state) => sub_state.update(sub_message),
-                        _ => {}
-                    },
+                    Message::MatrixMessage(sub_message) => {
+                        if let SubState::Matrix(sub_state) = &mut state.current {
+                            sub_state.update(sub_message)
+                        }
+                    }
+                    Message::GroupTheoryMessage(sub_message) => {
+                        if let SubState::GroupTheory(sub_state) = &mut state.current {
+                            return sub_state
+                                .update(sub_message)
+                                .map(Message::GroupTheoryMessage);
+                        }
+                    }
+                    Message::CodingMessage(sub_message) => {
+                        if let SubState::Coding(sub_state) = &mut state.current {
+                            sub_state.update(sub_message)
+                        }
+                    }
                     _ => {}
                 }
                 Command::none()
@@ -117,7 +132,7 @@ impl Application for Pyruvate {
         }
     }
 
-    fn view(&mut self) -> Element<Message> {
+    fn view(&mut self) -> Element<'_, Message> {
         match self {
             Pyruvate::Loading => loading_message(),
             Pyruvate::Loaded(State {
@@ -135,12 +150,11 @@ impl Application for Pyruvate {
                     SubState::None => Column::new()
                         .push(Text::new("Please Select Something"))
                         .into(),
-                    SubState::Matrix(sub_state) => sub_state
-                        .view()
-                        .map(move |message| Message::MatrixMessage(message)),
-                    SubState::GroupTheory(sub_state) => sub_state
-                        .view()
-                        .map(move |message| Message::GroupTheoryMessage(message)),
+                    SubState::Matrix(sub_state) => sub_state.view().map(Message::MatrixMessage),
+                    SubState::GroupTheory(sub_state) => {
+                        sub_state.view().map(Message::GroupTheoryMessage)
+                    }
+                    SubState::Coding(sub_state) => sub_state.view().map(Message::CodingMessage),
                 };
                 let controls = controls.view();
 
@@ -166,10 +180,11 @@ pub struct Controls {
     home_button: button::State,
     matrices_button: button::State,
     group_theory_button: button::State,
+    coding_button: button::State,
 }
 
 impl Controls {
-    fn view(&mut self) -> Row<Message> {
+    fn view(&mut self) -> Row<'_, Message> {
         Row::new()
             .spacing(10)
             .align_items(Align::Center)
@@ -187,5 +202,9 @@ impl Controls {
                     Message::SwitchState(SubState::GroupTheory(IcedGroupTheory::new())),
                 ),
             )
+            .push(
+                Button::new(&mut self.coding_button, Text::new("Coding Theory"))
+                    .on_press(Message::SwitchState(SubState::Coding(IcedCoding::new()))),
+            )
     }
 }
@@ -1,14 +1,39 @@
+use crate::matrix::chain::chain_order;
+use crate::matrix::functions::{
+    exponential, format_rows_f64, from_rows, power, scale, to_rows, Diagonalization,
+    MatrixFunction, PADE_DEGREE,
+};
+use crate::matrix::lattice::{is_exact, lll, matrix_of, rows_of, LatticeExercise, DEFAULT_DELTA};
+use crate::matrix::normal_form::{format_rows, hermite_normal_form, smith_normal_form};
 use crate::utils::{delete_icon, edit_icon, new_icon};
 use glucose::DMatrix;
 use iced::{
     button, text_input, Align, Button, Column, Element, Length, Radio, Row, Text, TextInput,
 };
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub mod chain;
+pub mod functions;
+pub mod lattice;
+pub mod normal_form;
+
+/// longer LLL logs are cut off
+const STEP_LOG_LIMIT: usize = 200;
+const ROUNDED_NOTE: &str =
+    "entries above 2^53 are shown rounded, LLL and the answer use the exact basis";
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
 pub enum IcedMatrixOperation {
+    #[default]
     Add,
     Sub,
     Mul,
+    Lll,
+    Hermite,
+    Smith,
+    Scale,
+    Power,
+    Exp,
+    Function,
 }
 
 impl From<IcedMatrixOperation> for String {
@@ -17,6 +42,13 @@ impl From<IcedMatrixOperation> for String {
             IcedMatrixOperation::Add => "Addition",
             IcedMatrixOperation::Sub => "Subtraction",
             IcedMatrixOperation::Mul => "Multiplication",
+            IcedMatrixOperation::Lll => "LLL reduction of the rows of mat1",
+            IcedMatrixOperation::Hermite => "Hermite normal form of mat1",
+            IcedMatrixOperation::Smith => "Smith normal form of mat1",
+            IcedMatrixOperation::Scale => "k times mat1",
+            IcedMatrixOperation::Power => "mat1 to the power k",
+            IcedMatrixOperation::Exp => "e^(t mat1)",
+            IcedMatrixOperation::Function => "f(mat1) by diagonalization",
         })
     }
 }
@@ -30,31 +62,48 @@ impl std::fmt::Display for IcedMatrixOperation {
                 IcedMatrixOperation::Add => "Add",
                 IcedMatrixOperation::Sub => "Sub",
                 IcedMatrixOperation::Mul => "Mul",
+                IcedMatrixOperation::Lll => "LLL",
+                IcedMatrixOperation::Hermite => "HNF",
+                IcedMatrixOperation::Smith => "SNF",
+                IcedMatrixOperation::Scale => "kA",
+                IcedMatrixOperation::Power => "A^k",
+                IcedMatrixOperation::Exp => "exp",
+                IcedMatrixOperation::Function => "f(A)",
             }
         )
     }
 }
 
 impl IcedMatrixOperation {
-    pub const ALL: [IcedMatrixOperation; 3] = [
+    pub const ALL: [IcedMatrixOperation; 10] = [
         IcedMatrixOperation::Add,
         IcedMatrixOperation::Sub,
         IcedMatrixOperation::Mul,
+        IcedMatrixOperation::Lll,
+        IcedMatrixOperation::Hermite,
+        IcedMatrixOperation::Smith,
+        IcedMatrixOperation::Scale,
+        IcedMatrixOperation::Power,
+        IcedMatrixOperation::Exp,
+        IcedMatrixOperation::Function,
     ];
 }
 
-impl Default for IcedMatrixOperation {
-    fn default() -> Self {
-        Self::Add
-    }
-}
-
 #[derive(Debug, Clone)]
 pub enum MatrixMessage {
     IcedMatrixMessage(usize, IcedMatrixMessage),
     MatrixOpSelected(IcedMatrixOperation),
     MatrixCalculate,
     ReuseResult,
+    AddSlot,
+    RemoveSlot,
+    EditOrder(String),
+    EditScalar(String),
+    FunctionSelected(MatrixFunction),
+    EditDelta(String),
+    EditExercise(String),
+    BuildKnapsack,
+    BuildSmallRoot,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -63,7 +112,29 @@ pub struct MatrixCalculationState {
     selected_mat_op: Option<IcedMatrixOperation>,
     calculate_button: button::State,
     reuse_button: button::State,
+    add_slot_button: button::State,
+    remove_slot_button: button::State,
+    /// 1-based slots to combine, all of them in order if empty
+    order_text: String,
+    order_input: text_input::State,
+    /// k for k mat1 and mat1^k, t for e^(t mat1)
+    scalar_text: String,
+    scalar_input: text_input::State,
+    function: MatrixFunction,
+    delta_text: String,
+    delta_input: text_input::State,
+    exercise_text: String,
+    exercise_input: text_input::State,
+    knapsack_button: button::State,
+    small_root_button: button::State,
+    /// the problem mat1 was built from, read back from the reduced basis
+    exercise: Option<LatticeExercise>,
     result: Option<IcedMatrix>,
+    /// the partial sums or products on the way to the result
+    intermediates: Vec<(String, DMatrix<f64>)>,
+    /// the LLL steps, Hadamard ratios and what the reduced basis solves,
+    /// or the unimodular transforms of a normal form
+    notes: Vec<String>,
     error_message: Option<String>,
 }
 
@@ -74,7 +145,23 @@ impl MatrixCalculationState {
             selected_mat_op: Default::default(),
             calculate_button: Default::default(),
             reuse_button: Default::default(),
+            add_slot_button: Default::default(),
+            remove_slot_button: Default::default(),
+            order_text: String::default(),
+            order_input: Default::default(),
+            scalar_text: String::default(),
+            scalar_input: Default::default(),
+            function: MatrixFunction::default(),
+            delta_text: String::default(),
+            delta_input: Default::default(),
+            exercise_text: String::default(),
+            exercise_input: Default::default(),
+            knapsack_button: Default::default(),
+            small_root_button: Default::default(),
+            exercise: None,
             result: None,
+            intermediates: Vec::new(),
+            notes: Vec::new(),
             error_message: None,
         }
     }
@@ -82,6 +169,9 @@ impl MatrixCalculationState {
     pub fn update(&mut self, message: MatrixMessage) {
         match message {
             MatrixMessage::IcedMatrixMessage(id, matrix_message) => {
+                if id == 0 {
+                    self.exercise = None;
+                }
                 if let Some(matrix) = self.matrices.get_mut(id) {
                     matrix.update(matrix_message)
                 }
@@ -91,76 +181,341 @@ impl MatrixCalculationState {
             }
             MatrixMessage::MatrixCalculate => {
                 self.error_message = None;
-                if self.matrices[0].is_initialized() && self.matrices[1].is_initialized() {
-                    match self.selected_mat_op {
-                        Some(IcedMatrixOperation::Add) => {
-                            if self.matrices[0].get_size_unchecked()
-                                == self.matrices[1].get_size_unchecked()
-                            {
-                                self.result = Some(IcedMatrix::from_matrix(
-                                    self.matrices[0].get_matrix_unchecked()
-                                        + self.matrices[1].get_matrix_unchecked(),
-                                ))
-                            } else {
-                                self.error_message = Some(String::from("Matrix sizes not equal"));
-                            }
-                        }
-                        Some(IcedMatrixOperation::Sub) => {
-                            if self.matrices[0].get_size_unchecked()
-                                == self.matrices[1].get_size_unchecked()
-                            {
-                                self.result = Some(IcedMatrix::from_matrix(
-                                    self.matrices[0].get_matrix_unchecked()
-                                        - self.matrices[1].get_matrix_unchecked(),
-                                ))
-                            } else {
-                                self.error_message = Some(String::from("Matrix sizes not equal"));
-                            }
-                        }
-                        Some(IcedMatrixOperation::Mul) => {
-                            if self.matrices[0].get_size_unchecked().1
-                                == self.matrices[1].get_size_unchecked().0
-                            {
-                                self.result = Some(IcedMatrix::from_matrix(
-                                    self.matrices[0].get_matrix_unchecked()
-                                        * self.matrices[1].get_matrix_unchecked(),
-                                ))
-                            } else {
-                                self.error_message =
-                                    Some(String::from("column size of mat1 != row size of mat2"));
-                            }
-                        }
-                        None => {
-                            self.error_message = Some(String::from("please select an operation"))
-                        }
+                self.notes.clear();
+                self.intermediates.clear();
+                let unary = match self.selected_mat_op {
+                    Some(IcedMatrixOperation::Lll) => Some(self.reduce()),
+                    Some(IcedMatrixOperation::Hermite) => Some(self.hermite()),
+                    Some(IcedMatrixOperation::Smith) => Some(self.smith()),
+                    Some(op @ IcedMatrixOperation::Scale)
+                    | Some(op @ IcedMatrixOperation::Power)
+                    | Some(op @ IcedMatrixOperation::Exp)
+                    | Some(op @ IcedMatrixOperation::Function) => Some(self.scalar_operation(op)),
+                    _ => None,
+                };
+                if let Some(result) = unary {
+                    if let Err(message) = result {
+                        self.error_message = Some(message);
+                    }
+                } else if let Some(op) = self.selected_mat_op {
+                    if let Err(message) = self.combine(op) {
+                        self.error_message = Some(message);
                     }
                 } else {
-                    self.error_message = Some(String::from("Matrices not initialized"))
+                    self.error_message = Some(String::from("please select an operation"))
                 }
             }
             MatrixMessage::ReuseResult => {
                 if self.result.is_some() {
-                    self.matrices[0] = self.result.clone().unwrap()
+                    self.matrices[0] = self.result.clone().unwrap();
+                    self.exercise = None;
                 } else {
                     self.error_message = Some(String::from("Please calculate a result first"))
                 }
             }
+            MatrixMessage::AddSlot => self.matrices.push(IcedMatrix::new()),
+            MatrixMessage::RemoveSlot => {
+                if self.matrices.len() > 1 {
+                    self.matrices.pop();
+                }
+            }
+            MatrixMessage::EditOrder(content) => self.order_text = content,
+            MatrixMessage::EditScalar(content) => self.scalar_text = content,
+            MatrixMessage::FunctionSelected(function) => self.function = function,
+            MatrixMessage::EditDelta(content) => self.delta_text = content,
+            MatrixMessage::EditExercise(content) => self.exercise_text = content,
+            MatrixMessage::BuildKnapsack | MatrixMessage::BuildSmallRoot => {
+                self.error_message = None;
+                let exercise = if let MatrixMessage::BuildKnapsack = message {
+                    LatticeExercise::knapsack(&self.exercise_text)
+                } else {
+                    LatticeExercise::small_root(&self.exercise_text)
+                };
+                match exercise {
+                    Ok(exercise) => {
+                        let basis = exercise.basis();
+                        self.notes.clear();
+                        if !is_exact(&basis) {
+                            self.notes.push(String::from(ROUNDED_NOTE));
+                        }
+                        self.matrices[0] = IcedMatrix::from_matrix(matrix_of(&basis));
+                        self.exercise = Some(exercise);
+                        self.selected_mat_op = Some(IcedMatrixOperation::Lll);
+                    }
+                    Err(message) => self.error_message = Some(message),
+                }
+            }
+        }
+    }
+
+    /// the slots named in the order input, every slot if it is empty
+    fn operand_order(&self) -> Result<Vec<usize>, String> {
+        if self.order_text.trim().is_empty() {
+            return Ok((0..self.matrices.len()).collect());
         }
+        self.order_text
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|slot| !slot.is_empty())
+            .map(|slot| match slot.parse::<usize>() {
+                Ok(i) if i >= 1 && i <= self.matrices.len() => Ok(i - 1),
+                _ => Err(format!("there is no mat{}", slot)),
+            })
+            .collect()
     }
 
-    pub fn view(&mut self) -> Element<MatrixMessage> {
+    /// sums and differences left to right, products in the cheapest bracketing
+    fn combine(&mut self, op: IcedMatrixOperation) -> Result<(), String> {
+        let order = self.operand_order()?;
+        if order.len() < 2 {
+            return Err(String::from("please choose at least two matrices"));
+        }
+        if order.iter().any(|&i| !self.matrices[i].is_initialized()) {
+            return Err(String::from("Matrices not initialized"));
+        }
+        let operands: Vec<(String, DMatrix<f64>)> = order
+            .iter()
+            .map(|&i| {
+                (
+                    format!("mat{}", i + 1),
+                    self.matrices[i].get_matrix_unchecked(),
+                )
+            })
+            .collect();
+        let result = match op {
+            IcedMatrixOperation::Add | IcedMatrixOperation::Sub => {
+                if operands.iter().any(|(_, m)| m.size != operands[0].1.size) {
+                    return Err(String::from("Matrix sizes not equal"));
+                }
+                let sign = if op == IcedMatrixOperation::Add {
+                    "+"
+                } else {
+                    "-"
+                };
+                let (mut name, mut sum) = operands[0].clone();
+                for (next_name, next) in &operands[1..] {
+                    sum = if op == IcedMatrixOperation::Add {
+                        sum + next.clone()
+                    } else {
+                        sum - next.clone()
+                    };
+                    name = format!("{} {} {}", name, sign, next_name);
+                    self.intermediates.push((name.clone(), sum.clone()));
+                }
+                sum
+            }
+            IcedMatrixOperation::Mul => {
+                for pair in operands.windows(2) {
+                    if pair[0].1.size.1 != pair[1].1.size.0 {
+                        return Err(format!(
+                            "column size of {} != row size of {}",
+                            pair[0].0, pair[1].0
+                        ));
+                    }
+                }
+                let sizes: Vec<(usize, usize)> = operands.iter().map(|(_, m)| m.size).collect();
+                let chain = chain_order(&sizes);
+                let (name, product) = chain.evaluate(
+                    &operands,
+                    &|a: &DMatrix<f64>, b: &DMatrix<f64>| a.clone() * b.clone(),
+                    &mut self.intermediates,
+                );
+                self.notes.push(format!(
+                    "{} scalar multiplications for {}, {} from left to right",
+                    chain.cost, name, chain.naive_cost
+                ));
+                product
+            }
+            _ => return Err(String::from("please select an operation")),
+        };
+        // the last partial result is the result itself
+        self.intermediates.pop();
+        self.result = Some(IcedMatrix::from_matrix(result));
+        Ok(())
+    }
+
+    /// the number in the scalar input, default if it is empty
+    fn scalar(&self, default: Option<f64>) -> Result<f64, String> {
+        match (self.scalar_text.trim(), default) {
+            ("", Some(default)) => Ok(default),
+            (text, _) => text
+                .parse::<f64>()
+                .ok()
+                .filter(|k| k.is_finite())
+                .ok_or_else(|| String::from("k must be a finite number")),
+        }
+    }
+
+    /// k mat1, mat1^k, e^(t mat1) and f(mat1), computed on the rows of mat1
+    fn scalar_operation(&mut self, op: IcedMatrixOperation) -> Result<(), String> {
+        if !self.matrices[0].is_initialized() {
+            return Err(String::from("Matrix not initialized"));
+        }
+        let a = to_rows(&self.matrices[0].get_matrix_unchecked());
+        let result = match op {
+            IcedMatrixOperation::Scale => scale(&a, self.scalar(None)?),
+            IcedMatrixOperation::Power => {
+                let k = self
+                    .scalar_text
+                    .trim()
+                    .parse::<i64>()
+                    .map_err(|_| String::from("k must be a whole number"))?;
+                let (result, products) = power(&a, k)?;
+                self.notes.push(format!(
+                    "{} matrix products by repeated squaring{}",
+                    products,
+                    if k < 0 { " of the inverse" } else { "" }
+                ));
+                result
+            }
+            IcedMatrixOperation::Exp => {
+                let t = self.scalar(Some(1.0))?;
+                let (result, squarings) = exponential(&scale(&a, t))?;
+                self.notes.push(format!(
+                    "t = {}, Pade approximant of degree {} at t mat1 / 2^{}, squared {} times",
+                    t, PADE_DEGREE, squarings, squarings
+                ));
+                result
+            }
+            IcedMatrixOperation::Function => {
+                let diagonalization = Diagonalization::new(&a)?;
+                self.notes.push(format!(
+                    "mat1 = P D P^-1 with D = {}",
+                    format_rows_f64(&diagonalization.d())
+                ));
+                self.notes
+                    .push(format!("P = {}", format_rows_f64(&diagonalization.p)));
+                self.notes.push(format!(
+                    "P^-1 = {}",
+                    format_rows_f64(&diagonalization.p_inverse)
+                ));
+                diagonalization.apply(self.function)?
+            }
+            _ => return Err(String::from("please select an operation")),
+        };
+        self.result = Some(IcedMatrix::from_matrix(from_rows(&result)));
+        Ok(())
+    }
+
+    /// LLL on the rows of mat1 with the delta from its input, 3/4 if left empty
+    fn reduce(&mut self) -> Result<(), String> {
+        if !self.matrices[0].is_initialized() {
+            return Err(String::from("Matrix not initialized"));
+        }
+        let delta = if self.delta_text.trim().is_empty() {
+            DEFAULT_DELTA
+        } else {
+            self.delta_text
+                .trim()
+                .parse::<f64>()
+                .map_err(|_| String::from("delta must be a number"))?
+        };
+        // an exercise basis is kept exact, mat1 only shows it
+        let basis = match &self.exercise {
+            Some(exercise) => exercise.basis(),
+            None => rows_of(&self.matrices[0].get_matrix_unchecked())?,
+        };
+        let reduction = lll(basis, delta)?;
+        if !is_exact(&reduction.basis) {
+            self.notes.push(String::from(ROUNDED_NOTE));
+        }
+        self.notes.push(format!(
+            "Hadamard ratio {:.4} before and {:.4} after, 1 means orthogonal",
+            reduction.hadamard_before, reduction.hadamard_after
+        ));
+        self.notes
+            .extend(reduction.steps.iter().take(STEP_LOG_LIMIT).cloned());
+        if reduction.steps.len() > STEP_LOG_LIMIT {
+            self.notes.push(format!(
+                "... {} more steps",
+                reduction.steps.len() - STEP_LOG_LIMIT
+            ));
+        }
+        if let Some(exercise) = &self.exercise {
+            self.notes.extend(exercise.interpret(&reduction.basis));
+        }
+        self.result = Some(IcedMatrix::from_matrix(matrix_of(&reduction.basis)));
+        Ok(())
+    }
+
+    /// U mat1 = H, the notes show U
+    fn hermite(&mut self) -> Result<(), String> {
+        if !self.matrices[0].is_initialized() {
+            return Err(String::from("Matrix not initialized"));
+        }
+        let form = hermite_normal_form(&rows_of(&self.matrices[0].get_matrix_unchecked())?)?;
+        self.notes.push(format!("U = {}", format_rows(&form.u)));
+        self.notes.push(format!(
+            "rank {}, pivots in columns {:?}",
+            form.pivots.len(),
+            form.pivots.iter().map(|j| j + 1).collect::<Vec<usize>>()
+        ));
+        self.result = Some(IcedMatrix::from_matrix(matrix_of(&form.h)));
+        Ok(())
+    }
+
+    /// U mat1 V = D, the notes show U, V and the group the rows of mat1 present
+    fn smith(&mut self) -> Result<(), String> {
+        if !self.matrices[0].is_initialized() {
+            return Err(String::from("Matrix not initialized"));
+        }
+        let form = smith_normal_form(&rows_of(&self.matrices[0].get_matrix_unchecked())?)?;
+        self.notes.push(format!("U = {}", format_rows(&form.u)));
+        self.notes.push(format!("V = {}", format_rows(&form.v)));
+        self.notes.push(format!(
+            "Z^{} / rows of mat1 = {}",
+            form.v.len(),
+            form.structure()
+        ));
+        self.notes.extend(
+            form.generators()
+                .into_iter()
+                .map(|generator| format!("    generated by {}", generator)),
+        );
+        self.result = Some(IcedMatrix::from_matrix(matrix_of(&form.d)));
+        Ok(())
+    }
+
+    pub fn view(&mut self) -> Element<'_, MatrixMessage> {
         let title = Text::new("Matrices").size(30).color([0.0, 0.0, 0.0]);
         let matrices = self.matrices.iter_mut().enumerate().fold(
             Row::new().spacing(30).align_items(Align::Center),
             |row, (i, matrix)| {
                 row.push(
-                    matrix
-                        .view()
-                        .map(move |message| MatrixMessage::IcedMatrixMessage(i, message)),
+                    Column::new()
+                        .spacing(5)
+                        .align_items(Align::Center)
+                        .push(Text::new(format!("mat{}", i + 1)))
+                        .push(
+                            matrix
+                                .view()
+                                .map(move |message| MatrixMessage::IcedMatrixMessage(i, message)),
+                        ),
                 )
             },
         );
 
+        let slot_controls = Row::new()
+            .spacing(10)
+            .align_items(Align::Center)
+            .push(
+                Button::new(&mut self.add_slot_button, Text::new("Add slot"))
+                    .on_press(MatrixMessage::AddSlot),
+            )
+            .push(
+                Button::new(&mut self.remove_slot_button, Text::new("Remove slot"))
+                    .on_press(MatrixMessage::RemoveSlot),
+            )
+            .push(Text::new("Order, all slots if empty: "))
+            .push(
+                TextInput::new(
+                    &mut self.order_input,
+                    "1 2 3",
+                    &self.order_text,
+                    MatrixMessage::EditOrder,
+                )
+                .width(Length::Units(150)),
+            );
+
         // let mat_op_selector = Row::new()
         //     .spacing(20)
         //     .push(PickList::new(
@@ -174,10 +529,67 @@ impl MatrixCalculationState {
         //             .on_press(MatrixMessage::MatrixCalculate),
         //     );
 
+        let function = self.function;
+        let scalar_input = Column::new()
+            .spacing(5)
+            .width(Length::Units(200))
+            .push(Text::new("k, or t with 1 if empty"))
+            .push(TextInput::new(
+                &mut self.scalar_input,
+                "2",
+                &self.scalar_text,
+                MatrixMessage::EditScalar,
+            ))
+            .push(Text::new("f for f(mat1)"))
+            .push(MatrixFunction::ALL.iter().cloned().fold(
+                Column::new().spacing(5),
+                |column, option| {
+                    column.push(Radio::new(
+                        option,
+                        option,
+                        Some(function),
+                        MatrixMessage::FunctionSelected,
+                    ))
+                },
+            ));
+
+        let lattice_input = Column::new()
+            .spacing(5)
+            .width(Length::Units(350))
+            .push(Text::new("LLL delta, 0.75 if empty"))
+            .push(TextInput::new(
+                &mut self.delta_input,
+                "0.75",
+                &self.delta_text,
+                MatrixMessage::EditDelta,
+            ))
+            .push(Text::new("w_1 ... w_n; s  or  f(x); N; X"))
+            .push(TextInput::new(
+                &mut self.exercise_input,
+                "",
+                &self.exercise_text,
+                MatrixMessage::EditExercise,
+            ))
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .push(
+                        Button::new(&mut self.knapsack_button, Text::new("Knapsack lattice"))
+                            .on_press(MatrixMessage::BuildKnapsack),
+                    )
+                    .push(
+                        Button::new(&mut self.small_root_button, Text::new("Small root lattice"))
+                            .on_press(MatrixMessage::BuildSmallRoot),
+                    ),
+            );
+
         let mat_op_selector = Row::new()
             .padding(20)
+            .spacing(20)
             .align_items(Align::Center)
             .push(Self::selection_column(self.selected_mat_op))
+            .push(scalar_input)
+            .push(lattice_input)
             .push(
                 Column::new()
                     .align_items(Align::Center)
@@ -191,27 +603,31 @@ impl MatrixCalculationState {
                     ),
             );
 
+        let intermediates = self.intermediates.iter().fold(
+            Column::new().spacing(10).align_items(Align::Center),
+            |col, (name, matrix)| {
+                col.push(
+                    Row::new()
+                        .spacing(20)
+                        .align_items(Align::Center)
+                        .push(Text::new(name))
+                        .push(Self::matrix_grid(matrix)),
+                )
+            },
+        );
+
         let maybe_result = match &self.result {
             None => Row::new().push(Text::new("")),
-            Some(mat) => {
-                let string_mat = mat.get_matrix_unchecked().to_string_vec();
-                string_mat
-                    .data
-                    .iter()
-                    .fold(
-                        Row::new().spacing(10).align_items(Align::Center),
-                        |row, chunk| {
-                            row.push(
-                                chunk
-                                    .iter()
-                                    .fold(Column::new(), |col, item| col.push(Text::new(item))),
-                            )
-                        },
-                    )
-                    .into()
-            }
+            Some(mat) => Self::matrix_grid(&mat.get_matrix_unchecked()),
         };
 
+        let notes = self
+            .notes
+            .iter()
+            .fold(Column::new().spacing(5), |col, note| {
+                col.push(Text::new(note))
+            });
+
         let maybe_error = match &self.error_message {
             None => Text::new(""),
             Some(message) => Text::new(message).color([0.921, 0.039, 0.039]),
@@ -222,12 +638,29 @@ impl MatrixCalculationState {
             .spacing(20)
             .push(title)
             .push(mat_op_selector)
+            .push(slot_controls)
             .push(matrices)
+            .push(intermediates)
             .push(maybe_result)
+            .push(notes)
             .push(maybe_error)
             .into()
     }
 
+    fn matrix_grid<'a>(matrix: &DMatrix<f64>) -> Row<'a, MatrixMessage> {
+        let string_mat = matrix.to_string_vec();
+        string_mat.data.iter().fold(
+            Row::new().spacing(10).align_items(Align::Center),
+            |row, chunk| {
+                row.push(
+                    chunk
+                        .iter()
+                        .fold(Column::new(), |col, item| col.push(Text::new(item))),
+                )
+            },
+        )
+    }
+
     fn selection_column<'a>(selection: Option<IcedMatrixOperation>) -> Column<'a, MatrixMessage> {
         Column::new()
             .padding(5)
@@ -350,7 +783,7 @@ impl IcedMatrix {
         }
     }
 
-    pub fn view(&mut self) -> Element<IcedMatrixMessage> {
+    pub fn view(&mut self) -> Element<'_, IcedMatrixMessage> {
         match &mut self.state {
             IcedMatrixState::Uninitialized { new_button } => Column::new()
                 .align_items(Align::Center)
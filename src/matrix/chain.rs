@@ -0,0 +1,114 @@
+/// the cheapest bracketing of a matrix product, by dynamic programming over subchains
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainOrder {
+    /// scalar multiplications of the best bracketing
+    pub cost: usize,
+    /// scalar multiplications of ((A_1 A_2) A_3) ...
+    pub naive_cost: usize,
+    /// split[i][j] = k means A_i..A_j is best computed as (A_i..A_k)(A_k+1..A_j)
+    split: Vec<Vec<usize>>,
+}
+
+/// sizes are (rows, columns) and have to chain up
+pub fn chain_order(sizes: &[(usize, usize)]) -> ChainOrder {
+    let n = sizes.len();
+    let mut cost = vec![vec![0usize; n]; n];
+    let mut split = vec![vec![0usize; n]; n];
+    for length in 2..=n {
+        for i in 0..=n - length {
+            let j = i + length - 1;
+            let (k, best) = (i..j)
+                .map(|k| {
+                    (
+                        k,
+                        cost[i][k] + cost[k + 1][j] + sizes[i].0 * sizes[k].1 * sizes[j].1,
+                    )
+                })
+                .min_by_key(|&(_, c)| c)
+                .unwrap();
+            cost[i][j] = best;
+            split[i][j] = k;
+        }
+    }
+    let naive_cost = (1..n).map(|k| sizes[0].0 * sizes[k].0 * sizes[k].1).sum();
+    ChainOrder {
+        cost: if n == 0 { 0 } else { cost[0][n - 1] },
+        naive_cost,
+        split,
+    }
+}
+
+impl ChainOrder {
+    /// multiplies the operands in the best order, every partial product is logged with its bracketing
+    pub fn evaluate<T: Clone>(
+        &self,
+        operands: &[(String, T)],
+        mul: &impl Fn(&T, &T) -> T,
+        log: &mut Vec<(String, T)>,
+    ) -> (String, T) {
+        self.evaluate_range(operands, 0, operands.len() - 1, mul, log)
+    }
+
+    fn evaluate_range<T: Clone>(
+        &self,
+        operands: &[(String, T)],
+        i: usize,
+        j: usize,
+        mul: &impl Fn(&T, &T) -> T,
+        log: &mut Vec<(String, T)>,
+    ) -> (String, T) {
+        if i == j {
+            return operands[i].clone();
+        }
+        let k = self.split[i][j];
+        let (left_name, left) = self.evaluate_range(operands, i, k, mul, log);
+        let (right_name, right) = self.evaluate_range(operands, k + 1, j, mul, log);
+        let product = (
+            format!("({} {})", left_name, right_name),
+            mul(&left, &right),
+        );
+        log.push(product.clone());
+        product
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(sizes: &[(usize, usize)]) -> (ChainOrder, String, (usize, usize)) {
+        let order = chain_order(sizes);
+        let operands: Vec<(String, (usize, usize))> = sizes
+            .iter()
+            .enumerate()
+            .map(|(i, size)| (format!("mat{}", i + 1), *size))
+            .collect();
+        let mut log = Vec::new();
+        let (name, size) = order.evaluate(&operands, &|a, b| (a.0, b.1), &mut log);
+        assert_eq!(log.len(), sizes.len() - 1);
+        (order, name, size)
+    }
+
+    #[test]
+    fn textbook_chain() {
+        let (order, name, size) = evaluate(&[(10, 30), (30, 5), (5, 60)]);
+        assert_eq!(order.cost, 4500);
+        assert_eq!(order.naive_cost, 4500);
+        assert_eq!(name, "((mat1 mat2) mat3)");
+        assert_eq!(size, (10, 60));
+    }
+
+    #[test]
+    fn best_order_beats_left_to_right() {
+        let (order, name, size) = evaluate(&[(40, 20), (20, 30), (30, 10), (10, 30)]);
+        assert_eq!(order.cost, 26_000);
+        assert_eq!(order.naive_cost, 24_000 + 12_000 + 12_000);
+        assert_eq!(name, "((mat1 (mat2 mat3)) mat4)");
+        assert_eq!(size, (40, 30));
+        let (order, name, _) = evaluate(&[(5, 7)]);
+        assert_eq!(
+            (order.cost, order.naive_cost, name.as_str()),
+            (0, 0, "mat1")
+        );
+    }
+}
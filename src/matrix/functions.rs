@@ -0,0 +1,504 @@
+use glucose::DMatrix;
+
+/// relative size below which pivots, subdiagonals and eigenvalue gaps count as zero
+const TOLERANCE: f64 = 1e-10;
+/// QR steps before the eigenvalue search gives up
+const QR_ITERATION_LIMIT: usize = 10_000;
+/// degree of the diagonal Pade approximant of e^x
+pub const PADE_DEGREE: i32 = 6;
+
+/// the rows of a matrix, glucose stores the columns
+pub fn to_rows(matrix: &DMatrix<f64>) -> Vec<Vec<f64>> {
+    let (rows, columns) = matrix.size;
+    (0..rows)
+        .map(|i| (0..columns).map(|j| matrix.data[j][i]).collect())
+        .collect()
+}
+
+/// the inverse of to_rows
+pub fn from_rows(rows: &[Vec<f64>]) -> DMatrix<f64> {
+    let columns = rows.first().map_or(0, |row| row.len());
+    DMatrix::new(
+        (0..columns)
+            .map(|j| rows.iter().map(|row| row[j]).collect())
+            .collect(),
+    )
+}
+
+/// "[1.0000 0.5000; 0.0000 1.0000]"
+pub fn format_rows_f64(rows: &[Vec<f64>]) -> String {
+    let rows: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            let entries: Vec<String> = row.iter().map(|x| format!("{:.4}", x)).collect();
+            entries.join(" ")
+        })
+        .collect();
+    format!("[{}]", rows.join("; "))
+}
+
+pub fn identity(n: usize) -> Vec<Vec<f64>> {
+    (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect()
+}
+
+pub fn scale(a: &[Vec<f64>], k: f64) -> Vec<Vec<f64>> {
+    a.iter()
+        .map(|row| row.iter().map(|x| k * x).collect())
+        .collect()
+}
+
+fn add(a: &[Vec<f64>], b: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| x.iter().zip(y).map(|(x, y)| x + y).collect())
+        .collect()
+}
+
+pub fn multiply(a: &[Vec<f64>], b: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let columns = b.first().map_or(0, |row| row.len());
+    a.iter()
+        .map(|row| {
+            (0..columns)
+                .map(|j| row.iter().zip(b).map(|(x, b_row)| x * b_row[j]).sum())
+                .collect()
+        })
+        .collect()
+}
+
+/// the maximum absolute row sum
+pub fn norm(a: &[Vec<f64>]) -> f64 {
+    a.iter()
+        .map(|row| row.iter().map(|x| x.abs()).sum::<f64>())
+        .fold(0.0, f64::max)
+}
+
+fn check_square(a: &[Vec<f64>]) -> Result<usize, String> {
+    if a.is_empty() || a.iter().any(|row| row.len() != a.len()) {
+        Err(String::from("the matrix has to be square"))
+    } else {
+        Ok(a.len())
+    }
+}
+
+/// rounds entries that are noise next to the largest one to zero
+fn clean(mut a: Vec<Vec<f64>>) -> Vec<Vec<f64>> {
+    let size = norm(&a);
+    for x in a.iter_mut().flatten() {
+        if x.abs() <= TOLERANCE * size {
+            *x = 0.0;
+        }
+    }
+    a
+}
+
+/// solves A X = B by gaussian elimination with partial pivoting
+pub fn solve(a: &[Vec<f64>], b: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, String> {
+    let n = check_square(a)?;
+    let size = norm(a);
+    let mut a = a.to_vec();
+    let mut x = b.to_vec();
+    for j in 0..n {
+        let pivot = (j..n)
+            .max_by(|&p, &q| a[p][j].abs().total_cmp(&a[q][j].abs()))
+            .unwrap();
+        if a[pivot][j].abs() <= TOLERANCE * size {
+            return Err(String::from("the matrix is singular"));
+        }
+        a.swap(j, pivot);
+        x.swap(j, pivot);
+        for i in (0..n).filter(|&i| i != j) {
+            let factor = a[i][j] / a[j][j];
+            if factor != 0.0 {
+                let (a_j, x_j) = (a[j].clone(), x[j].clone());
+                a[i].iter_mut()
+                    .zip(&a_j)
+                    .for_each(|(y, z)| *y -= factor * z);
+                x[i].iter_mut()
+                    .zip(&x_j)
+                    .for_each(|(y, z)| *y -= factor * z);
+            }
+        }
+    }
+    Ok(x.iter()
+        .zip(&a)
+        .enumerate()
+        .map(|(i, (row, a_row))| row.iter().map(|y| y / a_row[i]).collect())
+        .collect())
+}
+
+pub fn inverse(a: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, String> {
+    solve(a, &identity(check_square(a)?))
+}
+
+/// A^k by repeated squaring, of the inverse for negative k, with the number of products
+pub fn power(a: &[Vec<f64>], k: i64) -> Result<(Vec<Vec<f64>>, usize), String> {
+    let n = check_square(a)?;
+    let mut base = if k < 0 { inverse(a)? } else { a.to_vec() };
+    let mut exp = k.unsigned_abs();
+    let mut result = identity(n);
+    let mut products = 0;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = multiply(&result, &base);
+            products += 1;
+        }
+        exp >>= 1;
+        if exp > 0 {
+            base = multiply(&base, &base);
+            products += 1;
+        }
+    }
+    Ok((result, products))
+}
+
+/// e^A by scaling and squaring: A / 2^s has norm at most 1/2,
+/// the Pade approximant N / D of e^x is used there and squared s times
+pub fn exponential(a: &[Vec<f64>]) -> Result<(Vec<Vec<f64>>, u32), String> {
+    let n = check_square(a)?;
+    // an infinite norm would ask for billions of squarings
+    let size = norm(a);
+    if !size.is_finite() || a.iter().flatten().any(|x| !x.is_finite()) {
+        return Err(String::from(
+            "e^A needs finite entries whose norm does not overflow",
+        ));
+    }
+    let squarings = if size > 0.5 {
+        (size / 0.5).log2().ceil() as u32
+    } else {
+        0
+    };
+    let a = scale(a, 0.5f64.powi(squarings as i32));
+    let q = PADE_DEGREE;
+    let mut c = 0.5;
+    let mut x = a.clone();
+    let mut numerator = add(&identity(n), &scale(&a, c));
+    let mut denominator = add(&identity(n), &scale(&a, -c));
+    for k in 2..=q {
+        c *= f64::from(q - k + 1) / f64::from(k * (2 * q - k + 1));
+        x = multiply(&a, &x);
+        numerator = add(&numerator, &scale(&x, c));
+        let sign = if k % 2 == 0 { c } else { -c };
+        denominator = add(&denominator, &scale(&x, sign));
+    }
+    let mut result = solve(&denominator, &numerator)?;
+    for _ in 0..squarings {
+        result = multiply(&result, &result);
+    }
+    Ok((clean(result), squarings))
+}
+
+/// the eigenvalues of the trailing 2 x 2 block of the leading n x n part, if real
+fn block_eigenvalues(h: &[Vec<f64>], n: usize) -> Option<(f64, f64)> {
+    let (a, b, c, d) = (
+        h[n - 2][n - 2],
+        h[n - 2][n - 1],
+        h[n - 1][n - 2],
+        h[n - 1][n - 1],
+    );
+    let half_trace = (a + d) / 2.0;
+    let discriminant = (a - d) * (a - d) / 4.0 + b * c;
+    if discriminant < 0.0 {
+        None
+    } else {
+        let root = discriminant.sqrt();
+        Some((half_trace + root, half_trace - root))
+    }
+}
+
+/// the shifted QR algorithm with deflation, fails on complex eigenvalues
+pub fn real_eigenvalues(a: &[Vec<f64>]) -> Result<Vec<f64>, String> {
+    let mut n = check_square(a)?;
+    let size = norm(a).max(f64::MIN_POSITIVE);
+    let mut h = a.to_vec();
+    let mut eigenvalues = Vec::new();
+    let mut iterations = 0;
+    let negligible =
+        |row: &[f64], columns: usize| row[..columns].iter().all(|x| x.abs() <= TOLERANCE * size);
+    while n > 0 {
+        if n == 1 {
+            eigenvalues.push(h[0][0]);
+            break;
+        }
+        if negligible(&h[n - 1], n - 1) {
+            eigenvalues.push(h[n - 1][n - 1]);
+            n -= 1;
+            continue;
+        }
+        if n == 2 || (negligible(&h[n - 1], n - 2) && negligible(&h[n - 2], n - 2)) {
+            let (first, second) = block_eigenvalues(&h, n)
+                .ok_or_else(|| String::from("the matrix has complex eigenvalues"))?;
+            eigenvalues.push(first);
+            eigenvalues.push(second);
+            n -= 2;
+            continue;
+        }
+        iterations += 1;
+        if iterations > QR_ITERATION_LIMIT {
+            return Err(String::from("the eigenvalues did not converge"));
+        }
+        // the eigenvalue of the trailing block closer to the corner, exceptional shifts now and then
+        let corner = h[n - 1][n - 1];
+        let mut shift = match block_eigenvalues(&h, n) {
+            Some((first, second)) if (first - corner).abs() < (second - corner).abs() => first,
+            Some((_, second)) => second,
+            None => corner,
+        };
+        if iterations % 11 == 0 {
+            shift = corner + h[n - 1][n - 2].abs();
+        }
+        // H - mu I = QR by Gram-Schmidt on the columns, then H = RQ + mu I
+        let shifted: Vec<Vec<f64>> = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| h[i][j] - if i == j { shift } else { 0.0 })
+                    .collect()
+            })
+            .collect();
+        let mut q = vec![vec![0.0; n]; n];
+        let mut r = vec![vec![0.0; n]; n];
+        for j in 0..n {
+            let mut v: Vec<f64> = (0..n).map(|i| shifted[i][j]).collect();
+            for k in 0..j {
+                r[k][j] = (0..n).map(|i| q[i][k] * shifted[i][j]).sum();
+                for i in 0..n {
+                    v[i] -= r[k][j] * q[i][k];
+                }
+            }
+            r[j][j] = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+            for i in 0..n {
+                q[i][j] = if r[j][j] > 0.0 { v[i] / r[j][j] } else { 0.0 };
+            }
+        }
+        let rq = multiply(&r, &q);
+        for i in 0..n {
+            for j in 0..n {
+                h[i][j] = rq[i][j] + if i == j { shift } else { 0.0 };
+            }
+        }
+    }
+    eigenvalues.sort_by(|x, y| y.total_cmp(x));
+    Ok(eigenvalues)
+}
+
+/// a basis of the kernel of A - lambda I from its reduced row echelon form
+fn eigenspace(a: &[Vec<f64>], lambda: f64) -> Vec<Vec<f64>> {
+    let n = a.len();
+    let size = norm(a).max(lambda.abs()).max(f64::MIN_POSITIVE);
+    let mut m: Vec<Vec<f64>> = (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| a[i][j] - if i == j { lambda } else { 0.0 })
+                .collect()
+        })
+        .collect();
+    let mut pivots = Vec::new();
+    for j in 0..n {
+        let r = pivots.len();
+        if r == n {
+            break;
+        }
+        let pivot = (r..n)
+            .max_by(|&p, &q| m[p][j].abs().total_cmp(&m[q][j].abs()))
+            .unwrap();
+        if m[pivot][j].abs() <= 1e-7 * size {
+            continue;
+        }
+        m.swap(r, pivot);
+        let scale = m[r][j];
+        m[r].iter_mut().for_each(|x| *x /= scale);
+        for i in (0..n).filter(|&i| i != r) {
+            let factor = m[i][j];
+            let pivot_row = m[r].clone();
+            m[i].iter_mut()
+                .zip(&pivot_row)
+                .for_each(|(x, y)| *x -= factor * y);
+        }
+        pivots.push(j);
+    }
+    (0..n)
+        .filter(|c| !pivots.contains(c))
+        .map(|c| {
+            let mut vector = vec![0.0; n];
+            vector[c] = 1.0;
+            for (row, &pivot) in m.iter().zip(&pivots) {
+                vector[pivot] = -row[c];
+            }
+            vector
+        })
+        .collect()
+}
+
+/// A = P D P^-1 with the eigenvectors as the columns of P
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagonalization {
+    pub eigenvalues: Vec<f64>,
+    pub p: Vec<Vec<f64>>,
+    pub p_inverse: Vec<Vec<f64>>,
+}
+
+impl Diagonalization {
+    pub fn new(a: &[Vec<f64>]) -> Result<Self, String> {
+        let n = check_square(a)?;
+        let eigenvalues = real_eigenvalues(a)?;
+        let gap = 1e-6 * norm(a).max(1.0);
+        let mut columns: Vec<Vec<f64>> = Vec::new();
+        let mut diagonal = Vec::new();
+        let mut i = 0;
+        while i < n {
+            // a cluster of numerically equal eigenvalues shares one eigenspace
+            let end = (i..n)
+                .find(|&j| (eigenvalues[j] - eigenvalues[i]).abs() > gap)
+                .unwrap_or(n);
+            let lambda = eigenvalues[i..end].iter().sum::<f64>() / (end - i) as f64;
+            let space = eigenspace(a, lambda);
+            if space.len() < end - i {
+                return Err(format!(
+                    "eigenvalue {:.4} has multiplicity {} but only {} independent eigenvectors",
+                    lambda,
+                    end - i,
+                    space.len()
+                ));
+            }
+            for vector in space.into_iter().take(end - i) {
+                columns.push(vector);
+                diagonal.push(lambda);
+            }
+            i = end;
+        }
+        let p: Vec<Vec<f64>> = (0..n)
+            .map(|r| columns.iter().map(|column| column[r]).collect())
+            .collect();
+        let p_inverse = inverse(&p)?;
+        Ok(Self {
+            eigenvalues: diagonal,
+            p,
+            p_inverse,
+        })
+    }
+
+    pub fn d(&self) -> Vec<Vec<f64>> {
+        let n = self.eigenvalues.len();
+        (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| if i == j { self.eigenvalues[i] } else { 0.0 })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// P f(D) P^-1
+    pub fn apply(&self, f: MatrixFunction) -> Result<Vec<Vec<f64>>, String> {
+        let n = self.eigenvalues.len();
+        let values = self
+            .eigenvalues
+            .iter()
+            .map(|&lambda| f.evaluate(lambda))
+            .collect::<Result<Vec<f64>, String>>()?;
+        let f_d: Vec<Vec<f64>> = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| if i == j { values[i] } else { 0.0 })
+                    .collect()
+            })
+            .collect();
+        Ok(clean(multiply(&multiply(&self.p, &f_d), &self.p_inverse)))
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum MatrixFunction {
+    #[default]
+    Exp,
+    Ln,
+    Sqrt,
+    Sin,
+    Cos,
+}
+
+impl From<MatrixFunction> for String {
+    fn from(f: MatrixFunction) -> Self {
+        String::from(match f {
+            MatrixFunction::Exp => "exp",
+            MatrixFunction::Ln => "ln",
+            MatrixFunction::Sqrt => "sqrt",
+            MatrixFunction::Sin => "sin",
+            MatrixFunction::Cos => "cos",
+        })
+    }
+}
+
+impl MatrixFunction {
+    pub const ALL: [MatrixFunction; 5] = [
+        MatrixFunction::Exp,
+        MatrixFunction::Ln,
+        MatrixFunction::Sqrt,
+        MatrixFunction::Sin,
+        MatrixFunction::Cos,
+    ];
+
+    pub fn evaluate(self, x: f64) -> Result<f64, String> {
+        match self {
+            MatrixFunction::Exp => Ok(x.exp()),
+            MatrixFunction::Ln if x > 0.0 => Ok(x.ln()),
+            MatrixFunction::Ln => Err(format!("ln is not defined at the eigenvalue {:.4}", x)),
+            MatrixFunction::Sqrt if x >= 0.0 => Ok(x.sqrt()),
+            MatrixFunction::Sqrt => Err(format!("sqrt is not real at the eigenvalue {:.4}", x)),
+            MatrixFunction::Sin => Ok(x.sin()),
+            MatrixFunction::Cos => Ok(x.cos()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &[Vec<f64>], b: &[Vec<f64>]) {
+        for (row_a, row_b) in a.iter().zip(b) {
+            for (x, y) in row_a.iter().zip(row_b) {
+                assert!((x - y).abs() <= 1e-9 * y.abs().max(1.0), "{} != {}", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn exponential_of_a_diagonal_matrix() {
+        let (result, _) = exponential(&[vec![1.0, 0.0], vec![0.0, 2.0]]).unwrap();
+        assert_close(&result, &[vec![1f64.exp(), 0.0], vec![0.0, 2f64.exp()]]);
+        let (result, squarings) = exponential(&[vec![-30.0, 0.0], vec![0.0, 10.0]]).unwrap();
+        assert!(squarings > 0);
+        assert_close(
+            &result,
+            &[vec![(-30f64).exp(), 0.0], vec![0.0, 10f64.exp()]],
+        );
+    }
+
+    #[test]
+    fn exponential_of_a_rotation_generator() {
+        let t = 2.5f64;
+        let (result, _) = exponential(&[vec![0.0, -t], vec![t, 0.0]]).unwrap();
+        assert_close(&result, &[vec![t.cos(), -t.sin()], vec![t.sin(), t.cos()]]);
+    }
+
+    #[test]
+    fn exponential_agrees_with_the_diagonalization() {
+        let a = vec![
+            vec![2.0, 1.0, 0.0],
+            vec![1.0, 3.0, 1.0],
+            vec![0.0, 1.0, 4.0],
+        ];
+        let (pade, _) = exponential(&a).unwrap();
+        let diagonal = Diagonalization::new(&a).unwrap();
+        assert_close(&pade, &diagonal.apply(MatrixFunction::Exp).unwrap());
+    }
+
+    #[test]
+    fn exponential_rejects_non_finite_entries() {
+        assert!(exponential(&[vec![f64::NAN]]).is_err());
+        assert!(exponential(&[vec![f64::INFINITY, 0.0], vec![0.0, 1.0]]).is_err());
+        assert!(exponential(&[vec![f64::MAX, f64::MAX], vec![f64::MAX, f64::MAX]]).is_err());
+    }
+}
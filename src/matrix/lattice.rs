@@ -0,0 +1,510 @@
+use crate::group_theory::number_theory::parse_big;
+use crate::group_theory::polynomial::{
+    evaluate, format_poly, parse_poly, rational_roots, Rational, Rationals,
+};
+use glucose::DMatrix;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{FromPrimitive, Signed, ToPrimitive, Zero};
+
+pub const DEFAULT_DELTA: f64 = 0.75;
+/// swaps and size reductions together, LLL terminates long before this on sane input
+const LLL_STEP_LIMIT: usize = 10_000;
+/// f64 holds integers exactly up to 2^53
+const EXACT_LIMIT: f64 = 9_007_199_254_740_992.0;
+
+/// the rows of an integer matrix, glucose stores the columns
+pub fn rows_of(matrix: &DMatrix<f64>) -> Result<Vec<Vec<BigInt>>, String> {
+    let (rows, columns) = matrix.size;
+    (0..rows)
+        .map(|i| {
+            (0..columns)
+                .map(|j| {
+                    let entry = matrix.data[j][i];
+                    if entry.fract() != 0.0 {
+                        Err(format!("{} is not an integer entry", entry))
+                    } else if entry.abs() >= EXACT_LIMIT {
+                        Err(format!(
+                            "{} is not exact, entries above 2^53 are rounded in a matrix slot",
+                            entry
+                        ))
+                    } else {
+                        BigInt::from_f64(entry).ok_or_else(|| String::from("entry out of range"))
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// whether matrix_of keeps every entry exact
+pub fn is_exact(rows: &[Vec<BigInt>]) -> bool {
+    rows.iter()
+        .flatten()
+        .all(|x| x.to_f64().is_some_and(|x| x.abs() < EXACT_LIMIT))
+}
+
+/// the inverse of rows_of, entries above 2^53 are rounded
+pub fn matrix_of(rows: &[Vec<BigInt>]) -> DMatrix<f64> {
+    let columns = rows.first().map_or(0, |row| row.len());
+    DMatrix::new(
+        (0..columns)
+            .map(|j| {
+                rows.iter()
+                    .map(|row| row[j].to_f64().unwrap_or(f64::NAN))
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn to_f64(row: &[BigInt]) -> Vec<f64> {
+    row.iter().map(|x| x.to_f64().unwrap_or(f64::NAN)).collect()
+}
+
+/// mu[i][j] = <b_i, b*_j> / <b*_j, b*_j> for j < i and the squared norms of the b*_i
+#[derive(Debug, Clone)]
+pub struct GramSchmidt {
+    pub mu: Vec<Vec<f64>>,
+    pub norms: Vec<f64>,
+}
+
+impl GramSchmidt {
+    pub fn new(basis: &[Vec<BigInt>]) -> Self {
+        let mut orthogonal: Vec<Vec<f64>> = Vec::with_capacity(basis.len());
+        let mut mu = vec![vec![0.0; basis.len()]; basis.len()];
+        let mut norms = Vec::with_capacity(basis.len());
+        for (i, row) in basis.iter().enumerate() {
+            let row = to_f64(row);
+            let mut star = row.clone();
+            for j in 0..i {
+                mu[i][j] = if norms[j] == 0.0 {
+                    0.0
+                } else {
+                    dot(&row, &orthogonal[j]) / norms[j]
+                };
+                for (s, o) in star.iter_mut().zip(&orthogonal[j]) {
+                    *s -= mu[i][j] * o;
+                }
+            }
+            norms.push(dot(&star, &star));
+            orthogonal.push(star);
+        }
+        Self { mu, norms }
+    }
+}
+
+/// (det L / prod |b_i|)^(1/n) with det L = prod |b*_i|, 1 for an orthogonal basis
+pub fn hadamard_ratio(basis: &[Vec<BigInt>]) -> f64 {
+    let gram_schmidt = GramSchmidt::new(basis);
+    let log_ratio: f64 = basis
+        .iter()
+        .zip(&gram_schmidt.norms)
+        .map(|(row, norm)| {
+            let row = to_f64(row);
+            0.5 * (norm.ln() - dot(&row, &row).ln())
+        })
+        .sum();
+    (log_ratio / basis.len() as f64).exp()
+}
+
+#[derive(Debug, Clone)]
+pub struct LllReduction {
+    pub basis: Vec<Vec<BigInt>>,
+    /// every size reduction and swap with the Gram-Schmidt data that caused it
+    pub steps: Vec<String>,
+    pub hadamard_before: f64,
+    pub hadamard_after: f64,
+}
+
+fn format_row(row: &[BigInt]) -> String {
+    let entries: Vec<String> = row.iter().map(|x| x.to_string()).collect();
+    format!("({})", entries.join(", "))
+}
+
+/// Lenstra-Lenstra-Lovasz on the rows, 1/4 < delta <= 1; the basis stays integral and only
+/// the Gram-Schmidt data is floating point
+pub fn lll(mut basis: Vec<Vec<BigInt>>, delta: f64) -> Result<LllReduction, String> {
+    if !(delta > 0.25 && delta <= 1.0) {
+        return Err(String::from("delta must lie in (1/4, 1]"));
+    }
+    if basis.is_empty() {
+        return Err(String::from("the lattice has no basis vectors"));
+    }
+    let gram_schmidt = GramSchmidt::new(&basis);
+    if gram_schmidt.norms.iter().any(|norm| *norm < 1e-9) {
+        return Err(String::from(
+            "the rows are linearly dependent, LLL needs a basis",
+        ));
+    }
+    let hadamard_before = hadamard_ratio(&basis);
+    let mut steps = Vec::new();
+    let mut gram_schmidt = gram_schmidt;
+    let mut k = 1;
+    while k < basis.len() {
+        if steps.len() > LLL_STEP_LIMIT {
+            return Err(format!("no reduced basis after {} steps", LLL_STEP_LIMIT));
+        }
+        // with large entries mu is only approximate, so one pass can leave |mu| > 1/2
+        // and the size reduction is repeated until it holds for the updated basis
+        let mut reduced = true;
+        while reduced {
+            reduced = false;
+            if steps.len() > LLL_STEP_LIMIT {
+                return Err(format!("no reduced basis after {} steps", LLL_STEP_LIMIT));
+            }
+            for j in (0..k).rev() {
+                let mu = gram_schmidt.mu[k][j];
+                if mu.abs() > 0.5 {
+                    reduced = true;
+                    let q = mu.round();
+                    let q_big = BigInt::from_f64(q).unwrap_or_default();
+                    let (head, tail) = basis.split_at_mut(k);
+                    for (x, y) in tail[0].iter_mut().zip(&head[j]) {
+                        *x -= &q_big * y;
+                    }
+                    steps.push(format!(
+                        "size reduction: mu_{},{} = {:.4}, b_{} -= {} b_{} gives {}",
+                        k + 1,
+                        j + 1,
+                        mu,
+                        k + 1,
+                        q,
+                        j + 1,
+                        format_row(&basis[k])
+                    ));
+                    gram_schmidt = GramSchmidt::new(&basis);
+                }
+            }
+        }
+        let mu = gram_schmidt.mu[k][k - 1];
+        let (norm, previous) = (gram_schmidt.norms[k], gram_schmidt.norms[k - 1]);
+        if norm >= (delta - mu * mu) * previous {
+            k += 1;
+        } else {
+            steps.push(format!(
+                "swap b_{} and b_{}: |b*_{}|^2 = {:.4} < (delta - mu_{},{}^2) |b*_{}|^2 = ({} - {:.4}^2) {:.4} = {:.4}",
+                k,
+                k + 1,
+                k + 1,
+                norm,
+                k + 1,
+                k,
+                k,
+                delta,
+                mu,
+                previous,
+                (delta - mu * mu) * previous
+            ));
+            basis.swap(k, k - 1);
+            gram_schmidt = GramSchmidt::new(&basis);
+            k = (k - 1).max(1);
+        }
+    }
+    Ok(LllReduction {
+        hadamard_after: hadamard_ratio(&basis),
+        basis,
+        steps,
+        hadamard_before,
+    })
+}
+
+/// a lattice whose short vectors solve a cryptanalytic problem
+#[derive(Debug, Clone)]
+pub enum LatticeExercise {
+    /// sum x_i w_i = s with x_i in {0, 1}, after Lagarias and Odlyzko
+    Knapsack { weights: Vec<BigInt>, sum: BigInt },
+    /// a root |x_0| <= X of f mod N, after Howgrave-Graham with the shifts N x^i
+    SmallRoot {
+        poly: Vec<BigInt>,
+        modulus: BigInt,
+        bound: BigInt,
+    },
+}
+
+impl LatticeExercise {
+    /// "w_1 w_2 ... w_n; s"
+    pub fn knapsack(text: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = text.split(';').collect();
+        if parts.len() != 2 {
+            return Err(String::from("a knapsack is given as w_1 w_2 ... w_n; s"));
+        }
+        let weights: Vec<BigInt> = parts[0]
+            .split_whitespace()
+            .map(parse_big)
+            .collect::<Option<_>>()
+            .ok_or("the weights must be numbers")?;
+        let sum = parse_big(parts[1]).ok_or("s must be a number")?;
+        if weights.len() < 2 {
+            return Err(String::from("a knapsack needs at least two weights"));
+        }
+        Ok(LatticeExercise::Knapsack { weights, sum })
+    }
+
+    /// "f(x); N; X"
+    pub fn small_root(text: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = text.split(';').collect();
+        if parts.len() != 3 {
+            return Err(String::from("a small root problem is given as f(x); N; X"));
+        }
+        let poly: Vec<BigInt> = parse_poly(parts[0], &Rationals)?
+            .iter()
+            .map(|c| {
+                if c.denom() == &BigInt::from(1) {
+                    Some(c.numer().clone())
+                } else {
+                    None
+                }
+            })
+            .collect::<Option<_>>()
+            .ok_or("f must have integer coefficients")?;
+        let modulus = parse_big(parts[1]).filter(|n| n > &BigInt::from(1));
+        let bound = parse_big(parts[2]).filter(|x| x.is_positive());
+        match (modulus, bound) {
+            (Some(modulus), Some(bound)) if poly.len() >= 2 => Ok(LatticeExercise::SmallRoot {
+                poly,
+                modulus,
+                bound,
+            }),
+            (Some(_), Some(_)) => Err(String::from("f must not be constant")),
+            _ => Err(String::from("N must be above 1 and X positive")),
+        }
+    }
+
+    pub fn basis(&self) -> Vec<Vec<BigInt>> {
+        match self {
+            // rows (e_i, n w_i) and (0, ..., 0, -n s), the solution is (x, 0)
+            LatticeExercise::Knapsack { weights, sum } => {
+                let n = weights.len();
+                let scale = BigInt::from(n);
+                let mut rows: Vec<Vec<BigInt>> = weights
+                    .iter()
+                    .enumerate()
+                    .map(|(i, w)| {
+                        let mut row = vec![BigInt::zero(); n + 1];
+                        row[i] = BigInt::from(1);
+                        row[n] = &scale * w;
+                        row
+                    })
+                    .collect();
+                let mut last = vec![BigInt::zero(); n + 1];
+                last[n] = -(&scale * sum);
+                rows.push(last);
+                rows
+            }
+            // the coefficients of N (xX)^i for i < deg f and of f(xX)
+            LatticeExercise::SmallRoot {
+                poly,
+                modulus,
+                bound,
+            } => {
+                let degree = poly.len() - 1;
+                let powers: Vec<BigInt> = (0..=degree).map(|i| bound.pow(i as u32)).collect();
+                let mut rows: Vec<Vec<BigInt>> = (0..degree)
+                    .map(|i| {
+                        let mut row = vec![BigInt::zero(); degree + 1];
+                        row[i] = modulus * &powers[i];
+                        row
+                    })
+                    .collect();
+                rows.push(poly.iter().zip(&powers).map(|(c, p)| c * p).collect());
+                rows
+            }
+        }
+    }
+
+    /// reads the reduced basis back as an answer
+    pub fn interpret(&self, reduced: &[Vec<BigInt>]) -> Vec<String> {
+        match self {
+            LatticeExercise::Knapsack { weights, sum } => {
+                let n = weights.len();
+                let solution = reduced.iter().find_map(|row| {
+                    if !row[n].is_zero() {
+                        return None;
+                    }
+                    // a solution may come out negated
+                    let sign = if row[..n].iter().any(|x| x.is_negative()) {
+                        BigInt::from(-1)
+                    } else {
+                        BigInt::from(1)
+                    };
+                    let x: Vec<BigInt> = row[..n].iter().map(|x| x * &sign).collect();
+                    let binary = x.iter().all(|x| x.is_zero() || x == &BigInt::from(1));
+                    let total: BigInt = x.iter().zip(weights).map(|(x, w)| x * w).sum();
+                    if binary && &total == sum {
+                        Some(x)
+                    } else {
+                        None
+                    }
+                });
+                match solution {
+                    Some(x) => {
+                        let chosen: Vec<String> = x
+                            .iter()
+                            .zip(weights)
+                            .filter(|(x, _)| !x.is_zero())
+                            .map(|(_, w)| w.to_string())
+                            .collect();
+                        vec![
+                            format!("knapsack solution x = {}", format_row(&x)),
+                            format!("{} = {}", chosen.join(" + "), sum),
+                        ]
+                    }
+                    None => vec![String::from(
+                        "no row of the reduced basis is a 0/1 solution, the density may be too high",
+                    )],
+                }
+            }
+            LatticeExercise::SmallRoot {
+                poly,
+                modulus,
+                bound,
+            } => {
+                // the shortest row is g(xX), g has the small roots of f over the integers
+                let g: Vec<Rational> = reduced[0]
+                    .iter()
+                    .enumerate()
+                    .map(|(i, c)| Rational::new(c.clone(), bound.pow(i as u32)))
+                    .collect();
+                let mut lines = vec![format!(
+                    "shortest vector as g(x) = {}",
+                    format_poly(&g, "x")
+                )];
+                // 0 is checked by hand, the rational root test needs g(0) != 0
+                let zeros = g.iter().take_while(|c| c.numer().is_zero()).count();
+                let mut candidates = if zeros > 0 {
+                    vec![Rational::integer(BigInt::zero())]
+                } else {
+                    Vec::new()
+                };
+                match rational_roots(&g[zeros..]) {
+                    Ok(roots) => candidates.extend(roots),
+                    Err(message) => lines.push(message),
+                }
+                let f: Vec<Rational> = poly.iter().cloned().map(Rational::integer).collect();
+                let roots: Vec<String> = candidates
+                    .iter()
+                    .filter(|root| root.denom() == &BigInt::from(1) && root.numer().abs() <= *bound)
+                    .filter(|root| {
+                        let value = evaluate(&f, *root, &Rationals);
+                        value.numer().mod_floor(modulus).is_zero()
+                    })
+                    .map(|root| root.numer().to_string())
+                    .collect();
+                lines.push(if roots.is_empty() {
+                    format!(
+                        "no root of g with |x| <= {} is a root of f mod {}",
+                        bound, modulus
+                    )
+                } else {
+                    format!("small roots of f mod {}: {}", modulus, roots.join(", "))
+                });
+                lines
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::normal_form::parse_rows;
+
+    fn determinant(rows: &[Vec<BigInt>]) -> BigInt {
+        if rows.len() == 1 {
+            return rows[0][0].clone();
+        }
+        (0..rows.len())
+            .map(|j| {
+                let minor: Vec<Vec<BigInt>> = rows[1..]
+                    .iter()
+                    .map(|row| [&row[..j], &row[j + 1..]].concat())
+                    .collect();
+                let term = &rows[0][j] * determinant(&minor);
+                if j % 2 == 0 {
+                    term
+                } else {
+                    -term
+                }
+            })
+            .sum()
+    }
+
+    #[test]
+    fn lll_returns_a_reduced_basis_of_the_same_lattice() {
+        for text in [
+            "[1 1 1; -1 0 2; 3 5 6]",
+            "[201 37; 1648 297]",
+            "[1 0 0 0 1234; 0 1 0 0 5678; 0 0 1 0 9101; 0 0 0 1 1121; 0 0 0 0 100003]",
+        ] {
+            let basis = parse_rows(text).unwrap();
+            let reduction = lll(basis.clone(), DEFAULT_DELTA).unwrap();
+            let reduced = &reduction.basis;
+            assert_eq!(
+                determinant(reduced).abs(),
+                determinant(&basis).abs(),
+                "{}",
+                text
+            );
+            let gram_schmidt = GramSchmidt::new(reduced);
+            for k in 1..reduced.len() {
+                for j in 0..k {
+                    assert!(gram_schmidt.mu[k][j].abs() <= 0.5 + 1e-9);
+                }
+                let mu = gram_schmidt.mu[k][k - 1];
+                assert!(
+                    gram_schmidt.norms[k]
+                        >= (DEFAULT_DELTA - mu * mu) * gram_schmidt.norms[k - 1] - 1e-9
+                );
+            }
+            assert!(reduction.hadamard_after >= reduction.hadamard_before - 1e-9);
+        }
+    }
+
+    #[test]
+    fn lll_rejects_dependent_rows() {
+        assert!(lll(parse_rows("[1 2; 2 4]").unwrap(), DEFAULT_DELTA).is_err());
+        assert!(lll(parse_rows("[1 0; 0 1]").unwrap(), 0.2).is_err());
+    }
+
+    #[test]
+    fn knapsack_with_entries_above_2_53_is_solved() {
+        let exercise = LatticeExercise::knapsack(
+            "870968805654166597 989824054622273689 796031015877463607 970196089301134314 \
+             751741364423228969 611094350337506720 968855419926405871 894619317271989211; \
+             3606051330759170389",
+        )
+        .unwrap();
+        let basis = exercise.basis();
+        assert!(!is_exact(&basis));
+        let reduction = lll(basis, 0.99).unwrap();
+        assert_eq!(
+            exercise.interpret(&reduction.basis)[0],
+            "knapsack solution x = (1, 0, 1, 1, 0, 0, 1, 0)"
+        );
+    }
+
+    #[test]
+    fn small_root_is_found() {
+        // f(3) = 0 mod N, the coefficients of f are of the size of N
+        let exercise =
+            LatticeExercise::small_root("x^2 + 23296726x + 30269876; 100160063; 10").unwrap();
+        let reduction = lll(exercise.basis(), DEFAULT_DELTA).unwrap();
+        let lines = exercise.interpret(&reduction.basis);
+        assert_eq!(lines.last().unwrap(), "small roots of f mod 100160063: 3");
+    }
+
+    #[test]
+    fn rows_of_reports_inexact_entries() {
+        let exact = matrix_of(&parse_rows("[1 -2; 3 4]").unwrap());
+        assert_eq!(rows_of(&exact).unwrap(), parse_rows("[1 -2; 3 4]").unwrap());
+        let large = matrix_of(&parse_rows("[18014398509481984 1]").unwrap());
+        assert!(rows_of(&large).unwrap_err().contains("2^53"));
+        let fraction = DMatrix::new(vec![vec![0.5]]);
+        assert!(rows_of(&fraction).unwrap_err().contains("not an integer"));
+    }
+}
@@ -0,0 +1,422 @@
+use crate::group_theory::abelian::{AbelianGroup, Slot};
+use crate::group_theory::number_theory::parse_big;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+
+/// the n x n identity as rows
+pub fn identity(n: usize) -> Vec<Vec<BigInt>> {
+    (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| {
+                    if i == j {
+                        BigInt::one()
+                    } else {
+                        BigInt::zero()
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+pub fn multiply(a: &[Vec<BigInt>], b: &[Vec<BigInt>]) -> Vec<Vec<BigInt>> {
+    let columns = b.first().map_or(0, |row| row.len());
+    a.iter()
+        .map(|row| {
+            (0..columns)
+                .map(|j| row.iter().zip(b).map(|(x, b_row)| x * &b_row[j]).sum())
+                .collect()
+        })
+        .collect()
+}
+
+/// "[1 0; 0 1]"
+pub fn format_rows(rows: &[Vec<BigInt>]) -> String {
+    let rows: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            let entries: Vec<String> = row.iter().map(|x| x.to_string()).collect();
+            entries.join(" ")
+        })
+        .collect();
+    format!("[{}]", rows.join("; "))
+}
+
+/// the inverse of format_rows, brackets and commas are optional
+pub fn parse_rows(text: &str) -> Result<Vec<Vec<BigInt>>, String> {
+    let inner = text.trim().trim_start_matches('[').trim_end_matches(']');
+    let rows = inner
+        .split(';')
+        .filter(|row| !row.trim().is_empty())
+        .map(|row| {
+            row.split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|entry| !entry.is_empty())
+                .map(|entry| parse_big(entry).ok_or_else(|| format!("{} is not an integer", entry)))
+                .collect::<Result<Vec<BigInt>, String>>()
+        })
+        .collect::<Result<Vec<Vec<BigInt>>, String>>()?;
+    check_rectangular(&rows)?;
+    Ok(rows)
+}
+
+fn check_rectangular(a: &[Vec<BigInt>]) -> Result<usize, String> {
+    let columns = a.first().map_or(0, |row| row.len());
+    if columns == 0 {
+        Err(String::from("the matrix is empty"))
+    } else if a.iter().any(|row| row.len() != columns) {
+        Err(String::from("all rows need the same number of entries"))
+    } else {
+        Ok(columns)
+    }
+}
+
+/// row_i -= k * row_j
+fn sub_row(rows: &mut [Vec<BigInt>], i: usize, j: usize, k: &BigInt) {
+    let row_j = rows[j].clone();
+    for (x, y) in rows[i].iter_mut().zip(&row_j) {
+        *x -= k * y;
+    }
+}
+
+/// col_i -= k * col_j
+fn sub_column(rows: &mut [Vec<BigInt>], i: usize, j: usize, k: &BigInt) {
+    for row in rows.iter_mut() {
+        let y = row[j].clone();
+        row[i] -= k * y;
+    }
+}
+
+fn swap_columns(rows: &mut [Vec<BigInt>], i: usize, j: usize) {
+    for row in rows.iter_mut() {
+        row.swap(i, j);
+    }
+}
+
+fn negate_row(rows: &mut [Vec<BigInt>], i: usize) {
+    for x in rows[i].iter_mut() {
+        *x = -&*x;
+    }
+}
+
+/// U A = H with U unimodular, H in row echelon form with positive pivots
+/// and the entries above every pivot reduced into [0, pivot)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HermiteForm {
+    pub h: Vec<Vec<BigInt>>,
+    pub u: Vec<Vec<BigInt>>,
+    /// the pivot columns, one per nonzero row of h
+    pub pivots: Vec<usize>,
+}
+
+pub fn hermite_normal_form(a: &[Vec<BigInt>]) -> Result<HermiteForm, String> {
+    let columns = check_rectangular(a)?;
+    let mut h = a.to_vec();
+    let mut u = identity(h.len());
+    let mut pivots = Vec::new();
+    for j in 0..columns {
+        let r = pivots.len();
+        if r == h.len() {
+            break;
+        }
+        // euclid down the column until a single nonzero entry is left in row r
+        loop {
+            let smallest = (r..h.len())
+                .filter(|&i| !h[i][j].is_zero())
+                .min_by(|&x, &y| h[x][j].abs().cmp(&h[y][j].abs()));
+            let smallest = match smallest {
+                Some(i) => i,
+                None => break,
+            };
+            h.swap(r, smallest);
+            u.swap(r, smallest);
+            let mut done = true;
+            for i in r + 1..h.len() {
+                if !h[i][j].is_zero() {
+                    let q = h[i][j].div_floor(&h[r][j]);
+                    sub_row(&mut h, i, r, &q);
+                    sub_row(&mut u, i, r, &q);
+                    done &= h[i][j].is_zero();
+                }
+            }
+            if done {
+                break;
+            }
+        }
+        if h[r][j].is_zero() {
+            continue;
+        }
+        if h[r][j].is_negative() {
+            negate_row(&mut h, r);
+            negate_row(&mut u, r);
+        }
+        for i in 0..r {
+            let q = h[i][j].div_floor(&h[r][j]);
+            sub_row(&mut h, i, r, &q);
+            sub_row(&mut u, i, r, &q);
+        }
+        pivots.push(j);
+    }
+    Ok(HermiteForm { h, u, pivots })
+}
+
+/// U A V = D with U, V unimodular and D diagonal with d_1 | d_2 | ... | d_r
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SmithForm {
+    pub d: Vec<Vec<BigInt>>,
+    pub u: Vec<Vec<BigInt>>,
+    pub v: Vec<Vec<BigInt>>,
+    /// kept alongside v, its rows are the new generators of Z^n
+    pub v_inverse: Vec<Vec<BigInt>>,
+}
+
+pub fn smith_normal_form(a: &[Vec<BigInt>]) -> Result<SmithForm, String> {
+    let columns = check_rectangular(a)?;
+    let mut d = a.to_vec();
+    let mut u = identity(d.len());
+    let mut v = identity(columns);
+    let mut v_inverse = identity(columns);
+    for t in 0..d.len().min(columns) {
+        loop {
+            let smallest = (t..d.len())
+                .flat_map(|i| (t..columns).map(move |j| (i, j)))
+                .filter(|&(i, j)| !d[i][j].is_zero())
+                .min_by(|&(a, b), &(x, y)| d[a][b].abs().cmp(&d[x][y].abs()));
+            let (i, j) = match smallest {
+                Some(position) => position,
+                None => break,
+            };
+            d.swap(t, i);
+            u.swap(t, i);
+            swap_columns(&mut d, t, j);
+            swap_columns(&mut v, t, j);
+            v_inverse.swap(t, j);
+
+            let mut done = true;
+            for i in t + 1..d.len() {
+                let q = d[i][t].div_floor(&d[t][t]);
+                sub_row(&mut d, i, t, &q);
+                sub_row(&mut u, i, t, &q);
+                done &= d[i][t].is_zero();
+            }
+            for j in t + 1..columns {
+                let q = d[t][j].div_floor(&d[t][t]);
+                // A -> A E changes V -> V E and V^-1 -> E^-1 V^-1
+                sub_column(&mut d, j, t, &q);
+                sub_column(&mut v, j, t, &q);
+                sub_row(&mut v_inverse, t, j, &-&q);
+                done &= d[t][j].is_zero();
+            }
+            if !done {
+                continue;
+            }
+            // the pivot has to divide the rest, otherwise pull the offending row up
+            let offending = (t + 1..d.len())
+                .find(|&i| (t + 1..columns).any(|j| !d[i][j].is_multiple_of(&d[t][t])));
+            match offending {
+                Some(i) => {
+                    sub_row(&mut d, t, i, &-BigInt::one());
+                    sub_row(&mut u, t, i, &-BigInt::one());
+                }
+                None => break,
+            }
+        }
+        if d[t][t].is_negative() {
+            negate_row(&mut d, t);
+            negate_row(&mut u, t);
+        }
+    }
+    Ok(SmithForm { d, u, v, v_inverse })
+}
+
+impl SmithForm {
+    /// the nonzero diagonal entries
+    pub fn diagonal(&self) -> Vec<BigInt> {
+        (0..self.d.len().min(self.v.len()))
+            .map(|i| self.d[i][i].clone())
+            .filter(|x| !x.is_zero())
+            .collect()
+    }
+
+    /// Z^n / (row lattice of A) has this many copies of Z
+    pub fn free_rank(&self) -> usize {
+        self.v.len() - self.diagonal().len()
+    }
+
+    /// the diagonal entries above 1, the torsion Z_d_1 x ... x Z_d_k
+    pub fn invariant_factors(&self) -> Vec<BigInt> {
+        self.diagonal()
+            .into_iter()
+            .filter(|x| !x.is_one())
+            .collect()
+    }
+
+    /// the torsion part as a group the abelian classifier can decompose
+    pub fn torsion(&self) -> AbelianGroup {
+        AbelianGroup {
+            slots: self
+                .invariant_factors()
+                .into_iter()
+                .map(|modulo| Slot {
+                    modulo,
+                    multiplicative: false,
+                })
+                .collect(),
+        }
+    }
+
+    /// "Z_2 x Z_6 x Z^2"
+    pub fn structure(&self) -> String {
+        let mut factors: Vec<String> = self
+            .invariant_factors()
+            .iter()
+            .map(|d| format!("Z_{}", d))
+            .collect();
+        match self.free_rank() {
+            0 => {}
+            1 => factors.push(String::from("Z")),
+            rank => factors.push(format!("Z^{}", rank)),
+        }
+        if factors.is_empty() {
+            String::from("trivial group")
+        } else {
+            factors.join(" x ")
+        }
+    }
+
+    /// x_1 ... x_n generate Z^n, the rows of A are relations between them,
+    /// one generator per factor of the structure written in the x_i
+    pub fn generators(&self) -> Vec<String> {
+        let diagonal = self.diagonal();
+        self.v_inverse
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| diagonal.get(*i).is_none_or(|d| !d.is_one()))
+            .map(|(i, row)| {
+                let order = diagonal
+                    .get(i)
+                    .map_or_else(|| String::from("infinite"), |d| d.to_string());
+                format!("{} (order {})", format_combination(row), order)
+            })
+            .collect()
+    }
+}
+
+/// "2 x_1 - x_3"
+fn format_combination(row: &[BigInt]) -> String {
+    let mut text = String::new();
+    for (i, c) in row.iter().enumerate().filter(|(_, c)| !c.is_zero()) {
+        let sign = if c.is_negative() { "-" } else { "+" };
+        if text.is_empty() {
+            if c.is_negative() {
+                text.push('-');
+            }
+        } else {
+            text.push_str(&format!(" {} ", sign));
+        }
+        if !c.abs().is_one() {
+            text.push_str(&format!("{} ", c.abs()));
+        }
+        text.push_str(&format!("x_{}", i + 1));
+    }
+    if text.is_empty() {
+        String::from("0")
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::group_theory::number_theory::XorShift;
+
+    /// small entries in [-range, range], about a third of them zero for rank deficient cases
+    fn random_matrix(
+        random: &mut XorShift,
+        rows: usize,
+        columns: usize,
+        range: i64,
+    ) -> Vec<Vec<BigInt>> {
+        (0..rows)
+            .map(|_| {
+                (0..columns)
+                    .map(|_| match random.next_u64() % 3 {
+                        0 => BigInt::zero(),
+                        _ => BigInt::from(
+                            (random.next_u64() % (2 * range as u64 + 1)) as i64 - range,
+                        ),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn matrices() -> Vec<Vec<Vec<BigInt>>> {
+        let mut random = XorShift(0x2545_f491_4f6c_dd1d);
+        let mut matrices = vec![
+            parse_rows("[2 4 4; -6 6 12; 10 -4 -16]").unwrap(),
+            parse_rows("[0 0; 0 0]").unwrap(),
+            parse_rows("[6 4]").unwrap(),
+        ];
+        for size in 1..=5 {
+            for columns in 1..=5 {
+                matrices.push(random_matrix(&mut random, size, columns, 9));
+            }
+        }
+        matrices
+    }
+
+    #[test]
+    fn hermite_form_is_reached_by_u() {
+        for a in matrices() {
+            let form = hermite_normal_form(&a).unwrap();
+            assert_eq!(multiply(&form.u, &a), form.h, "{}", format_rows(&a));
+            for (r, &j) in form.pivots.iter().enumerate() {
+                let pivot = &form.h[r][j];
+                assert!(pivot.is_positive());
+                assert!((0..r).all(|i| !form.h[i][j].is_negative() && &form.h[i][j] < pivot));
+                assert!((r + 1..a.len()).all(|i| form.h[i][j].is_zero()));
+            }
+        }
+    }
+
+    #[test]
+    fn smith_form_is_reached_by_u_and_v() {
+        for a in matrices() {
+            let form = smith_normal_form(&a).unwrap();
+            assert_eq!(
+                multiply(&multiply(&form.u, &a), &form.v),
+                form.d,
+                "{}",
+                format_rows(&a)
+            );
+            assert_eq!(multiply(&form.v, &form.v_inverse), identity(form.v.len()));
+            let diagonal = form.diagonal();
+            for (i, row) in form.d.iter().enumerate() {
+                for (j, entry) in row.iter().enumerate() {
+                    assert!(i == j || entry.is_zero());
+                }
+            }
+            for pair in diagonal.windows(2) {
+                assert!(
+                    pair[1].is_zero() || (!pair[0].is_zero() && pair[1].is_multiple_of(&pair[0]))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn smith_form_reads_off_the_quotient() {
+        let form = smith_normal_form(&parse_rows("[2 4 4; -6 6 12; 10 -4 -16]").unwrap()).unwrap();
+        assert_eq!(
+            form.diagonal(),
+            vec![BigInt::from(2), BigInt::from(6), BigInt::from(12)]
+        );
+        assert_eq!(form.structure(), "Z_2 x Z_6 x Z_12");
+        let free = smith_normal_form(&parse_rows("[2 0 0; 0 3 0]").unwrap()).unwrap();
+        assert_eq!(free.free_rank(), 1);
+        assert_eq!(free.invariant_factors(), vec![BigInt::from(6)]);
+    }
+}
@@ -1,5 +1,8 @@
 use crate::Message;
-use iced::{Container, Element, Font, HorizontalAlignment, Length, Text};
+use iced::{
+    canvas, container, Background, Color, Container, Element, Font, HorizontalAlignment, Length,
+    Point, Text, VerticalAlignment,
+};
 
 pub const ICONS: Font = Font::External {
     name: "Icons",
@@ -19,7 +22,7 @@ pub fn loading_message<'a>() -> Element<'a, Message> {
 }
 
 pub fn icon(unicode: char) -> Text {
-    Text::new(&unicode.to_string())
+    Text::new(unicode.to_string())
         .font(ICONS)
         .width(Length::Units(20))
         .horizontal_alignment(HorizontalAlignment::Center)
@@ -37,3 +40,83 @@ pub fn edit_icon() -> Text {
 pub fn delete_icon() -> Text {
     icon('\u{F1F8}')
 }
+
+/// background, border and text colour of a single grid cell
+#[derive(Debug, Clone, Copy)]
+pub struct CellStyle {
+    pub background: Color,
+    pub border_color: Color,
+    pub border_width: f32,
+    pub text_color: Color,
+}
+
+impl CellStyle {
+    pub fn new(background: Color) -> Self {
+        Self {
+            background,
+            border_color: Color::TRANSPARENT,
+            border_width: 0.0,
+            text_color: Color::BLACK,
+        }
+    }
+
+    pub fn border(mut self, color: Color, width: f32) -> Self {
+        self.border_color = color;
+        self.border_width = width;
+        self
+    }
+
+    pub fn text_color(mut self, color: Color) -> Self {
+        self.text_color = color;
+        self
+    }
+}
+
+impl container::StyleSheet for CellStyle {
+    fn style(&self) -> container::Style {
+        container::Style {
+            text_color: Some(self.text_color),
+            background: Some(Background::Color(self.background)),
+            border_radius: 0.0,
+            border_width: self.border_width,
+            border_color: self.border_color,
+        }
+    }
+}
+
+/// fixed size, centered, coloured box around some text
+pub fn cell<'a, M: 'a>(text: &str, width: u16, height: u16, style: CellStyle) -> Container<'a, M> {
+    Container::new(Text::new(text).size(14))
+        .width(Length::Units(width))
+        .height(Length::Units(height))
+        .center_x()
+        .center_y()
+        .style(style)
+}
+
+/// one of count evenly spaced pastel hues
+pub fn element_color(index: usize, count: usize) -> Color {
+    let hue = index as f32 / count.max(1) as f32 * 6.0;
+    let x = 1.0 - (hue % 2.0 - 1.0).abs();
+    let (r, g, b) = match hue as u32 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+    Color::from_rgb(0.55 + 0.45 * r, 0.55 + 0.45 * g, 0.55 + 0.45 * b)
+}
+
+/// text centred on a point of a canvas
+pub fn canvas_label(content: impl Into<String>, position: Point, size: f32) -> canvas::Text {
+    canvas::Text {
+        content: content.into(),
+        position,
+        size,
+        horizontal_alignment: HorizontalAlignment::Center,
+        vertical_alignment: VerticalAlignment::Center,
+        ..canvas::Text::default()
+    }
+}